@@ -0,0 +1,48 @@
+//! Compare cloning range sets, sharing their storage, with deep copies of them
+//!
+//! Run with `cargo bench --bench clone_ranges`.
+
+use std::{collections::HashMap, hint::black_box, time::Instant};
+
+use areq::{range::Ranges, version::pure::PureVersion};
+
+const ROUNDS: usize = 2000;
+
+fn main() {
+    let constraints: HashMap<String, Ranges<PureVersion>> = (0..200)
+        .map(|idx| {
+            let range = Ranges::from_str(&format!(
+                ">=1.{idx}.0 && <2.0.0-0 || >=3.{idx}.0-rc.1 && <4.0.0 || =={}.0.0",
+                idx + 10
+            ))
+            .unwrap();
+            (format!("package-{idx}"), range)
+        })
+        .collect();
+
+    // Every branch of a search clones the whole map, and narrows a single entry
+    let start = Instant::now();
+    for round in 0..ROUNDS {
+        let mut branch = constraints.clone();
+        let entry = branch.get_mut(&format!("package-{}", round % 200)).unwrap();
+        entry.and_assign(&Ranges::from(PureVersion::new(1, 50, 0)));
+        black_box(branch);
+    }
+    let shared = start.elapsed();
+
+    let start = Instant::now();
+    for round in 0..ROUNDS {
+        let mut branch: HashMap<_, _> = constraints
+            .iter()
+            .map(|(name, range)| (name.clone(), range.clone().map(|v| v)))
+            .collect();
+        let entry = branch.get_mut(&format!("package-{}", round % 200)).unwrap();
+        entry.and_assign(&Ranges::from(PureVersion::new(1, 50, 0)));
+        black_box(branch);
+    }
+    let deep = start.elapsed();
+
+    println!("deep copies:    {:?} per branch", deep / ROUNDS as u32);
+    println!("shared storage: {:?} per branch", shared / ROUNDS as u32);
+    println!("speedup: {:.1}x", deep.as_secs_f64() / shared.as_secs_f64());
+}
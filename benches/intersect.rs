@@ -0,0 +1,49 @@
+//! Compare intersecting many constraints with `and` on clones and with `and_assign`
+//!
+//! Run with `cargo bench --bench intersect`.
+
+use std::{hint::black_box, time::Instant};
+
+use areq::{range::Ranges, version::pure::PureVersion};
+
+const ROUNDS: usize = 2000;
+
+fn main() {
+    let constraints: Vec<Ranges<PureVersion>> = (0..200)
+        .map(|idx| {
+            Ranges::from_str(&format!(
+                ">=1.{idx}.0-rc.1 || <0.{idx}.0-alpha.{idx} || =={}.0.0-beta",
+                idx + 10
+            ))
+            .unwrap()
+        })
+        .collect();
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        let mut range = Ranges::full();
+        for constraint in &constraints {
+            range = black_box(range.clone().and(constraint));
+        }
+        black_box(range);
+    }
+    let cloning = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        let mut range = Ranges::full();
+        for constraint in &constraints {
+            range.and_assign(constraint);
+            black_box(&range);
+        }
+        black_box(range);
+    }
+    let assigning = start.elapsed();
+
+    println!("and on clones: {:?} per round", cloning / ROUNDS as u32);
+    println!("and_assign:    {:?} per round", assigning / ROUNDS as u32);
+    println!(
+        "speedup: {:.1}x",
+        cloning.as_secs_f64() / assigning.as_secs_f64()
+    );
+}
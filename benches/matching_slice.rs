@@ -0,0 +1,49 @@
+//! Compare matching a requirement against a sorted slice with binary search and with a linear filter
+//!
+//! Run with `cargo bench --bench matching_slice`.
+
+use std::{hint::black_box, time::Instant};
+
+use areq::{range::Ranges, version::pure::PureVersion};
+
+const ROUNDS: usize = 200;
+
+fn main() {
+    let published: Vec<PureVersion> = (0..100_000)
+        .map(|idx| PureVersion::new(idx / 10_000, idx / 100 % 100, idx % 100))
+        .collect();
+    let reqs: Vec<Ranges<PureVersion>> = (0..10)
+        .map(|idx| {
+            Ranges::from_str(&format!(
+                ">={idx}.{idx}.0 && <{idx}.{}.0 || =={}.0.0",
+                idx + 2,
+                idx + 1
+            ))
+            .unwrap()
+        })
+        .collect();
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for req in &reqs {
+            black_box(published.iter().filter(|v| req.contains(v)).count());
+        }
+    }
+    let linear = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for req in &reqs {
+            black_box(req.count_satisfying_sorted(&published));
+        }
+    }
+    let binary = start.elapsed();
+
+    let lookups = (ROUNDS * reqs.len()) as u32;
+    println!("linear filter: {:?} per requirement", linear / lookups);
+    println!("binary search: {:?} per requirement", binary / lookups);
+    println!(
+        "speedup: {:.1}x",
+        linear.as_secs_f64() / binary.as_secs_f64()
+    );
+}
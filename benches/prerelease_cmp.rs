@@ -0,0 +1,44 @@
+//! Compare sorting versions with common prerelease tags and with uncommon ones
+//!
+//! Run with `cargo bench --bench prerelease_cmp`.
+
+use std::{hint::black_box, time::Instant};
+
+use areq::version::pure::PureVersion;
+
+const ROUNDS: usize = 20;
+
+fn versions(tags: &[&str]) -> Vec<PureVersion> {
+    (0..100_000u64)
+        .map(|idx| {
+            let tag = tags[(idx * 7919 % tags.len() as u64) as usize];
+            format!("1.{}.0-{tag}.{}", idx % 3, idx % 5)
+                .parse()
+                .unwrap()
+        })
+        .collect()
+}
+
+fn time_sort(versions: &[PureVersion]) -> std::time::Duration {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        let mut versions = versions.to_vec();
+        versions.sort_unstable();
+        black_box(versions);
+    }
+    start.elapsed() / ROUNDS as u32
+}
+
+fn main() {
+    let common = versions(&["alpha", "beta", "rc", "dev", "pre"]);
+    let uncommon = versions(&["alphx", "betx", "rx", "dex", "prx"]);
+
+    let common = time_sort(&common);
+    let uncommon = time_sort(&uncommon);
+    println!("common tags:   {common:?} per sort");
+    println!("uncommon tags: {uncommon:?} per sort");
+    println!(
+        "speedup: {:.1}x",
+        uncommon.as_secs_f64() / common.as_secs_f64()
+    );
+}
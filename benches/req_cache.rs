@@ -0,0 +1,47 @@
+//! Compare parsing requirements with getting them from a [`ReqCache`]
+//!
+//! Run with `cargo bench --bench req_cache`.
+
+use std::{hint::black_box, num::NonZeroUsize, time::Instant};
+
+use areq::{cache::ReqCache, range::Ranges, version::pure::PureVersion};
+
+const ROUNDS: usize = 200;
+
+fn main() {
+    let reqs: Vec<_> = (0..300)
+        .map(|idx| {
+            format!(
+                ">={}.{}.0 && <{}.0.0 || =={idx}.0.0-rc.1",
+                idx / 10,
+                idx % 10,
+                idx / 10 + 1
+            )
+        })
+        .collect();
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for req in &reqs {
+            black_box(Ranges::<PureVersion>::from_str(req).unwrap());
+        }
+    }
+    let parsing = start.elapsed();
+
+    let cache = ReqCache::new(NonZeroUsize::new(reqs.len()).unwrap());
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for req in &reqs {
+            black_box(cache.get_or_parse(req).unwrap());
+        }
+    }
+    let cached = start.elapsed();
+
+    let lookups = (ROUNDS * reqs.len()) as u32;
+    println!("parsing: {:?} per requirement", parsing / lookups);
+    println!("cached:  {:?} per requirement", cached / lookups);
+    println!(
+        "speedup: {:.1}x",
+        parsing.as_secs_f64() / cached.as_secs_f64()
+    );
+}
@@ -1,27 +1,28 @@
-use areq::range::Ranges;
-use areq::version::pure::PureVersion;
+use std::io::IsTerminal;
+
 use rustyline::error::ReadlineError;
 use rustyline::{DefaultEditor, Result};
 
+mod session;
+
+use session::Session;
+
 fn main() -> Result<()> {
+    // Piped input is executed without the line editor
+    if !std::io::stdin().is_terminal() {
+        session::run(std::io::stdin().lock(), std::io::stdout())?;
+        return Ok(());
+    }
+
     let mut rl = DefaultEditor::new()?;
-    println!("Press Ctrl-D to exit");
+    let mut session = Session::new();
+    println!("Type `help` for the list of commands, press Ctrl-D to exit");
     loop {
         let readline = rl.readline(">> ");
         match readline {
             Ok(line) => {
                 rl.add_history_entry(&line)?;
-
-                match Ranges::<PureVersion>::from_str(&line) {
-                    Ok(range) => println!("Ranges: {}", range),
-                    Err(errs) => {
-                        println!("Invalid ranges");
-
-                        for err in errs {
-                            println!("  - {}", err);
-                        }
-                    }
-                }
+                session.execute(&line, &mut std::io::stdout())?;
             }
             Err(ReadlineError::Interrupted) => {
                 println!("Interrupted");
@@ -0,0 +1,185 @@
+//! State and commands of the ranges REPL
+
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+use areq::{range::Ranges, version::pure::PureVersion};
+
+const HELP: &str = "\
+Commands:
+  parse [<slot> =] <req>    parse a requirement and store it (default slot `_`)
+  contains <req> ; <ver>    check if a version matches a requirement
+  and <req> ; <req>         intersection of two requirements, stored in `_`
+  or <req> ; <req>          union of two requirements, stored in `_`
+  not <req>                 complement of a requirement, stored in `_`
+  show [<slot>]             show a slot as constraints and as intervals
+  help                      show this message
+Requirements can be written inline or refer to a slot as `$<slot>`.
+A line without a command is parsed as a requirement.";
+
+/// Slot written by commands that do not name one
+const LAST: &str = "_";
+
+/// A command of the REPL
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Command<'s> {
+    Parse { slot: &'s str, req: &'s str },
+    Contains { req: &'s str, version: &'s str },
+    And(&'s str, &'s str),
+    Or(&'s str, &'s str),
+    Not(&'s str),
+    Show(&'s str),
+    Help,
+}
+
+impl<'s> Command<'s> {
+    fn parse(line: &'s str) -> Result<Self, String> {
+        let line = line.trim();
+        let (command, args) = line
+            .split_once(char::is_whitespace)
+            .map_or((line, ""), |(command, args)| (command, args.trim()));
+        let pair = |args: &'s str| {
+            args.split_once(';')
+                .map(|(a, b)| (a.trim(), b.trim()))
+                .ok_or_else(|| format!("`{command}` needs two arguments separated by `;`"))
+        };
+
+        Ok(match command {
+            "parse" => match args.split_once('=') {
+                Some((slot, req)) if is_slot_name(slot.trim()) => Command::Parse {
+                    slot: slot.trim(),
+                    req: req.trim(),
+                },
+                _ => Command::Parse {
+                    slot: LAST,
+                    req: args,
+                },
+            },
+            "contains" => {
+                let (req, version) = pair(args)?;
+                Command::Contains { req, version }
+            }
+            "and" => pair(args).map(|(a, b)| Command::And(a, b))?,
+            "or" => pair(args).map(|(a, b)| Command::Or(a, b))?,
+            "not" => Command::Not(args),
+            "show" if args.is_empty() => Command::Show(LAST),
+            "show" => Command::Show(args.strip_prefix('$').unwrap_or(args)),
+            "help" => Command::Help,
+            _ => Command::Parse {
+                slot: LAST,
+                req: line,
+            },
+        })
+    }
+}
+
+fn is_slot_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Named requirements kept between commands
+#[derive(Debug, Default)]
+pub struct Session {
+    slots: BTreeMap<String, Ranges<PureVersion>>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Execute a line, writing the result or the error to `out`
+    pub fn execute(&mut self, line: &str, out: &mut impl Write) -> io::Result<()> {
+        if line.trim().is_empty() {
+            return Ok(());
+        }
+        match self.try_execute(line) {
+            Ok(output) => writeln!(out, "{output}"),
+            Err(error) => writeln!(out, "{error}"),
+        }
+    }
+
+    fn try_execute(&mut self, line: &str) -> Result<String, String> {
+        Ok(match Command::parse(line)? {
+            Command::Parse { slot, req } => {
+                let req = self.operand(req)?;
+                self.store(slot, req)
+            }
+            Command::Contains { req, version } => {
+                let req = self.operand(req)?;
+                let version = PureVersion::from_str(version)
+                    .map_err(|err| format!("Invalid version: {err}"))?;
+                req.contains(&version).to_string()
+            }
+            Command::And(a, b) => {
+                let req = self.operand(a)?.and(&self.operand(b)?);
+                self.store(LAST, req)
+            }
+            Command::Or(a, b) => {
+                let req = self.operand(a)?.or(&self.operand(b)?);
+                self.store(LAST, req)
+            }
+            Command::Not(req) => {
+                let req = self.operand(req)?.not();
+                self.store(LAST, req)
+            }
+            Command::Show(slot) => {
+                let req = self.slot(slot)?;
+                let intervals = if req.is_empty() {
+                    "(none)".to_owned()
+                } else {
+                    req.intervals()
+                        .map(|(start, end)| {
+                            let start =
+                                start.map_or("(-inf".to_owned(), |start| format!("[{start}"));
+                            let end = end.map_or("+inf)".to_owned(), |end| format!("{end})"));
+                            format!("{start}, {end}")
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" u ")
+                };
+                format!("constraints: {req}\nintervals:   {intervals}")
+            }
+            Command::Help => HELP.to_owned(),
+        })
+    }
+
+    fn store(&mut self, slot: &str, req: Ranges<PureVersion>) -> String {
+        let output = format!("{slot} = {req}");
+        self.slots.insert(slot.to_owned(), req);
+        output
+    }
+
+    fn slot(&self, name: &str) -> Result<Ranges<PureVersion>, String> {
+        self.slots
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown slot `{name}`"))
+    }
+
+    /// Resolve a slot reference, or parse an inline requirement
+    fn operand(&self, operand: &str) -> Result<Ranges<PureVersion>, String> {
+        if let Some(name) = operand.strip_prefix('$') {
+            return self.slot(name);
+        }
+        Ranges::from_str(operand).map_err(|errs| {
+            let mut message = "Invalid ranges".to_owned();
+            for err in errs {
+                message.push_str(&format!("\n  - {err}"));
+            }
+            message
+        })
+    }
+}
+
+/// Run a non-interactive session, one command per line
+pub fn run(input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let mut session = Session::new();
+    for line in input.lines() {
+        session.execute(&line?, &mut output)?;
+    }
+    Ok(())
+}
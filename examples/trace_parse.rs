@@ -0,0 +1,28 @@
+//! Show the decisions of the range parser
+//!
+//! Run with `RUST_LOG=areq=trace cargo run --example trace_parse --features tracing`,
+//! optionally passing the range to parse as argument.
+
+use areq::{range::Ranges, version::pure::PureVersion};
+use tracing_subscriber::EnvFilter;
+
+fn main() {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_target(false)
+        .without_time()
+        .init();
+
+    let input = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| ">=1.0.0 && (<1.2.0 || >1.4.0) || ==2.0.0-rc.1".to_owned());
+    match Ranges::<PureVersion>::from_str(&input) {
+        Ok(range) => println!("Parsed: {range}"),
+        Err(errors) => {
+            println!("Invalid range");
+            for error in errors {
+                println!("  - {error}");
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+#ifndef AREQ_H
+#define AREQ_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The call succeeded
+#define AREQ_OK 0
+
+// A required pointer was null
+#define AREQ_ERR_NULL -1
+
+// A string was not valid UTF-8
+#define AREQ_ERR_UTF8 -2
+
+// A version or requirement could not be parsed
+#define AREQ_ERR_PARSE -3
+
+// The library panicked, this is a bug
+#define AREQ_ERR_PANIC -4
+
+// A parsed requirement
+typedef struct AreqReq AreqReq;
+
+// A parsed version
+typedef struct AreqVersion AreqVersion;
+
+// Parse a version, storing it in `*out`
+//
+// On failure `*out` is set to null, if `out` is not null.
+//
+// # Safety
+// `s` must be null or point to a nul-terminated string.
+// `out` must be null or valid for writes.
+int areq_version_parse(const char *s, struct AreqVersion **out);
+
+// Release a version. Null is ignored.
+//
+// # Safety
+// `version` must be null or returned by [`areq_version_parse`], and not already released.
+void areq_version_free(struct AreqVersion *version);
+
+// Compare two versions by precedence, storing `-1`, `0` or `1` in `*out`
+//
+// Build metadata is ignored, as it does not contribute to the precedence.
+//
+// # Safety
+// Each pointer must be null or valid: `a` and `b` for reads, `out` for writes.
+int areq_version_cmp(const struct AreqVersion *a, const struct AreqVersion *b, int *out);
+
+// Parse a requirement, storing it in `*out`
+//
+// On failure `*out` is set to null, if `out` is not null.
+//
+// # Safety
+// `s` must be null or point to a nul-terminated string.
+// `out` must be null or valid for writes.
+int areq_req_parse(const char *s, struct AreqReq **out);
+
+// Release a requirement. Null is ignored.
+//
+// # Safety
+// `req` must be null or returned by [`areq_req_parse`], and not already released.
+void areq_req_free(struct AreqReq *req);
+
+// Check if a version matches a requirement, with the default prerelease policy
+//
+// Returns `1` if it matches, `0` if it does not, or a negative error code.
+//
+// # Safety
+// `req` and `version` must be null or valid for reads.
+int areq_req_matches(const struct AreqReq *req, const struct AreqVersion *version);
+
+// Message of the last error on the calling thread, or null if there was none
+//
+// The string is owned by the library, and is valid until the next failing call
+// on the same thread.
+const char *areq_last_error_message(void);
+
+#endif  /* AREQ_H */
@@ -0,0 +1,187 @@
+//! Command line interface to check versions against requirements
+
+use std::{
+    io::{self, BufRead, Write},
+    process::ExitCode,
+};
+
+use areq::{
+    cargo, npm,
+    range::Ranges,
+    version::{
+        ParseOptions, Version,
+        pure::{PrereleasePolicy, PureVersion},
+    },
+};
+use clap::{Parser, Subcommand, ValueEnum};
+
+/// Check versions against requirements
+///
+/// Commands reading versions take one per line from stdin, and print the matching lines unchanged.
+#[derive(Debug, Parser)]
+#[command(version)]
+struct Cli {
+    /// How prerelease versions are matched
+    #[arg(long, value_enum, default_value_t, global = true)]
+    prereleases: Prereleases,
+    /// Accept `v` prefixed versions, leading zeros, npm and Cargo requirements,
+    /// and skip the input lines that are not versions
+    #[arg(long, global = true)]
+    lenient: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Exit successfully if the version matches the requirement
+    Check { version: String, req: String },
+    /// Print the versions matching the requirement
+    Filter { req: String },
+    /// Print the versions sorted by precedence
+    Sort {
+        /// Sort from the highest version
+        #[arg(long)]
+        reverse: bool,
+    },
+    /// Print the highest version matching the requirement, failing if there is none
+    Max { req: String },
+}
+
+/// Command line mirror of [`PrereleasePolicy`]
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+enum Prereleases {
+    /// Prereleases match only when explicitly requested
+    #[default]
+    Explicit,
+    /// Prereleases match like any other version
+    Include,
+    /// Prereleases never match
+    Exclude,
+}
+
+impl From<Prereleases> for PrereleasePolicy {
+    fn from(value: Prereleases) -> Self {
+        match value {
+            Prereleases::Explicit => PrereleasePolicy::Explicit,
+            Prereleases::Include => PrereleasePolicy::Include,
+            Prereleases::Exclude => PrereleasePolicy::Exclude,
+        }
+    }
+}
+
+/// Exit code of errors, the same used by `clap` for invalid arguments
+const ERROR: u8 = 2;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli, io::stdin().lock(), io::stdout().lock()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(ERROR)
+        }
+    }
+}
+
+/// Run the command, returning whether it succeeded
+fn run(cli: &Cli, input: impl BufRead, mut output: impl Write) -> Result<bool, String> {
+    let policy = cli.prereleases.into();
+    let io_error = |err: io::Error| format!("I/O error: {err}");
+
+    match &cli.command {
+        Command::Check { version, req } => {
+            let version = cli.version(version)?;
+            Ok(cli
+                .requirement(req)?
+                .contains_with_policy(&version.pure, policy))
+        }
+        Command::Filter { req } => {
+            let req = cli.requirement(req)?;
+            for (line, version) in cli.versions(input)? {
+                if req.contains_with_policy(&version.pure, policy) {
+                    writeln!(output, "{line}").map_err(io_error)?;
+                }
+            }
+            Ok(true)
+        }
+        Command::Sort { reverse } => {
+            let mut versions = cli.versions(input)?;
+            versions.sort_by(|(_, a), (_, b)| a.cmp_including_build(b));
+            if *reverse {
+                versions.reverse();
+            }
+            for (line, _) in versions {
+                writeln!(output, "{line}").map_err(io_error)?;
+            }
+            Ok(true)
+        }
+        Command::Max { req } => {
+            let req = cli.requirement(req)?;
+            let max = cli
+                .versions(input)?
+                .into_iter()
+                .filter(|(_, version)| req.contains_with_policy(&version.pure, policy))
+                .max_by(|(_, a), (_, b)| a.cmp_including_build(b));
+            match max {
+                Some((line, _)) => {
+                    writeln!(output, "{line}").map_err(io_error)?;
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+    }
+}
+
+impl Cli {
+    fn version(&self, s: &str) -> Result<Version, String> {
+        let s = s.trim();
+        let parsed = if self.lenient {
+            let options = ParseOptions {
+                allow_leading_zeros: true,
+                ..Default::default()
+            };
+            Version::parse_with_options(s.strip_prefix(['v', 'V']).unwrap_or(s), &options)
+        } else {
+            s.parse()
+        };
+        parsed.map_err(|err| format!("Invalid version `{s}`: {err}"))
+    }
+
+    /// Parse a requirement, falling back to the npm and Cargo dialects if lenient
+    fn requirement(&self, s: &str) -> Result<Ranges<PureVersion>, String> {
+        let errors = match Ranges::from_str(s) {
+            Ok(req) => return Ok(req),
+            Err(errors) => errors,
+        };
+        if self.lenient
+            && let Some(req) = npm::parse_range(s)
+                .ok()
+                .or_else(|| cargo::parse_requirement(s).ok())
+        {
+            return Ok(req);
+        }
+        let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+        Err(format!("Invalid requirement `{s}`: {}", errors.join("; ")))
+    }
+
+    /// Read the versions from the input, together with their trimmed lines
+    fn versions(&self, input: impl BufRead) -> Result<Vec<(String, Version)>, String> {
+        let mut versions = vec![];
+        for (idx, line) in input.lines().enumerate() {
+            let line = line.map_err(|err| format!("I/O error: {err}"))?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            match self.version(line) {
+                Ok(version) => versions.push((line.to_owned(), version)),
+                Err(_) if self.lenient => (),
+                Err(err) => return Err(format!("line {}: {err}", idx + 1)),
+            }
+        }
+        Ok(versions)
+    }
+}
@@ -0,0 +1,129 @@
+//! Caching of parsed requirements
+//!
+//! Parsing is far slower than a hash lookup, so services receiving the same requirement
+//! strings over and over can keep the parsed sets in a [`ReqCache`].
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use chumsky::error::Rich;
+use itertools::Itertools;
+use snafu::Snafu;
+
+use crate::{range::Ranges, version::pure::PureVersion};
+
+#[cfg(test)]
+mod tests;
+
+/// A thread safe cache of parsed requirements, evicting the least recently used
+#[derive(Debug)]
+pub struct ReqCache {
+    capacity: NonZeroUsize,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// Parsed requirements, with the tick of their last use
+    entries: HashMap<String, (Arc<Ranges<PureVersion>>, u64)>,
+    /// Keys by the tick of their last use, from the least recent
+    by_use: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+impl ReqCache {
+    /// Create a cache keeping at most `capacity` requirements
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::default(),
+        }
+    }
+
+    /// Get the parsed requirement, parsing and caching it if missing
+    ///
+    /// Keys are the raw strings, so equivalent requirements written differently
+    /// are cached separately. Invalid requirements are not cached.
+    pub fn get_or_parse(&self, s: &str) -> Result<Arc<Ranges<PureVersion>>, InvalidRequirement> {
+        if let Some(req) = self.lock().get(s) {
+            return Ok(req);
+        }
+
+        // Parse without holding the lock, so other threads are not blocked
+        let req = Arc::new(Ranges::from_str(s).map_err(|errors| InvalidRequirement {
+            requirement: s.to_owned(),
+            errors: errors.into_iter().map(Rich::into_owned).collect(),
+        })?);
+        let mut inner = self.lock();
+        // Another thread might have been faster, keep its value to share the allocation
+        if let Some(req) = inner.get(s) {
+            return Ok(req);
+        }
+        inner.insert(s.to_owned(), req.clone());
+        while inner.entries.len() > self.capacity.get() {
+            inner.evict();
+        }
+        Ok(req)
+    }
+
+    /// Number of cached requirements
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all the cached requirements
+    pub fn clear(&self) {
+        *self.lock() = Inner::default();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        // The cache is always consistent between operations, so poisoning can be ignored
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Inner {
+    /// Get a requirement, marking it as the most recently used
+    fn get(&mut self, s: &str) -> Option<Arc<Ranges<PureVersion>>> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (req, last_use) = self.entries.get_mut(s)?;
+        let key = self
+            .by_use
+            .remove(last_use)
+            .expect("Every entry is in the use order");
+        *last_use = tick;
+        self.by_use.insert(tick, key);
+        Some(req.clone())
+    }
+
+    fn insert(&mut self, s: String, req: Arc<Ranges<PureVersion>>) {
+        self.tick += 1;
+        self.by_use.insert(self.tick, s.clone());
+        self.entries.insert(s, (req, self.tick));
+    }
+
+    /// Remove the least recently used requirement
+    fn evict(&mut self) {
+        if let Some((_, key)) = self.by_use.pop_first() {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+/// The requirement could not be parsed
+#[derive(Debug, Clone, Snafu)]
+#[snafu(display("Invalid requirement `{requirement}`: {}", errors.iter().join("; ")))]
+pub struct InvalidRequirement {
+    pub requirement: String,
+    pub errors: Vec<Rich<'static, char>>,
+}
@@ -0,0 +1,89 @@
+use std::{num::NonZeroUsize, sync::Arc};
+
+use super::ReqCache;
+use crate::range::Ranges;
+
+fn cache(capacity: usize) -> ReqCache {
+    ReqCache::new(NonZeroUsize::new(capacity).unwrap())
+}
+
+fn is_cached(cache: &ReqCache, s: &str) -> bool {
+    cache.lock().entries.contains_key(s)
+}
+
+#[test]
+fn parses_once() {
+    let cache = cache(4);
+    let a = cache.get_or_parse(">=1.0.0 && <2.0.0").unwrap();
+    let b = cache.get_or_parse(">=1.0.0 && <2.0.0").unwrap();
+    assert!(Arc::ptr_eq(&a, &b));
+    assert_eq!(*a, Ranges::from_str(">=1.0.0 && <2.0.0").unwrap());
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn invalid_requirements_are_not_cached() {
+    let cache = cache(4);
+    let err = cache.get_or_parse(">=1.0").unwrap_err();
+    assert_eq!(err.requirement, ">=1.0");
+    assert!(!err.errors.is_empty());
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn evicts_least_recently_inserted() {
+    let cache = cache(2);
+    cache.get_or_parse("==1.0.0").unwrap();
+    cache.get_or_parse("==2.0.0").unwrap();
+    cache.get_or_parse("==3.0.0").unwrap();
+    assert!(!is_cached(&cache, "==1.0.0"));
+    assert!(is_cached(&cache, "==2.0.0"));
+    assert!(is_cached(&cache, "==3.0.0"));
+}
+
+#[test]
+fn use_refreshes_entries() {
+    let cache = cache(2);
+    cache.get_or_parse("==1.0.0").unwrap();
+    cache.get_or_parse("==2.0.0").unwrap();
+    // Now `==2.0.0` is the least recently used
+    cache.get_or_parse("==1.0.0").unwrap();
+    cache.get_or_parse("==3.0.0").unwrap();
+    assert!(is_cached(&cache, "==1.0.0"));
+    assert!(!is_cached(&cache, "==2.0.0"));
+    assert!(is_cached(&cache, "==3.0.0"));
+}
+
+#[test]
+fn clear() {
+    let cache = cache(2);
+    cache.get_or_parse("==1.0.0").unwrap();
+    cache.clear();
+    assert!(cache.is_empty());
+    cache.get_or_parse("==1.0.0").unwrap();
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn concurrent_access() {
+    let cache = cache(8);
+    let reqs: Vec<_> = (0..16).map(|major| format!(">={major}.0.0")).collect();
+    std::thread::scope(|s| {
+        for thread in 0..8 {
+            let (cache, reqs) = (&cache, &reqs);
+            s.spawn(move || {
+                for idx in 0..1000 {
+                    let s = &reqs[(idx * 7 + thread) % reqs.len()];
+                    let req = cache.get_or_parse(s).unwrap();
+                    assert_eq!(*req, Ranges::from_str(s).unwrap());
+                }
+            });
+        }
+    });
+    assert_eq!(cache.len(), 8);
+    let inner = cache.lock();
+    assert_eq!(inner.by_use.len(), inner.entries.len());
+    for (tick, key) in &inner.by_use {
+        assert_eq!(inner.entries[key].1, *tick);
+    }
+}
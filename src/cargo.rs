@@ -0,0 +1,195 @@
+//! Extraction of version requirements from `Cargo.toml` manifests
+
+use std::fmt::Display;
+
+use derive_more::Display;
+use snafu::{OptionExt, ResultExt, Snafu};
+use toml::{Table, Value};
+
+use crate::{
+    range::Ranges,
+    version::pure::{
+        PureVersion,
+        comparator::{self, Partial, RangeOp},
+    },
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Kind of a dependency table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+pub enum DependencyKind {
+    #[display("dependencies")]
+    Normal,
+    #[display("dev-dependencies")]
+    Dev,
+    #[display("build-dependencies")]
+    Build,
+}
+
+/// Table a dependency was declared in
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Section {
+    pub kind: DependencyKind,
+    /// Platform of a `[target.<cfg>.*]` table
+    pub target: Option<String>,
+}
+
+impl Display for Section {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.target {
+            Some(target) => write!(f, "target.{target}.{}", self.kind),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+/// A dependency declared in a manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub name: String,
+    /// Accepted versions, full if the dependency has no `version` key
+    pub requirement: Ranges<PureVersion>,
+    pub section: Section,
+    /// Whether the dependency had a `version` key (path and git dependencies might not)
+    pub has_version: bool,
+}
+
+/// Extract all the dependencies from a `Cargo.toml` manifest
+///
+/// Looks into `[dependencies]`, `[dev-dependencies]`, `[build-dependencies]` and their
+/// `[target.<cfg>.*]` counterparts.
+pub fn dependencies_from_manifest(manifest: &str) -> Result<Vec<Dependency>, ManifestError> {
+    let manifest: Table = manifest.parse().context(TomlSnafu)?;
+
+    let mut dependencies = vec![];
+    collect(&manifest, None, &mut dependencies)?;
+    if let Some(targets) = manifest.get("target") {
+        let targets = targets.as_table().context(NotATableSnafu {
+            key: "target".to_owned(),
+        })?;
+        for (target, table) in targets {
+            let table = table.as_table().context(NotATableSnafu {
+                key: format!("target.{target}"),
+            })?;
+            collect(table, Some(target), &mut dependencies)?;
+        }
+    }
+    Ok(dependencies)
+}
+
+fn collect(
+    table: &Table,
+    target: Option<&str>,
+    dependencies: &mut Vec<Dependency>,
+) -> Result<(), ManifestError> {
+    for kind in [
+        DependencyKind::Normal,
+        DependencyKind::Dev,
+        DependencyKind::Build,
+    ] {
+        let section = Section {
+            kind,
+            target: target.map(str::to_owned),
+        };
+        let Some(entries) = table.get(&kind.to_string()) else {
+            continue;
+        };
+        let entries = entries.as_table().context(NotATableSnafu {
+            key: section.to_string(),
+        })?;
+        for (name, entry) in entries {
+            let version = match entry {
+                Value::String(version) => Some(version),
+                Value::Table(entry) => match entry.get("version") {
+                    None => None,
+                    Some(Value::String(version)) => Some(version),
+                    Some(_) => {
+                        return InvalidEntrySnafu {
+                            section,
+                            name: name.clone(),
+                        }
+                        .fail();
+                    }
+                },
+                _ => {
+                    return InvalidEntrySnafu {
+                        section,
+                        name: name.clone(),
+                    }
+                    .fail();
+                }
+            };
+            let requirement = match version {
+                Some(version) => parse_requirement(version).context(InvalidRequirementSnafu {
+                    section: section.clone(),
+                    name: name.clone(),
+                    requirement: version.clone(),
+                })?,
+                None => Ranges::full(),
+            };
+            dependencies.push(Dependency {
+                name: name.clone(),
+                requirement,
+                section: section.clone(),
+                has_version: version.is_some(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Parse a version requirement using Cargo semantics
+///
+/// Comparators are separated by commas, and a bare version is a caret requirement.
+pub fn parse_requirement(s: &str) -> Result<Ranges<PureVersion>, InvalidCargoReq> {
+    if s.trim().is_empty() {
+        return EmptySnafu.fail();
+    }
+    s.split(',').try_fold(Ranges::full(), |acc, comparator| {
+        let comparator = comparator.trim();
+        let (op, version) = RangeOp::split(comparator);
+        let version =
+            Partial::parse(version.trim_start()).context(InvalidComparatorSnafu { comparator })?;
+        // A bare wildcard like `1.2.*` is the same as `=1.2`
+        let op = op.unwrap_or(if version.wildcard {
+            RangeOp::Exact
+        } else {
+            RangeOp::Caret
+        });
+        Ok(acc.and(&version.ranges(op)))
+    })
+}
+
+#[derive(Debug, Clone, Snafu)]
+pub enum InvalidCargoReq {
+    #[snafu(display("The requirement is empty"))]
+    Empty,
+    #[snafu(display("Invalid comparator `{comparator}`"))]
+    InvalidComparator {
+        comparator: String,
+        source: comparator::InvalidComparator,
+    },
+}
+
+#[derive(Debug, Snafu)]
+pub enum ManifestError {
+    #[snafu(display("The manifest is not valid TOML"))]
+    Toml {
+        #[snafu(source(from(toml::de::Error, Box::new)))]
+        source: Box<toml::de::Error>,
+    },
+    #[snafu(display("`{key}` must be a table"))]
+    NotATable { key: String },
+    #[snafu(display("The dependency `{name}` in `[{section}]` must be a string or a table"))]
+    InvalidEntry { section: Section, name: String },
+    #[snafu(display("Invalid requirement `{requirement}` for `{name}` in `[{section}]`"))]
+    InvalidRequirement {
+        section: Section,
+        name: String,
+        requirement: String,
+        #[snafu(source(from(InvalidCargoReq, Box::new)))]
+        source: Box<InvalidCargoReq>,
+    },
+}
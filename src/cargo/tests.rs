@@ -0,0 +1,141 @@
+use crate::range::Ranges;
+
+use super::*;
+
+fn req(s: &str) -> Ranges<PureVersion> {
+    Ranges::from_str(s).unwrap()
+}
+
+const MANIFEST: &str = r#"
+[package]
+name = "example"
+version = "0.1.0"
+edition = "2024"
+
+[dependencies]
+serde = { version = "1.0", features = ["derive"] }
+itertools = "0.14.0"
+local = { path = "../local" }
+remote = { git = "https://example.com/remote.git", version = "=2.1.0" }
+
+[dev-dependencies]
+proptest = "~1.4"
+
+[build-dependencies]
+cc = ">=1.0.80, <1.2"
+
+[target.'cfg(windows)'.dependencies]
+winapi = { version = "0.3", features = ["winuser"] }
+
+[target.'cfg(unix)'.dev-dependencies]
+nix = "*"
+"#;
+
+#[test]
+fn manifest() {
+    let dependencies = dependencies_from_manifest(MANIFEST).unwrap();
+    let found = |name: &str| {
+        dependencies
+            .iter()
+            .find(|dep| dep.name == name)
+            .unwrap_or_else(|| panic!("Missing dependency `{name}`"))
+    };
+    assert_eq!(dependencies.len(), 8);
+
+    let serde = found("serde");
+    assert_eq!(serde.requirement, req(">=1.0.0 && <2.0.0-0"));
+    assert_eq!(
+        serde.section,
+        Section {
+            kind: DependencyKind::Normal,
+            target: None
+        }
+    );
+    assert!(serde.has_version);
+
+    assert_eq!(found("itertools").requirement, req(">=0.14.0 && <0.15.0-0"));
+
+    let local = found("local");
+    assert!(!local.has_version);
+    assert!(local.requirement.is_full());
+
+    assert_eq!(found("remote").requirement, req("==2.1.0"));
+
+    let proptest = found("proptest");
+    assert_eq!(proptest.requirement, req(">=1.4.0 && <1.5.0-0"));
+    assert_eq!(proptest.section.kind, DependencyKind::Dev);
+
+    let cc = found("cc");
+    assert_eq!(cc.requirement, req(">=1.0.80 && <1.2.0-0"));
+    assert_eq!(cc.section.kind, DependencyKind::Build);
+
+    let winapi = found("winapi");
+    assert_eq!(winapi.requirement, req(">=0.3.0 && <0.4.0-0"));
+    assert_eq!(winapi.section.target.as_deref(), Some("cfg(windows)"));
+    assert_eq!(
+        winapi.section.to_string(),
+        "target.cfg(windows).dependencies"
+    );
+
+    let nix = found("nix");
+    assert!(nix.has_version);
+    assert!(nix.requirement.is_full());
+    assert_eq!(nix.section.kind, DependencyKind::Dev);
+}
+
+#[test]
+fn cargo_requirements() {
+    for (cargo, expected) in [
+        ("1.2.3", ">=1.2.3 && <2.0.0-0"),
+        ("^1.2", ">=1.2.0 && <2.0.0-0"),
+        ("^0.2.3", ">=0.2.3 && <0.3.0-0"),
+        ("^0.0.3", ">=0.0.3 && <0.0.4-0"),
+        ("^0.0", ">=0.0.0 && <0.1.0-0"),
+        ("^0", ">=0.0.0 && <1.0.0-0"),
+        ("~1.2.3", ">=1.2.3 && <1.3.0-0"),
+        ("~1", ">=1.0.0 && <2.0.0-0"),
+        ("=1.2", ">=1.2.0 && <1.3.0-0"),
+        ("1.*", ">=1.0.0 && <2.0.0-0"),
+        ("1.2.*", ">=1.2.0 && <1.3.0-0"),
+        (">1.2.3", ">1.2.3"),
+        (">1.2", ">=1.3.0-0"),
+        ("<1.2", "<1.2.0-0"),
+        ("<=1.2", "<1.3.0-0"),
+        ("<=1.2.3", "<=1.2.3"),
+        ("=1.2.3-alpha.1", "==1.2.3-alpha.1"),
+        ("1.2.3+build", ">=1.2.3 && <2.0.0-0"),
+        (">= 1.2, < 1.5", ">=1.2.0 && <1.5.0-0"),
+    ] {
+        assert_eq!(
+            parse_requirement(cargo).unwrap(),
+            req(expected),
+            "`{cargo}` should be `{expected}`"
+        );
+    }
+}
+
+#[test]
+fn invalid_requirements() {
+    for invalid in ["", "1.2.3.4", ">=", "1.a", "1.2-alpha"] {
+        assert!(
+            parse_requirement(invalid).is_err(),
+            "`{invalid}` should not parse"
+        );
+    }
+}
+
+#[test]
+fn invalid_manifest() {
+    assert!(matches!(
+        dependencies_from_manifest("[dependencies]\nserde = 1"),
+        Err(ManifestError::InvalidEntry { .. })
+    ));
+    assert!(matches!(
+        dependencies_from_manifest("[dependencies]\nserde = \"nope\""),
+        Err(ManifestError::InvalidRequirement { .. })
+    ));
+    assert!(matches!(
+        dependencies_from_manifest("[dependencies"),
+        Err(ManifestError::Toml { .. })
+    ));
+}
@@ -0,0 +1,109 @@
+//! Merging of requirements coming from several sources, reporting which ones conflict
+//!
+//! ```
+//! # use areq::{conflict, range::Ranges};
+//! let reqs = [
+//!     ("app", Ranges::from_str(">=1.2.0").unwrap()),
+//!     ("plugin", Ranges::from_str("<2.0.0").unwrap()),
+//!     ("legacy", Ranges::from_str("<1.0.0").unwrap()),
+//! ];
+//! let report = conflict::merge(reqs).unwrap_err();
+//! let labels: Vec<_> = report.sources.iter().map(|source| source.label).collect();
+//! assert_eq!(labels, ["app", "legacy"]);
+//! ```
+
+use std::fmt::{Debug, Display};
+
+use crate::{
+    range::{Range, Ranges},
+    version::pure::PureVersion,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Intersect the requirements of all the sources
+///
+/// The requirements are intersected in order. If the intersection becomes empty, the report
+/// holds a minimal set of sources whose requirements already conflict: the source that
+/// emptied the intersection, and the earlier ones needed to conflict with it.
+/// Dropping any of them makes the rest satisfiable.
+pub fn merge<L>(
+    reqs: impl IntoIterator<Item = (L, Ranges<PureVersion>)>,
+) -> Result<Ranges<PureVersion>, ConflictReport<L>> {
+    let mut merged = Ranges::full();
+    let mut seen = vec![];
+    for (label, ranges) in reqs {
+        let next = merged.clone().and(&ranges);
+        if next.is_empty() {
+            return Err(ConflictReport::new(seen, (label, ranges)));
+        }
+        merged = next;
+        seen.push((label, ranges));
+    }
+    Ok(merged)
+}
+
+/// The requirements of some sources have no version in common, see [`merge`]
+#[derive(Debug, Clone)]
+pub struct ConflictReport<L> {
+    /// The conflicting sources, in the order they were merged
+    ///
+    /// The last one is the source that emptied the intersection.
+    pub sources: Vec<ConflictingSource<L>>,
+}
+
+/// A source taking part in a conflict
+#[derive(Debug, Clone)]
+pub struct ConflictingSource<L> {
+    pub label: L,
+    pub ranges: Ranges<PureVersion>,
+    /// The smallest contiguous range containing the requirement, for a compact display
+    pub hull: Range<PureVersion>,
+}
+
+impl<L> ConflictReport<L> {
+    /// Find the sources conflicting with `last`, that all the `previous` together conflict with
+    fn new(previous: Vec<(L, Ranges<PureVersion>)>, last: (L, Ranges<PureVersion>)) -> Self {
+        // The previous sources are satisfiable together, so `last` is always kept
+        let ranges: Vec<_> = previous
+            .iter()
+            .chain([&last])
+            .map(|(_, ranges)| ranges.clone())
+            .collect();
+        let keep = Ranges::minimal_conflict(&ranges);
+        let sources = previous
+            .into_iter()
+            .chain([last])
+            .zip(keep)
+            .filter_map(|(source, keep)| keep.then_some(source))
+            .map(|(label, ranges)| ConflictingSource {
+                hull: ranges.hull(),
+                label,
+                ranges,
+            })
+            .collect();
+        Self { sources }
+    }
+}
+
+impl<L> Display for ConflictReport<L>
+where
+    L: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &*self.sources {
+            [single] => write!(f, "The requirement of {} matches no version:", single.label)?,
+            _ => write!(f, "No version satisfies all the requirements of:")?,
+        }
+        for source in &self.sources {
+            write!(f, "\n  {}: {}", source.label, source.ranges)?;
+            if !source.ranges.is_contiguous() && !source.ranges.is_empty() {
+                write!(f, " (within {})", source.hull)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<L> std::error::Error for ConflictReport<L> where L: Display + Debug {}
@@ -0,0 +1,71 @@
+use std::ops::Bound;
+
+use super::{ConflictReport, merge};
+use crate::{
+    range::{Range, Ranges},
+    version::pure::PureVersion,
+};
+
+fn r(s: &str) -> Ranges<PureVersion> {
+    Ranges::from_str(s).unwrap()
+}
+
+fn labels<L: Copy>(report: &ConflictReport<L>) -> Vec<L> {
+    report.sources.iter().map(|source| source.label).collect()
+}
+
+#[test]
+fn satisfiable() {
+    let merged = merge([("a", r(">=1.0.0")), ("b", r("<2.0.0")), ("c", r("!=1.5.0"))]).unwrap();
+    assert_eq!(merged, r(">=1.0.0 && <2.0.0 && !=1.5.0"));
+    assert!(merge::<&str>([]).unwrap().is_full());
+}
+
+#[test]
+fn two_way_conflict() {
+    let report = merge([
+        ("app", r(">=1.2.0")),
+        ("tools", r("<3.0.0")),
+        ("legacy", r("<1.0.0")),
+        ("never merged", r("==0.5.0")),
+    ])
+    .unwrap_err();
+    assert_eq!(labels(&report), ["app", "legacy"]);
+    assert_eq!(
+        report.to_string(),
+        "No version satisfies all the requirements of:\n  app: >=1.2.0\n  legacy: <1.0.0"
+    );
+}
+
+#[test]
+fn three_way_conflict() {
+    let (app, tools, plugin) = (r(">=1.2.0"), r("<3.0.0"), r("<1.0.0 || ==4.0.0"));
+    // No pair conflicts
+    assert!(!app.clone().and(&tools).is_empty());
+    assert!(!app.clone().and(&plugin).is_empty());
+    assert!(!tools.clone().and(&plugin).is_empty());
+
+    let report = merge([("app", app), ("tools", tools), ("plugin", plugin)]).unwrap_err();
+    assert_eq!(labels(&report), ["app", "tools", "plugin"]);
+    assert_eq!(
+        report.sources[2].hull,
+        Range::from_bounds(Bound::Unbounded, Bound::Included("4.0.0".parse().unwrap()))
+    );
+    assert_eq!(
+        report.to_string(),
+        "No version satisfies all the requirements of:\n  \
+        app: >=1.2.0\n  \
+        tools: <3.0.0\n  \
+        plugin: <1.0.0 || ==4.0.0 (within <=4.0.0)"
+    );
+}
+
+#[test]
+fn empty_source() {
+    let report = merge([("app", r(">=1.0.0")), ("broken", r(">=2.0.0 && <1.0.0"))]).unwrap_err();
+    assert_eq!(labels(&report), ["broken"]);
+    assert_eq!(
+        report.to_string(),
+        "The requirement of broken matches no version:\n  broken: -"
+    );
+}
@@ -0,0 +1,253 @@
+//! C interface, for hosts that cannot link Rust directly
+//!
+//! Build the library with
+//! `cargo rustc --lib --release --features ffi --crate-type cdylib` (or `staticlib`),
+//! and include `include/areq.h`, generated by `cbindgen` from this module
+//! with the configuration in `cbindgen.toml`.
+//!
+//! Fallible functions return [`AREQ_OK`] or one of the negative `AREQ_ERR_*` codes,
+//! and store a description of the error retrievable with [`areq_last_error_message`].
+//! Panics never cross the boundary: they are reported as [`AREQ_ERR_PANIC`].
+//! Objects returned through out pointers are owned by the caller, and must be released
+//! with the matching `*_free` function.
+
+use std::{
+    cell::RefCell,
+    ffi::{CStr, CString, c_char, c_int},
+    panic::{AssertUnwindSafe, catch_unwind},
+    ptr,
+};
+
+use crate::{
+    range::Ranges,
+    version::{Version, pure::PureVersion},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The call succeeded
+pub const AREQ_OK: c_int = 0;
+/// A required pointer was null
+pub const AREQ_ERR_NULL: c_int = -1;
+/// A string was not valid UTF-8
+pub const AREQ_ERR_UTF8: c_int = -2;
+/// A version or requirement could not be parsed
+pub const AREQ_ERR_PARSE: c_int = -3;
+/// The library panicked, this is a bug
+pub const AREQ_ERR_PANIC: c_int = -4;
+
+/// A parsed version
+pub struct AreqVersion(Version);
+
+/// A parsed requirement
+pub struct AreqReq(Ranges<PureVersion>);
+
+/// A failed call, with the code to return and the message to store
+struct Error {
+    code: c_int,
+    message: String,
+}
+
+impl Error {
+    fn null(name: &str) -> Self {
+        Self {
+            code: AREQ_ERR_NULL,
+            message: format!("`{name}` is null"),
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', "\\0")).expect("Nul bytes were replaced");
+    LAST_ERROR.with_borrow_mut(|last| *last = Some(message));
+}
+
+/// Run `f`, converting errors and panics into error codes
+fn guard(f: impl FnOnce() -> Result<c_int, Error>) -> c_int {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(code)) => code,
+        Ok(Err(Error { code, message })) => {
+            set_last_error(message);
+            code
+        }
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown panic");
+            set_last_error(format!("Panic inside areq: {message}"));
+            AREQ_ERR_PANIC
+        }
+    }
+}
+
+/// # Safety
+/// `s` must be null or point to a nul-terminated string, valid for the lifetime `'a`.
+unsafe fn read_str<'a>(s: *const c_char, name: &str) -> Result<&'a str, Error> {
+    if s.is_null() {
+        return Err(Error::null(name));
+    }
+    // SAFETY: the pointer is not null, the rest is guaranteed by the caller
+    let s = unsafe { CStr::from_ptr(s) };
+    s.to_str().map_err(|err| Error {
+        code: AREQ_ERR_UTF8,
+        message: format!("`{name}` is not valid UTF-8: {err}"),
+    })
+}
+
+/// # Safety
+/// `p` must be null or valid for reads for the lifetime `'a`.
+unsafe fn read_ref<'a, T>(p: *const T, name: &str) -> Result<&'a T, Error> {
+    // SAFETY: guaranteed by the caller
+    unsafe { p.as_ref() }.ok_or_else(|| Error::null(name))
+}
+
+/// Parse a version, storing it in `*out`
+///
+/// On failure `*out` is set to null, if `out` is not null.
+///
+/// # Safety
+/// `s` must be null or point to a nul-terminated string.
+/// `out` must be null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn areq_version_parse(s: *const c_char, out: *mut *mut AreqVersion) -> c_int {
+    guard(|| {
+        if out.is_null() {
+            return Err(Error::null("out"));
+        }
+        // SAFETY: `out` is not null, and valid for writes by contract
+        unsafe { out.write(ptr::null_mut()) };
+        // SAFETY: guaranteed by the caller
+        let s = unsafe { read_str(s, "s") }?;
+        let version = s.parse::<Version>().map_err(|err| {
+            let mut message = format!("Invalid version `{s}`: {err}");
+            let mut source = std::error::Error::source(&err);
+            while let Some(cause) = source {
+                message.push_str(&format!(": {cause}"));
+                source = cause.source();
+            }
+            Error {
+                code: AREQ_ERR_PARSE,
+                message,
+            }
+        })?;
+        // SAFETY: as above
+        unsafe { out.write(Box::into_raw(Box::new(AreqVersion(version)))) };
+        Ok(AREQ_OK)
+    })
+}
+
+/// Release a version. Null is ignored.
+///
+/// # Safety
+/// `version` must be null or returned by [`areq_version_parse`], and not already released.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn areq_version_free(version: *mut AreqVersion) {
+    if !version.is_null() {
+        // SAFETY: the pointer comes from `Box::into_raw`, by contract
+        drop(unsafe { Box::from_raw(version) });
+    }
+}
+
+/// Compare two versions by precedence, storing `-1`, `0` or `1` in `*out`
+///
+/// Build metadata is ignored, as it does not contribute to the precedence.
+///
+/// # Safety
+/// Each pointer must be null or valid: `a` and `b` for reads, `out` for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn areq_version_cmp(
+    a: *const AreqVersion,
+    b: *const AreqVersion,
+    out: *mut c_int,
+) -> c_int {
+    guard(|| {
+        // SAFETY: guaranteed by the caller
+        let (a, b) = unsafe { (read_ref(a, "a")?, read_ref(b, "b")?) };
+        if out.is_null() {
+            return Err(Error::null("out"));
+        }
+        // SAFETY: `out` is not null, and valid for writes by contract
+        unsafe { out.write(a.0.pure.cmp(&b.0.pure) as c_int) };
+        Ok(AREQ_OK)
+    })
+}
+
+/// Parse a requirement, storing it in `*out`
+///
+/// On failure `*out` is set to null, if `out` is not null.
+///
+/// # Safety
+/// `s` must be null or point to a nul-terminated string.
+/// `out` must be null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn areq_req_parse(s: *const c_char, out: *mut *mut AreqReq) -> c_int {
+    guard(|| {
+        if out.is_null() {
+            return Err(Error::null("out"));
+        }
+        // SAFETY: `out` is not null, and valid for writes by contract
+        unsafe { out.write(ptr::null_mut()) };
+        // SAFETY: guaranteed by the caller
+        let s = unsafe { read_str(s, "s") }?;
+        let req = Ranges::from_str(s).map_err(|errors| Error {
+            code: AREQ_ERR_PARSE,
+            message: format!(
+                "Invalid requirement `{s}`: {}",
+                errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ),
+        })?;
+        // SAFETY: as above
+        unsafe { out.write(Box::into_raw(Box::new(AreqReq(req)))) };
+        Ok(AREQ_OK)
+    })
+}
+
+/// Release a requirement. Null is ignored.
+///
+/// # Safety
+/// `req` must be null or returned by [`areq_req_parse`], and not already released.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn areq_req_free(req: *mut AreqReq) {
+    if !req.is_null() {
+        // SAFETY: the pointer comes from `Box::into_raw`, by contract
+        drop(unsafe { Box::from_raw(req) });
+    }
+}
+
+/// Check if a version matches a requirement, with the default prerelease policy
+///
+/// Returns `1` if it matches, `0` if it does not, or a negative error code.
+///
+/// # Safety
+/// `req` and `version` must be null or valid for reads.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn areq_req_matches(
+    req: *const AreqReq,
+    version: *const AreqVersion,
+) -> c_int {
+    guard(|| {
+        // SAFETY: guaranteed by the caller
+        let (req, version) = unsafe { (read_ref(req, "req")?, read_ref(version, "version")?) };
+        Ok(version.0.matches(&req.0) as c_int)
+    })
+}
+
+/// Message of the last error on the calling thread, or null if there was none
+///
+/// The string is owned by the library, and is valid until the next failing call
+/// on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn areq_last_error_message() -> *const c_char {
+    LAST_ERROR.with_borrow(|last| last.as_ref().map_or(ptr::null(), |last| last.as_ptr()))
+}
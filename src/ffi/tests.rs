@@ -0,0 +1,152 @@
+//! The functions are called through their C symbols, so the tests exercise the C ABI
+
+use std::{
+    ffi::{CStr, c_char, c_int},
+    ptr,
+};
+
+use super::{AREQ_ERR_NULL, AREQ_ERR_PARSE, AREQ_ERR_UTF8, AREQ_OK};
+
+/// Opaque types, as seen by C
+#[repr(C)]
+struct AreqVersion {
+    _private: [u8; 0],
+}
+
+#[repr(C)]
+struct AreqReq {
+    _private: [u8; 0],
+}
+
+unsafe extern "C" {
+    fn areq_version_parse(s: *const c_char, out: *mut *mut AreqVersion) -> c_int;
+    fn areq_version_free(version: *mut AreqVersion);
+    fn areq_version_cmp(a: *const AreqVersion, b: *const AreqVersion, out: *mut c_int) -> c_int;
+    fn areq_req_parse(s: *const c_char, out: *mut *mut AreqReq) -> c_int;
+    fn areq_req_free(req: *mut AreqReq);
+    fn areq_req_matches(req: *const AreqReq, version: *const AreqVersion) -> c_int;
+    fn areq_last_error_message() -> *const c_char;
+}
+
+fn version(s: &CStr) -> *mut AreqVersion {
+    let mut version = ptr::null_mut();
+    assert_eq!(
+        unsafe { areq_version_parse(s.as_ptr(), &mut version) },
+        AREQ_OK
+    );
+    assert!(!version.is_null());
+    version
+}
+
+fn req(s: &CStr) -> *mut AreqReq {
+    let mut req = ptr::null_mut();
+    assert_eq!(unsafe { areq_req_parse(s.as_ptr(), &mut req) }, AREQ_OK);
+    assert!(!req.is_null());
+    req
+}
+
+fn last_error() -> String {
+    let message = unsafe { areq_last_error_message() };
+    assert!(!message.is_null());
+    unsafe { CStr::from_ptr(message) }
+        .to_str()
+        .unwrap()
+        .to_owned()
+}
+
+#[test]
+fn compare() {
+    let a = version(c"1.2.3");
+    let b = version(c"1.10.0-rc.1+build");
+    let mut ord = 42;
+    unsafe {
+        assert_eq!(areq_version_cmp(a, b, &mut ord), AREQ_OK);
+        assert_eq!(ord, -1);
+        assert_eq!(areq_version_cmp(b, a, &mut ord), AREQ_OK);
+        assert_eq!(ord, 1);
+        assert_eq!(areq_version_cmp(a, a, &mut ord), AREQ_OK);
+        assert_eq!(ord, 0);
+        areq_version_free(a);
+        areq_version_free(b);
+    }
+}
+
+#[test]
+fn matches() {
+    let req = req(c">=1.2.0, <2.0.0");
+    let inside = version(c"1.4.2");
+    let outside = version(c"2.0.0");
+    let prerelease = version(c"1.5.0-beta");
+    unsafe {
+        assert_eq!(areq_req_matches(req, inside), 1);
+        assert_eq!(areq_req_matches(req, outside), 0);
+        assert_eq!(areq_req_matches(req, prerelease), 0);
+        areq_req_free(req);
+        areq_version_free(inside);
+        areq_version_free(outside);
+        areq_version_free(prerelease);
+    }
+}
+
+#[test]
+fn parse_errors() {
+    let mut version = ptr::null_mut();
+    assert_eq!(
+        unsafe { areq_version_parse(c"1.2".as_ptr(), &mut version) },
+        AREQ_ERR_PARSE
+    );
+    assert!(version.is_null());
+    assert!(last_error().contains("`1.2`"));
+
+    let mut req = ptr::null_mut();
+    assert_eq!(
+        unsafe { areq_req_parse(c">=1.2".as_ptr(), &mut req) },
+        AREQ_ERR_PARSE
+    );
+    assert!(req.is_null());
+    assert!(last_error().contains("`>=1.2`"));
+}
+
+#[test]
+fn invalid_utf8() {
+    let mut version = ptr::null_mut();
+    let s = b"1.2.\xff\0";
+    assert_eq!(
+        unsafe { areq_version_parse(s.as_ptr().cast(), &mut version) },
+        AREQ_ERR_UTF8
+    );
+    assert!(version.is_null());
+    assert!(last_error().contains("UTF-8"));
+}
+
+#[test]
+fn null_pointers() {
+    let mut version = ptr::null_mut();
+    let mut ord = 0;
+    unsafe {
+        assert_eq!(areq_version_parse(ptr::null(), &mut version), AREQ_ERR_NULL);
+        assert_eq!(
+            areq_version_parse(c"1.2.3".as_ptr(), ptr::null_mut()),
+            AREQ_ERR_NULL
+        );
+        assert_eq!(
+            areq_version_cmp(ptr::null(), ptr::null(), &mut ord),
+            AREQ_ERR_NULL
+        );
+        assert_eq!(areq_req_matches(ptr::null(), ptr::null()), AREQ_ERR_NULL);
+        // Freeing null is a no-op
+        areq_version_free(ptr::null_mut());
+        areq_req_free(ptr::null_mut());
+    }
+    assert_eq!(last_error(), "`req` is null");
+}
+
+#[test]
+fn errors_are_per_thread() {
+    let mut version = ptr::null_mut();
+    unsafe { areq_version_parse(c"nope".as_ptr(), &mut version) };
+    std::thread::spawn(|| assert!(unsafe { areq_last_error_message() }.is_null()))
+        .join()
+        .unwrap();
+    assert!(last_error().contains("`nope`"));
+}
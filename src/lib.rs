@@ -1,2 +1,15 @@
+pub mod cache;
+#[cfg(feature = "toml")]
+pub mod cargo;
+pub mod conflict;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod npm;
 pub mod range;
+pub mod reqfile;
+pub mod resolve;
+pub mod satisfies;
+pub mod sort;
 pub mod version;
+#[cfg(feature = "wasm")]
+pub mod wasm;
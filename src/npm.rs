@@ -0,0 +1,128 @@
+//! The npm dialect of version ranges
+
+#[cfg(feature = "json")]
+use std::collections::BTreeMap;
+
+use snafu::{ResultExt, Snafu};
+
+use crate::{
+    range::Ranges,
+    version::pure::{
+        PureVersion,
+        comparator::{self, Partial, RangeOp},
+    },
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Parse a range using the npm dialect
+///
+/// Ranges are separated by `||`, and each of them is either a hyphen range like
+/// `1.2 - 2.3.4` or a list of comparators separated by whitespace. A bare version is
+/// an exact match, where missing parts are wildcards. An empty range matches anything.
+pub fn parse_range(s: &str) -> Result<Ranges<PureVersion>, InvalidNpmRange> {
+    s.split("||")
+        .try_fold(Ranges::EMPTY, |acc, set| Ok(acc.or(&parse_set(set)?)))
+}
+
+fn parse_set(set: &str) -> Result<Ranges<PureVersion>, InvalidNpmRange> {
+    if let [start, "-", end] = set.split_whitespace().collect::<Vec<_>>()[..] {
+        return Ok(parse_comparator(start, Some(RangeOp::GreaterEq))?
+            .and(&parse_comparator(end, Some(RangeOp::LessEq))?));
+    }
+
+    let mut ranges = Ranges::full();
+    let mut tokens = set.split_whitespace().peekable();
+    while let Some(token) = tokens.next() {
+        // Operators can be separated from their version, like in `>= 14`
+        let range = match (split_op(token), tokens.peek()) {
+            ((Some(_), ""), Some(version)) => {
+                let range = parse_comparator(&format!("{token}{version}"), None)?;
+                tokens.next();
+                range
+            }
+            _ => parse_comparator(token, None)?,
+        };
+        ranges = ranges.and(&range);
+    }
+    Ok(ranges)
+}
+
+/// Parse a single comparator, with `op` overriding its operator
+fn parse_comparator(
+    comparator: &str,
+    op: Option<RangeOp>,
+) -> Result<Ranges<PureVersion>, InvalidNpmRange> {
+    let (parsed_op, version) = split_op(comparator);
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let version = Partial::parse(version).context(InvalidComparatorSnafu { comparator })?;
+    Ok(version.ranges(op.or(parsed_op).unwrap_or(RangeOp::Exact)))
+}
+
+/// Split the operator of a comparator, accepting `~>` as an alias of `~`
+fn split_op(comparator: &str) -> (Option<RangeOp>, &str) {
+    match comparator.strip_prefix("~>") {
+        Some(version) => (Some(RangeOp::Tilde), version),
+        None => RangeOp::split(comparator),
+    }
+}
+
+#[derive(Debug, Clone, Snafu)]
+pub enum InvalidNpmRange {
+    #[snafu(display("Invalid comparator `{comparator}`"))]
+    InvalidComparator {
+        comparator: String,
+        source: comparator::InvalidComparator,
+    },
+}
+
+/// Extract the `engines` field of a `package.json`
+///
+/// Each engine is parsed on its own, so a malformed entry does not prevent reading
+/// the others. A missing `engines` field gives no engines.
+#[cfg(feature = "json")]
+pub fn engines_from_package_json(
+    json: &str,
+) -> Result<BTreeMap<String, Result<Ranges<PureVersion>, InvalidEngine>>, EnginesError> {
+    use serde_json::Value;
+
+    let package: Value = serde_json::from_str(json).context(JsonSnafu)?;
+    let Value::Object(package) = package else {
+        return NotAnObjectSnafu { key: "package" }.fail();
+    };
+    let engines = match package.get("engines") {
+        None | Some(Value::Null) => return Ok(BTreeMap::new()),
+        Some(Value::Object(engines)) => engines,
+        Some(_) => return NotAnObjectSnafu { key: "engines" }.fail(),
+    };
+
+    Ok(engines
+        .iter()
+        .map(|(name, range)| {
+            let range = match range {
+                Value::String(range) => parse_range(range).context(InvalidRangeSnafu),
+                _ => NotAStringSnafu.fail(),
+            };
+            (name.clone(), range)
+        })
+        .collect())
+}
+
+#[cfg(feature = "json")]
+#[derive(Debug, Snafu)]
+pub enum EnginesError {
+    #[snafu(display("The package is not valid JSON"))]
+    Json { source: serde_json::Error },
+    #[snafu(display("`{key}` must be an object"))]
+    NotAnObject { key: &'static str },
+}
+
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, Snafu)]
+pub enum InvalidEngine {
+    #[snafu(display("The engine range must be a string"))]
+    NotAString,
+    #[snafu(display("Invalid engine range"))]
+    InvalidRange { source: InvalidNpmRange },
+}
@@ -0,0 +1,118 @@
+use crate::range::Ranges;
+
+use super::*;
+
+fn req(s: &str) -> Ranges<PureVersion> {
+    Ranges::from_str(s).unwrap()
+}
+
+#[test]
+fn npm_ranges() {
+    for (npm, expected) in [
+        ("1.2.3", "==1.2.3"),
+        ("=v1.2.3", "==1.2.3"),
+        ("1.2", ">=1.2.0 && <1.3.0-0"),
+        ("1.x", ">=1.0.0 && <2.0.0-0"),
+        ("*", "*"),
+        ("", "*"),
+        ("x", "*"),
+        ("^1.2.3", ">=1.2.3 && <2.0.0-0"),
+        ("^0.0.3", ">=0.0.3 && <0.0.4-0"),
+        ("~1.2", ">=1.2.0 && <1.3.0-0"),
+        ("~>1.2", ">=1.2.0 && <1.3.0-0"),
+        (">= 14", ">=14.0.0"),
+        (">=1.2.7 <1.3.0", ">=1.2.7 && <1.3.0"),
+        ("1.2.3 - 2.3", ">=1.2.3 && <2.4.0-0"),
+        ("1.2 - 2.3.4", ">=1.2.0 && <=2.3.4"),
+        ("1.2.7 || >=1.2.9 <2.0.0", "==1.2.7 || (>=1.2.9 && <2.0.0)"),
+        ("<1.2", "<1.2.0-0"),
+    ] {
+        assert_eq!(
+            parse_range(npm).unwrap(),
+            req(expected),
+            "`{npm}` should be `{expected}`"
+        );
+    }
+}
+
+#[test]
+fn invalid_npm_ranges() {
+    for invalid in ["latest", "1.2.3.4", ">=", "1.2 || >=a"] {
+        assert!(
+            parse_range(invalid).is_err(),
+            "`{invalid}` should not parse"
+        );
+    }
+}
+
+#[cfg(feature = "json")]
+mod engines {
+    use super::*;
+
+    #[test]
+    fn real_packages() {
+        for (package, node) in [
+            // express
+            (r#"{"engines": {"node": ">= 0.10.0"}}"#, ">=0.10.0"),
+            // typescript
+            (r#"{"engines": {"node": ">=14.17"}}"#, ">=14.17.0"),
+            // npm
+            (
+                r#"{"engines": {"node": "^18.17.0 || >=20.5.0"}}"#,
+                "(>=18.17.0 && <19.0.0-0) || >=20.5.0",
+            ),
+            // eslint
+            (
+                r#"{"engines": {"node": "^18.18.0 || ^20.9.0 || >=21.1.0"}}"#,
+                "(>=18.18.0 && <19.0.0-0) || (>=20.9.0 && <21.0.0-0) || >=21.1.0",
+            ),
+            // old packages
+            (r#"{"engines": {"node": "*"}}"#, "*"),
+            (r#"{"engines": {"node": ""}}"#, "*"),
+            (r#"{"engines": {"node": ">= 14"}}"#, ">=14.0.0"),
+        ] {
+            let engines = engines_from_package_json(package).unwrap();
+            assert_eq!(engines.len(), 1);
+            assert_eq!(engines["node"].as_ref().unwrap(), &req(node), "{package}");
+        }
+    }
+
+    #[test]
+    fn invalid_entries_are_reported_per_key() {
+        let engines = engines_from_package_json(
+            r#"{
+                "name": "garbage",
+                "version": "1.0.0",
+                "engines": {"node": ">=8", "npm": "latest", "yarn": 1}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(engines["node"].as_ref().unwrap(), &req(">=8.0.0"));
+        assert!(matches!(
+            engines["npm"],
+            Err(InvalidEngine::InvalidRange { .. })
+        ));
+        assert!(matches!(engines["yarn"], Err(InvalidEngine::NotAString)));
+    }
+
+    #[test]
+    fn missing_engines() {
+        assert!(
+            engines_from_package_json(r#"{"name": "none"}"#)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn invalid_documents() {
+        assert!(matches!(
+            engines_from_package_json("{"),
+            Err(EnginesError::Json { .. })
+        ));
+        assert!(matches!(
+            engines_from_package_json(r#"{"engines": ["node >= 0.4"]}"#),
+            Err(EnginesError::NotAnObject { key: "engines" })
+        ));
+    }
+}
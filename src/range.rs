@@ -1,49 +1,78 @@
 use std::{
-    cmp::Reverse,
-    convert::identity,
+    borrow::Cow,
+    cmp::{Ordering, Reverse},
+    convert::{Infallible, identity},
     fmt::{Debug, Display},
+    str::FromStr,
 };
 
 use chumsky::{Parser, error::Rich};
-use itertools::Itertools;
-
+use itertools::{Either, EitherOrBoth, Itertools};
+
+mod builder;
+mod canonical;
+mod cover;
+mod diff;
+mod explained;
+mod extremes;
+mod interval;
+mod lazy;
 mod numeric_impls;
 mod parse;
-
-pub use parse::Extra as ParserExtra;
+#[cfg(feature = "pubgrub")]
+mod pubgrub_impls;
+mod simplify;
+mod sorted;
+mod style;
+
+pub use builder::RangesBuilder;
+pub use canonical::InvalidCanonical;
+pub use diff::RangesDiff;
+pub use explained::{ExplainedParseError, Unsatisfiable};
+use extremes::Extremes;
+pub use interval::{InvalidRange, NotContiguous, Range};
+pub use lazy::LazyReq;
+pub(crate) use parse::traced;
+pub use parse::{Error as ParserError, Extra as ParserExtra, ParseWarning};
+pub use sorted::NotSorted;
+pub use style::DisplayStyle;
 
 #[cfg(test)]
 mod tests;
 
 /// Required functions for being a range extreme
+///
+/// Ranges do not need sentinel values: an interval unbounded below or above is
+/// represented explicitly, so every value of the type can be used as an extreme.
 pub trait RangeExtreme: Ord + Clone {
-    /// Minimum value
+    /// Minimum value, if there is one
     ///
-    /// Every value should greather or equal of this
-    const MIN: Self;
-    /// Maximum value
-    ///
-    /// Every value should less or equal of this
-    const MAX: Self;
+    /// `T::minimum()` should be `Some(a)` if and only if every value is greater or equal of `a`.
+    /// Ranges starting from the minimum are normalized to ranges unbounded below.
+    fn minimum() -> Option<Self> {
+        None
+    }
 
     /// Next value
     ///
-    /// There should be no value `v` that satisfy `a < v < a.next()`
-    fn next(self) -> Self;
+    /// There should be no value `v` that satisfy `a < v < a.next()`.
+    /// If `a` is the maximum value this returns `None`.
+    fn next(self) -> Option<Self>;
 
     /// Compare value to next value
     ///
-    /// `a.compare_next_to(b)` should be true if and only if `a.next() == b`
+    /// `a.compare_next_to(b)` should be true if and only if `a.next() == Some(b)`
     fn compare_next_to(&self, other: &Self) -> bool {
-        &self.clone().next() == other
+        self.clone().next().as_ref() == Some(other)
     }
 }
 
 /// Required functions for the range to be displayed
 pub trait RangeExtremeDisplay: RangeExtreme + Display {
-    /// Check if this value has a previous value
+    /// Check if this value should be displayed through its previous value
     ///
-    /// `a.has_prev()` shoud be true if and only if it exist a value `b` so `b.next() == a`
+    /// `a.has_prev()` shoud be true only if it exist a value `b` so `b.next() == Some(a)`.
+    /// It can be false even if such a value exists, in which case `a` is displayed directly.
     fn has_prev(&self) -> bool;
 
     /// Display the previous value
@@ -55,8 +84,8 @@ pub trait RangeExtremeDisplay: RangeExtreme + Display {
 /// Marker trait for ranges extremes that can be parser
 ///
 /// Implementors must ensure that valid string representations must not
-///  - contain `||` or `&&`
-///  - start with `==`, `>`, `<`, `>=`, `<=`, `!` or `(`
+///  - contain `||`, `&&` or `,`
+///  - start with `=`, `>`, `<`, `!` or `(`
 ///  - end with `)`
 ///  - have surrounding whitespace
 ///  - be `*`.
@@ -64,13 +93,37 @@ pub trait RangeExtremeParseable: RangeExtreme {
     fn parser<'a>() -> impl Parser<'a, &'a str, Self, ParserExtra<'a>> + Clone;
 }
 
+/// Range extremes that can be written in the canonical form
+///
+/// Implementors promise that every value read by [`FromStr`], and every value built from them
+/// by [`RangeExtreme::next`], is written by [`Display`] in a form that reads back to an equal value.
+/// See [`Ranges::to_canonical_string`].
+pub trait RangeExtremeCanonical: RangeExtreme + Display + FromStr {}
+
+/// Range extremes that are bounded numbers, so ranges of them can be shifted
+pub trait RangeExtremeNumeric: RangeExtreme + Copy {
+    const MIN: Self;
+    const MAX: Self;
+
+    /// Add `delta`, saturating at [`RangeExtremeNumeric::MIN`] and [`RangeExtremeNumeric::MAX`]
+    fn saturating_add(self, delta: Self) -> Self;
+
+    /// Previous value, or `None` if this is the minimum
+    fn prev(self) -> Option<Self>;
+}
+
 /// A range of versions
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Ranges<T> {
+    /// Whether the range contains all the values below the last extreme
+    unbounded_below: bool,
     /// Sorted list of range extremes, in descending order
     ///
-    /// If the number of elements is odd, the last range is considered half-open
-    extremes: Vec<T>,
+    /// Each extreme is a point where the range switches from excluding to including values,
+    /// or viceversa. If the range is unbounded above, the first range is considered half-open.
+    /// The list is shared between clones, and copied only when modified.
+    /// Sharing needs `T: Sync` for the set to be `Send`.
+    extremes: Extremes<T>,
 }
 
 impl<T> Ranges<T>
@@ -78,7 +131,10 @@ where
     T: RangeExtreme,
 {
     /// Empty range
-    pub const EMPTY: Self = Self { extremes: vec![] };
+    pub const EMPTY: Self = Self {
+        unbounded_below: false,
+        extremes: Extremes::EMPTY,
+    };
 
     /// Create a new range from `start` to `end`, including `start` and excluding `end`
     pub fn between(start: T, end: T) -> Self {
@@ -86,46 +142,78 @@ where
         if start >= end {
             return Self::EMPTY;
         }
+        if is_min(&start) {
+            return Self {
+                unbounded_below: true,
+                extremes: vec![end].into(),
+            };
+        }
         Self {
-            extremes: vec![end, start],
+            unbounded_below: false,
+            extremes: vec![end, start].into(),
         }
     }
 
     /// Create a new range from `start` to `end`, excluding `start` and excluding `end`
     pub fn between_exclude_start(start: T, end: T) -> Self {
-        Self::between(start.next(), end)
+        match start.next() {
+            Some(start) => Self::between(start, end),
+            None => Self::EMPTY,
+        }
     }
 
     /// Create a new range from `start` to `end`, including `start` and including `end`
     pub fn between_include_end(start: T, end: T) -> Self {
-        Self::between(start, end.next())
+        match end.next() {
+            Some(end) => Self::between(start, end),
+            None => Self::from(start),
+        }
     }
 
     /// Create a new range from `start` to `end`, excluding `start` and including `end`
     pub fn between_exclude_start_include_end(start: T, end: T) -> Self {
-        Self::between(start.next(), end.next())
+        match start.next() {
+            Some(start) => Self::between_include_end(start, end),
+            None => Self::EMPTY,
+        }
     }
 
     /// Create a new range from `start` to infinity, including `start`
     pub fn from(start: T) -> Self {
+        if is_min(&start) {
+            return Self::full();
+        }
         Self {
-            extremes: vec![start],
+            unbounded_below: false,
+            extremes: vec![start].into(),
         }
     }
 
     /// Create a new range from `start` to infinity, excluding `start`
     pub fn from_exclusive(start: T) -> Self {
-        Self::from(start.next())
+        match start.next() {
+            Some(start) => Self::from(start),
+            None => Self::EMPTY,
+        }
     }
 
     /// Create a new range from negative infinity to `end`, excluding `end`
     pub fn to(end: T) -> Self {
-        Self::between(T::MIN, end)
+        if is_min(&end) {
+            return Self::EMPTY;
+        }
+        Self {
+            unbounded_below: true,
+            extremes: vec![end].into(),
+        }
     }
 
     /// Create a new range from negative infinity to `end`, including `end`
     pub fn to_inclusive(end: T) -> Self {
-        Self::to(end.next())
+        match end.next() {
+            Some(end) => Self::to(end),
+            None => Self::full(),
+        }
     }
 
     /// Create a range containing only one value.
@@ -133,6 +221,20 @@ where
         Self::between_include_end(value.clone(), value)
     }
 
+    /// Create a range containing only one value, pinning it exactly.
+    ///
+    /// This is the same as [`Ranges::single`].
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::pure::PureVersion};
+    /// let pin = Ranges::exact(PureVersion::new(1, 2, 3));
+    /// assert!(pin.contains(&PureVersion::new(1, 2, 3)));
+    /// assert!(!pin.contains(&PureVersion::new(1, 2, 4)));
+    /// ```
+    pub fn exact(value: T) -> Self {
+        Self::single(value)
+    }
+
     /// Create a range containing all values except one.
     pub fn except(value: T) -> Self {
         Self::single(value).not()
@@ -140,7 +242,20 @@ where
 
     /// Return whether the range contains exactly one element.
     pub fn is_single(&self) -> bool {
-        self.extremes.len() == 2 && self.extremes[1].compare_next_to(&self.extremes[0])
+        let mut ranges = self.ranges();
+        match (ranges.next(), ranges.next()) {
+            (Some((start, end)), None) => single_value(start, end).is_some(),
+            _ => false,
+        }
+    }
+
+    /// Return whether the range contains `value` and nothing else, like `==value`.
+    pub fn is_exactly(&self, value: &T) -> bool {
+        let mut ranges = self.ranges();
+        match (ranges.next(), ranges.next()) {
+            (Some((start, end)), None) => single_value(start, end).is_some_and(|v| *v == *value),
+            _ => false,
+        }
     }
 
     /// Return whether the range is empty.
@@ -150,81 +265,239 @@ where
 
     /// Return a range containing all values.
     pub fn full() -> Self {
-        Self::from(T::MIN)
+        Self {
+            unbounded_below: true,
+            extremes: Extremes::EMPTY,
+        }
+    }
+
+    /// Return a range containing all values, same as [`Ranges::full`]
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// assert!(Ranges::<u32>::any().contains(&42));
+    /// assert_eq!(Ranges::<u32>::any(), Ranges::from_str("*").unwrap());
+    /// ```
+    pub fn any() -> Self {
+        Self::full()
+    }
+
+    /// Return a range containing no values, same as [`Ranges::EMPTY`]
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// assert!(!Ranges::<u32>::none().contains(&42));
+    /// assert_eq!(Ranges::<u32>::none(), Ranges::from_str("-").unwrap());
+    /// ```
+    pub fn none() -> Self {
+        Self::EMPTY
     }
 
     /// Return whether the range contains all possible values.
     pub fn is_full(&self) -> bool {
-        self.extremes.len() == 1 && self.extremes[0] == T::MIN
+        self.unbounded_below && self.extremes.is_empty()
     }
 
     /// Return whether `value` is inside the range.
     pub fn contains(&self, value: &T) -> bool {
-        (self.extremes.len()
-            - self
-                .extremes
-                .binary_search_by_key(&Reverse(value), Reverse)
-                .unwrap_or_else(identity))
-            % 2
-            == 1
+        let above = self
+            .extremes
+            .binary_search_by_key(&Reverse(value), Reverse)
+            .unwrap_or_else(identity);
+        ((self.extremes.len() - above) % 2 == 1) != self.unbounded_below
     }
 
-    /// Return an iterator over the ranges in the range set
+    /// Return the interval containing `value`, if any
     ///
-    /// If the end is missing, the range is half-infinite
-    fn ranges(&self) -> impl IntoIterator<Item = (&T, Option<&T>)> {
-        let full_ranges = self.extremes.as_slice().rchunks_exact(2);
-        let remainder = full_ranges.remainder().first();
-        full_ranges
-            .map(|chunk| (&chunk[1], Some(&chunk[0])))
-            .chain(remainder.map(|chunk| (chunk, None)))
+    /// If the start or the end is missing, the interval is half-infinite
+    pub(crate) fn interval_of(&self, value: &T) -> Option<(Option<&T>, Option<&T>)> {
+        if !self.contains(value) {
+            return None;
+        }
+        let above = self
+            .extremes
+            .binary_search_by_key(&Reverse(value), Reverse)
+            .unwrap_or_else(identity);
+        Some((
+            self.extremes.get(above),
+            above.checked_sub(1).map(|i| &self.extremes[i]),
+        ))
     }
 
-    /// Return the negation of the range set
-    pub fn not(mut self) -> Self {
-        if self.extremes.last() == Some(&T::MIN) {
-            self.extremes.pop();
+    /// Return the disjoint intervals making up the set, in ascending order
+    ///
+    /// Each interval `(start, end)` contains the values `v` with `start <= v < end`.
+    /// A missing start or end means the interval is unbounded on that side.
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// let range = Ranges::<u32>::from_str("<3 || >=5 && <7").unwrap();
+    /// assert_eq!(
+    ///     range.intervals().collect::<Vec<_>>(),
+    ///     [(None, Some(&3)), (Some(&5), Some(&7))]
+    /// );
+    /// ```
+    pub fn intervals(&self) -> impl Iterator<Item = (Option<&T>, Option<&T>)> {
+        self.ranges()
+    }
+
+    /// Return the number of disjoint intervals making up the set
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// assert_eq!(Ranges::<u32>::except(3).num_intervals(), 2);
+    /// assert_eq!(Ranges::<u32>::EMPTY.num_intervals(), 0);
+    /// ```
+    pub fn num_intervals(&self) -> usize {
+        (self.extremes.len() + self.unbounded_below as usize).div_ceil(2)
+    }
+
+    /// Return whether the set is made of exactly one interval
+    ///
+    /// The empty set is not contiguous, while the full one is.
+    pub fn is_contiguous(&self) -> bool {
+        self.num_intervals() == 1
+    }
+
+    /// Return an iterator over the ranges in the range set, in ascending order
+    ///
+    /// If the start or the end is missing, the range is half-infinite
+    fn ranges(&self) -> impl Iterator<Item = (Option<&T>, Option<&T>)> {
+        self.unbounded_below
+            .then_some(None)
+            .into_iter()
+            .chain(self.extremes.iter().rev().map(Some))
+            .batching(|extremes| {
+                let start = extremes.next()?;
+                Some((start, extremes.next().flatten()))
+            })
+    }
+
+    /// Return the start of the first interval, or `None` if the range is unbounded below
+    ///
+    /// The result is meaningless for empty ranges
+    pub(crate) fn lowest(&self) -> Option<&T> {
+        if self.unbounded_below {
+            None
+        } else {
+            self.extremes.last()
+        }
+    }
+
+    /// Return the end of the last interval, or `None` if the range is unbounded above
+    ///
+    /// The result is meaningless for empty ranges
+    pub(crate) fn highest(&self) -> Option<&T> {
+        if self.is_unbounded_above() {
+            None
         } else {
-            self.extremes.push(T::MIN);
+            self.extremes.first()
+        }
+    }
+
+    /// Find a minimal subset of `sets` whose intersection is empty, as a mask over `sets`
+    ///
+    /// Each set is dropped in turn, keeping it out if the rest is still empty.
+    /// If the intersection of all the `sets` is not empty, all of them are kept.
+    pub(crate) fn minimal_conflict(sets: &[Self]) -> Vec<bool> {
+        let intersect = |keep: &[bool]| {
+            sets.iter()
+                .zip(keep)
+                .filter(|(_, keep)| **keep)
+                .fold(Self::full(), |acc, (set, _)| acc.and(set))
+        };
+        let mut keep = vec![true; sets.len()];
+        for idx in 0..sets.len() {
+            keep[idx] = false;
+            if !intersect(&keep).is_empty() {
+                keep[idx] = true;
+            }
         }
+        keep
+    }
+
+    /// Return whether the range contains all the values above the first extreme
+    fn is_unbounded_above(&self) -> bool {
+        (self.extremes.len() + self.unbounded_below as usize) % 2 == 1
+    }
+
+    /// Return the negation of the range set
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(mut self) -> Self {
+        self.unbounded_below = !self.unbounded_below;
         self
     }
 
+    /// Combine two range sets in place, applying `op` to the membership of each value
+    ///
+    /// `op(false, false)` must be `false` for the result to be well defined
+    /// above the last extreme of both sets.
+    /// The extremes of `self` are moved, only the ones of `other` that end up in the result are cloned.
+    fn combine_assign(&mut self, other: &Self, op: impl Fn(bool, bool) -> bool) {
+        let (mut in_self, mut in_other) = (self.unbounded_below, other.unbounded_below);
+        let unbounded_below = op(in_self, in_other);
+
+        let mut inside = unbounded_below;
+        // Move the extremes if they are not shared, otherwise clone the ones that are kept
+        let own = match self.extremes.take_unique() {
+            Some(unique) => Either::Left(unique.into_iter().rev().map(Cow::Owned)),
+            None => Either::Right(self.extremes.iter().rev().map(Cow::Borrowed)),
+        };
+        let mut extremes = Vec::with_capacity(own.size_hint().0 + other.extremes.len());
+        for extreme in own.merge_join_by(other.extremes.iter().rev(), |a, b| (**a).cmp(*b)) {
+            let extreme = match extreme {
+                EitherOrBoth::Left(extreme) => {
+                    in_self = !in_self;
+                    extreme
+                }
+                EitherOrBoth::Right(extreme) => {
+                    in_other = !in_other;
+                    Cow::Borrowed(extreme)
+                }
+                EitherOrBoth::Both(extreme, _) => {
+                    in_self = !in_self;
+                    in_other = !in_other;
+                    extreme
+                }
+            };
+            if op(in_self, in_other) != inside {
+                inside = !inside;
+                extremes.push(extreme.into_owned());
+            }
+        }
+        extremes.reverse();
+
+        self.unbounded_below = unbounded_below;
+        self.extremes.set(extremes);
+    }
+
     /// Return the union of two ranges set
     pub fn or(mut self, other: &Self) -> Self {
-        for (start, end) in other.ranges() {
-            let add_start = (!self.contains(start)).then_some(start);
-            let add_end = end.and_then(|end| (!self.contains(end)).then_some(end));
-
-            let i_start = self
-                .extremes
-                .binary_search_by_key(&Reverse(start), Reverse)
-                .unwrap_or_else(identity);
-            let i_end = end.map_or(0, |end| {
-                self.extremes
-                    .binary_search_by_key(&Reverse(end), Reverse)
-                    .unwrap_or_else(identity)
-            });
-
-            self.extremes.splice(
-                i_end..i_start,
-                [add_end, add_start].into_iter().flatten().cloned(),
-            );
-        }
+        self.or_assign(other);
         self
     }
 
-    /// Return the intersection of two ranges set
+    /// Replace the set with its union with `other`
+    ///
+    /// The result is normalized like the one of [`Ranges::or`], merging the intervals
+    /// that overlap or touch. No intermediate set is built.
+    pub fn or_assign(&mut self, other: &Self) {
+        self.combine_assign(other, |a, b| a || b)
+    }
+
+    /// Return the symmetric difference of many ranges set
     pub fn xor<'a>(ranges: impl IntoIterator<Item = &'a Self>) -> Self
     where
         T: 'a,
     {
+        let ranges = ranges.into_iter().collect_vec();
         // Each extreme is a point where the predicate passes from true to false.
         // Xor changes value each time it changes, so we can simply merge all the points
         // and deduplicate them
         Self {
+            unbounded_below: ranges.iter().fold(false, |acc, r| acc != r.unbounded_below),
             extremes: ranges
-                .into_iter()
+                .iter()
                 .map(|r| r.extremes.iter().map(Reverse))
                 .kmerge()
                 .map(|Reverse(extreme)| extreme)
@@ -236,65 +509,229 @@ where
     }
 
     /// Return the intersection of two ranges set
-    pub fn and(self, other: &Self) -> Self {
-        // Using the identity `a && b = a ^ b ^ (a || b)`
-        let or = self.clone().or(other);
-        Self::xor([&self, &other, &or])
+    pub fn and(mut self, other: &Self) -> Self {
+        self.and_assign(other);
+        self
     }
 
+    /// Replace the set with its intersection with `other`
+    ///
+    /// The result is normalized like the one of [`Ranges::and`], dropping the intervals
+    /// that become empty. No intermediate set is built.
+    pub fn and_assign(&mut self, other: &Self) {
+        self.combine_assign(other, |a, b| a && b)
+    }
+
+    /// Translate all the values by `delta`
+    ///
+    /// Values are translated with saturating arithmetic, so the values that would
+    /// overflow collapse into `T::MAX` (or `T::MIN`, for negative deltas).
+    pub fn shift(&self, delta: T) -> Self
+    where
+        T: RangeExtremeNumeric,
+    {
+        self.ranges()
+            .map(|(start, end)| {
+                let first = start.copied().unwrap_or(T::MIN);
+                let last = end.map_or(Some(T::MAX), |end| end.prev());
+                let last = last.expect("The end of an interval is never the minimum");
+                Self::between_include_end(first.saturating_add(delta), last.saturating_add(delta))
+            })
+            .fold(Self::EMPTY, |acc, interval| acc.or(&interval))
+    }
+
+    /// Apply `f` to every extreme of the range
+    ///
+    /// `f` must be strictly monotone (`a < b` implies `f(a) < f(b)`) and must map
+    /// values with no previous value to values with no previous value, so that the mapped
+    /// extremes describe the same intervals. Violations of the ordering are caught
+    /// by debug assertions.
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// let ranges = Ranges::<u32>::between(3, 7).map(u64::from);
+    /// assert_eq!(ranges, Ranges::<u64>::between(3, 7));
+    /// ```
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Ranges<U>
+    where
+        U: RangeExtreme,
+    {
+        let Ok(ranges) = self.try_map(|value| Ok::<_, Infallible>(f(value)));
+        ranges
+    }
+
+    /// Apply the fallible `f` to every extreme of the range, stopping at the first error
+    ///
+    /// See [`Ranges::map`] for the requirements on `f`.
+    pub fn try_map<U, E>(self, f: impl FnMut(T) -> Result<U, E>) -> Result<Ranges<U>, E>
+    where
+        U: RangeExtreme,
+    {
+        let mut extremes: Vec<U> = self.extremes.into_vec().into_iter().map(f).try_collect()?;
+        debug_assert!(
+            extremes.is_sorted_by(|a, b| a > b),
+            "The mapping function must be strictly monotone"
+        );
+        let mut unbounded_below = self.unbounded_below;
+        if !unbounded_below && extremes.last().is_some_and(is_min) {
+            extremes.pop();
+            unbounded_below = true;
+        }
+        Ok(Ranges {
+            unbounded_below,
+            extremes: extremes.into(),
+        })
+    }
+
+    /// Parse a range
+    ///
+    /// Constraints are combined with `&&` and `||`, with `&&` binding tighter.
+    /// Commas are accepted as an alias of `&&` for compatibility with the legacy syntax,
+    /// binding looser than `&&` but tighter than `||`: `>=1, <2 || ==5` is
+    /// `(>=1 && <2) || ==5`.
+    /// Exact matches can be written with a single `=`, so `=1` is the same as `==1`.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str<'a>(s: &'a str) -> Result<Self, Vec<Rich<'a, char>>>
     where
         T: RangeExtremeParseable + 'a,
     {
-        parse::parser().parse(s).into_result()
+        parse::parse(s)
+    }
+
+    /// Parser for a range, to embed it in other [`chumsky`] grammars
+    ///
+    /// The type of the parser is opaque and might change, only the accepted grammar is stable.
+    /// Whitespace around the range is consumed.
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// use chumsky::prelude::*;
+    ///
+    /// let requirement = just("requires")
+    ///     .ignore_then(Ranges::<u32>::parser())
+    ///     .then_ignore(just(';'));
+    /// let range = requirement.parse("requires >=3 && <5 ;").into_result().unwrap();
+    /// assert_eq!(range, Ranges::between(3, 5));
+    /// ```
+    pub fn parser<'a>() -> impl Parser<'a, &'a str, Self, ParserExtra<'a>> + Clone
+    where
+        T: RangeExtremeParseable + 'a,
+    {
+        parse::parser()
+    }
+
+    /// Parse the longest range at the start of `s`, returning the rest of the input
+    ///
+    /// Whitespace after the range is consumed.
+    pub fn parse_prefix<'a>(s: &'a str) -> Result<(Self, &'a str), Vec<Rich<'a, char>>>
+    where
+        T: RangeExtremeParseable + 'a,
+    {
+        parse::parser()
+            .then(chumsky::prelude::any().repeated().to_slice())
+            .parse(s)
+            .into_result()
+    }
+
+    /// Parse a range, dropping the constraints that cannot be parsed
+    ///
+    /// Each dropped constraint is reported with a [`ParseWarning`]. What a dropped
+    /// constraint stands for depends on where it appears:
+    ///  - a term of an `&&` is treated as no constraint, so `>=1 && ?` is `>=1`;
+    ///  - a branch of an `||` is treated as empty, so `<1 || ?` is `<1`.
+    ///
+    /// A negated constraint is dropped together with its negation. A parenthesized group,
+    /// or a whole `&&`, is dropped only if all its constraints are dropped.
+    /// If nothing can be salvaged the range is empty.
+    pub fn parse_lossy(s: &str) -> (Self, Vec<ParseWarning>)
+    where
+        T: RangeExtremeParseable,
+    {
+        parse::lossy(s)
     }
 }
 
-impl<T> Display for Ranges<T>
+/// Ranges are ordered by their lowest value, then by their highest value.
+///
+/// The empty range is ordered after all the others. Ranges with the same lowest and highest
+/// values are ordered by comparing their intervals in ascending order, so that the ordering
+/// is consistent with equality.
+impl<T> PartialOrd for Ranges<T>
 where
-    T: RangeExtremeDisplay,
+    T: RangeExtreme,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.is_empty() {
-            return write!(f, "-");
-        }
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-        if self.is_full() {
-            return write!(f, "*");
+impl<T> Ord for Ranges<T>
+where
+    T: RangeExtreme,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_empty(), other.is_empty()) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => return Ordering::Greater,
+            (false, true) => return Ordering::Less,
+            (false, false) => (),
         }
 
-        for (i, (start, end)) in self.ranges().into_iter().enumerate() {
-            if i > 0 {
-                write!(f, " || ")?;
-            }
+        self.lowest()
+            .cmp(&other.lowest())
+            .then_with(|| cmp_ends(self.highest(), other.highest()))
+            .then_with(|| {
+                self.ranges()
+                    .zip_longest(other.ranges())
+                    .map(|pair| match pair {
+                        EitherOrBoth::Both((a_start, a_end), (b_start, b_end)) => {
+                            a_start.cmp(&b_start).then_with(|| cmp_ends(a_end, b_end))
+                        }
+                        EitherOrBoth::Left(_) => Ordering::Greater,
+                        EitherOrBoth::Right(_) => Ordering::Less,
+                    })
+                    .find(|ord| ord.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+    }
+}
 
-            if start.compare_next_to(end.unwrap_or(&T::MAX)) {
-                write!(f, "=={}", start)?;
-                continue;
-            }
+/// Compare two interval ends, where a missing end is greater than all the others
+fn cmp_ends<T: Ord>(a: Option<&T>, b: Option<&T>) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => a.cmp(b),
+    }
+}
 
-            if start != &T::MIN {
-                if start.has_prev() {
-                    write!(f, ">")?;
-                    start.display_prev(f)?;
-                } else {
-                    write!(f, ">={}", start)?;
-                }
+/// Check if `value` is the minimum value
+fn is_min<T: RangeExtreme>(value: &T) -> bool {
+    T::minimum().as_ref() == Some(value)
+}
 
-                if end.is_some() {
-                    write!(f, " && ")?;
-                }
-            }
+/// Return the only value in the interval from `start` to `end`, if there is exactly one
+fn single_value<'a, T: RangeExtreme>(start: Option<&'a T>, end: Option<&T>) -> Option<Cow<'a, T>> {
+    match (start, end) {
+        (Some(start), Some(end)) => start.compare_next_to(end).then_some(Cow::Borrowed(start)),
+        (Some(start), None) => start
+            .clone()
+            .next()
+            .is_none()
+            .then_some(Cow::Borrowed(start)),
+        (None, Some(end)) => T::minimum()
+            .filter(|min| min.compare_next_to(end))
+            .map(Cow::Owned),
+        (None, None) => None,
+    }
+}
 
-            if let Some(end) = end {
-                if end.has_prev() {
-                    write!(f, "<=")?;
-                    end.display_prev(f)?;
-                } else {
-                    write!(f, "<{}", end)?;
-                }
-            }
-        }
-        Ok(())
+impl<T> Display for Ranges<T>
+where
+    T: RangeExtremeDisplay,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        style::display_ranges(self, &DisplayStyle::DEFAULT, f)
     }
 }
@@ -0,0 +1,247 @@
+//! Chainable construction of range sets
+
+use std::convert::Infallible;
+
+use super::{RangeExtreme, Ranges};
+
+#[cfg(test)]
+mod tests;
+
+/// Builder of a [`Ranges`], created by [`Ranges::build`] or [`Ranges::try_build`]
+///
+/// The result is the intersection of the constraints, united with each group added by
+/// [`RangesBuilder::or_group`]: `b.gte(a).lt(c).or_group(g)` is `>=a && <c || (g)`.
+/// A builder with no constraints contains everything, unless it has groups, in which
+/// case it is only their union.
+///
+/// Values are taken as anything convertible to `T`. Conversion errors are reported
+/// by [`RangesBuilder::try_finish`], that returns the first of them.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct RangesBuilder<T, E = Infallible> {
+    state: Result<State<T>, E>,
+}
+
+#[derive(Debug, Clone)]
+struct State<T> {
+    constrained: bool,
+    /// The included lower bound, if any
+    lower: Option<T>,
+    /// The excluded upper bound, if any
+    upper: Option<T>,
+    /// Whether a bound excluded every value
+    empty: bool,
+    excluded: Vec<T>,
+    groups: Ranges<T>,
+}
+
+impl<T> Ranges<T>
+where
+    T: RangeExtreme,
+{
+    /// Start building a range set from constraints
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// let range = Ranges::<u32>::build()
+    ///     .gte(3u32)
+    ///     .lt(10u32)
+    ///     .ne(5u32)
+    ///     .or_group(|b| b.eq(20u32))
+    ///     .finish();
+    /// assert_eq!(range, Ranges::from_str(">=3 && <10 && !=5 || ==20").unwrap());
+    /// ```
+    pub fn build() -> RangesBuilder<T> {
+        RangesBuilder::new()
+    }
+
+    /// Start building a range set from constraints, with values that can fail to convert
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::pure::{InvalidPureVersion, PureVersion}};
+    /// let range = Ranges::<PureVersion>::try_build::<InvalidPureVersion>()
+    ///     .gte("1.2.0")
+    ///     .lt("2.0.0")
+    ///     .try_finish()
+    ///     .unwrap();
+    /// assert_eq!(range, Ranges::from_str(">=1.2.0 && <2.0.0").unwrap());
+    /// ```
+    pub fn try_build<E>() -> RangesBuilder<T, E> {
+        RangesBuilder::new()
+    }
+}
+
+impl<T, E> RangesBuilder<T, E>
+where
+    T: RangeExtreme,
+{
+    fn new() -> Self {
+        Self {
+            state: Ok(State {
+                constrained: false,
+                lower: None,
+                upper: None,
+                empty: false,
+                excluded: vec![],
+                groups: Ranges::EMPTY,
+            }),
+        }
+    }
+
+    /// Convert `value` and apply `f` to the state, keeping the first error
+    fn with<V>(mut self, value: V, f: impl FnOnce(&mut State<T>, T)) -> Self
+    where
+        V: TryInto<T>,
+        V::Error: Into<E>,
+    {
+        if let Ok(state) = &mut self.state {
+            match value.try_into() {
+                Ok(value) => {
+                    state.constrained = true;
+                    f(state, value)
+                }
+                Err(err) => self.state = Err(err.into()),
+            }
+        }
+        self
+    }
+
+    /// Allow only values greater or equal to `value`
+    pub fn gte<V>(self, value: V) -> Self
+    where
+        V: TryInto<T>,
+        V::Error: Into<E>,
+    {
+        self.with(value, State::lower)
+    }
+
+    /// Allow only values greater than `value`
+    pub fn gt<V>(self, value: V) -> Self
+    where
+        V: TryInto<T>,
+        V::Error: Into<E>,
+    {
+        self.with(value, |state, value| match value.next() {
+            Some(value) => state.lower(value),
+            None => state.empty = true,
+        })
+    }
+
+    /// Allow only values less than `value`
+    pub fn lt<V>(self, value: V) -> Self
+    where
+        V: TryInto<T>,
+        V::Error: Into<E>,
+    {
+        self.with(value, State::upper)
+    }
+
+    /// Allow only values less or equal to `value`
+    pub fn lte<V>(self, value: V) -> Self
+    where
+        V: TryInto<T>,
+        V::Error: Into<E>,
+    {
+        self.with(value, |state, value| {
+            if let Some(value) = value.next() {
+                state.upper(value)
+            }
+        })
+    }
+
+    /// Allow only `value`
+    pub fn eq<V>(self, value: V) -> Self
+    where
+        V: TryInto<T>,
+        V::Error: Into<E>,
+    {
+        self.with(value, |state, value| {
+            state.lower(value.clone());
+            if let Some(value) = value.next() {
+                state.upper(value)
+            }
+        })
+    }
+
+    /// Exclude `value`
+    pub fn ne<V>(self, value: V) -> Self
+    where
+        V: TryInto<T>,
+        V::Error: Into<E>,
+    {
+        self.with(value, |state, value| state.excluded.push(value))
+    }
+
+    /// Add the alternative built by `group`
+    pub fn or_group(mut self, group: impl FnOnce(Self) -> Self) -> Self {
+        if self.state.is_ok() {
+            let group = group(Self::new());
+            match (&mut self.state, group.try_finish()) {
+                (Ok(state), Ok(group)) => state.groups.or_assign(&group),
+                (_, Err(err)) => self.state = Err(err),
+                (Err(_), _) => unreachable!("Checked above"),
+            }
+        }
+        self
+    }
+
+    /// Build the range set, or return the first conversion error
+    pub fn try_finish(self) -> Result<Ranges<T>, E> {
+        let State {
+            constrained,
+            lower,
+            upper,
+            empty,
+            excluded,
+            mut groups,
+        } = self.state?;
+        if !constrained && !groups.is_empty() {
+            return Ok(groups);
+        }
+        if !empty {
+            let interval = match (lower, upper) {
+                (Some(lower), Some(upper)) => Ranges::between(lower, upper),
+                (Some(lower), None) => Ranges::from(lower),
+                (None, Some(upper)) => Ranges::to(upper),
+                (None, None) => Ranges::full(),
+            };
+            let interval = if excluded.is_empty() {
+                interval
+            } else {
+                interval.and(&Ranges::excluding(excluded))
+            };
+            groups.or_assign(&interval);
+        }
+        Ok(groups)
+    }
+}
+
+impl<T> RangesBuilder<T>
+where
+    T: RangeExtreme,
+{
+    /// Build the range set
+    pub fn finish(self) -> Ranges<T> {
+        match self.try_finish() {
+            Ok(ranges) => ranges,
+            Err(never) => match never {},
+        }
+    }
+}
+
+impl<T> State<T>
+where
+    T: RangeExtreme,
+{
+    fn lower(&mut self, value: T) {
+        if self.lower.as_ref().is_none_or(|lower| *lower < value) {
+            self.lower = Some(value)
+        }
+    }
+
+    fn upper(&mut self, value: T) {
+        if self.upper.as_ref().is_none_or(|upper| *upper > value) {
+            self.upper = Some(value)
+        }
+    }
+}
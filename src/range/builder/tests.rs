@@ -0,0 +1,99 @@
+use crate::{
+    range::Ranges,
+    version::pure::{InvalidPureVersion, PureVersion},
+};
+
+fn v(s: &str) -> PureVersion {
+    s.parse().unwrap()
+}
+
+fn r(s: &str) -> Ranges<PureVersion> {
+    Ranges::from_str(s).unwrap()
+}
+
+#[test]
+fn conjunction() {
+    assert_eq!(
+        Ranges::build().gte(v("1.2.0")).lt(v("2.0.0")).finish(),
+        r(">=1.2.0 && <2.0.0")
+    );
+    assert_eq!(
+        Ranges::build()
+            .gt(v("1.2.0"))
+            .lte(v("1.9.0"))
+            .ne(v("1.5.0"))
+            .ne(v("1.5.1"))
+            .finish(),
+        r(">1.2.0 && <=1.9.0 && !=1.5.0 && !=1.5.1")
+    );
+    assert_eq!(Ranges::build().eq(v("1.2.3")).finish(), r("==1.2.3"));
+}
+
+#[test]
+fn tightest_bounds_win() {
+    assert_eq!(
+        Ranges::build()
+            .gte(v("1.0.0"))
+            .gte(v("1.4.0"))
+            .lt(v("3.0.0"))
+            .lte(v("2.0.0"))
+            .finish(),
+        r(">=1.0.0 && >=1.4.0 && <3.0.0 && <=2.0.0")
+    );
+    assert!(
+        Ranges::<PureVersion>::build()
+            .gte(v("2.0.0"))
+            .lt(v("1.0.0"))
+            .finish()
+            .is_empty()
+    );
+    assert!(Ranges::<u8>::build().gt(u8::MAX).finish().is_empty());
+    assert_eq!(Ranges::build().lte(u8::MAX).finish(), Ranges::<u8>::full());
+}
+
+#[test]
+fn groups() {
+    assert_eq!(
+        Ranges::build()
+            .gte(v("1.2.0"))
+            .lt(v("1.3.0"))
+            .or_group(|b| b.gte(v("2.0.0")).ne(v("2.1.0")))
+            .or_group(|b| b.eq(v("0.9.0")))
+            .finish(),
+        r(">=1.2.0 && <1.3.0 || >=2.0.0 && !=2.1.0 || ==0.9.0")
+    );
+    // Without constraints, only the groups are allowed
+    assert_eq!(
+        Ranges::build()
+            .or_group(|b| b.lt(v("1.0.0")))
+            .or_group(|b| b.gte(v("2.0.0")).or_group(|b| b.eq(v("1.5.0"))))
+            .finish(),
+        r("<1.0.0 || >=2.0.0 || ==1.5.0")
+    );
+    assert!(Ranges::<u8>::build().finish().is_full());
+}
+
+#[test]
+fn fallible_values() {
+    let range = Ranges::<PureVersion>::try_build::<InvalidPureVersion>()
+        .gte("1.2.0")
+        .lt("2.0.0")
+        .or_group(|b| b.eq("3.0.0-rc.1"))
+        .try_finish()
+        .unwrap();
+    assert_eq!(range, r(">=1.2.0 && <2.0.0 || ==3.0.0-rc.1"));
+
+    assert!(
+        Ranges::<PureVersion>::try_build::<InvalidPureVersion>()
+            .gte("1.2")
+            .lt("2.0.0")
+            .try_finish()
+            .is_err()
+    );
+    assert!(
+        Ranges::<PureVersion>::try_build::<InvalidPureVersion>()
+            .or_group(|b| b.ne("latest"))
+            .try_finish()
+            .is_err()
+    );
+}
@@ -0,0 +1,136 @@
+//! Canonical string form of range sets
+//!
+//! The canonical form lists the disjoint intervals of the set in ascending order,
+//! separated by `|`. Each interval is written as `>=a,<b`, `>=a` if unbounded above,
+//! or `<b` if unbounded below. The empty set is `-`, the full set is `*`. There is no
+//! whitespace, and values are written with their [`Display`](std::fmt::Display) implementation.
+//!
+//! The canonical form is available for extremes implementing [`RangeExtremeCanonical`], whose
+//! values all read back from their written form. As [`Ranges`] are always normalized, each set
+//! of them has exactly one canonical form.
+
+use snafu::Snafu;
+
+use super::{RangeExtremeCanonical, Ranges, is_min};
+
+#[cfg(test)]
+mod tests;
+
+impl<T> Ranges<T>
+where
+    T: RangeExtremeCanonical,
+{
+    /// Write the set in the canonical form
+    ///
+    /// Two sets are equal if and only if their canonical forms are equal, so the result can be
+    /// used as a key for deduplication. See [`Ranges::from_canonical`] to read it back.
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// let range = Ranges::<u32>::from_str("<3 || >=5 && <7 || >10").unwrap();
+    /// assert_eq!(range.to_canonical_string(), "<3|>=5,<7|>=11");
+    /// ```
+    pub fn to_canonical_string(&self) -> String {
+        if self.is_empty() {
+            return "-".to_owned();
+        }
+        if self.is_full() {
+            return "*".to_owned();
+        }
+        let mut s = String::new();
+        for (idx, (start, end)) in self.ranges().enumerate() {
+            if idx > 0 {
+                s.push('|');
+            }
+            match (start, end) {
+                (Some(start), Some(end)) => s.push_str(&format!(">={start},<{end}")),
+                (Some(start), None) => s.push_str(&format!(">={start}")),
+                (None, Some(end)) => s.push_str(&format!("<{end}")),
+                (None, None) => unreachable!("Only the full set has an unbounded interval"),
+            }
+        }
+        s
+    }
+
+    /// Read a set written by [`Ranges::to_canonical_string`]
+    ///
+    /// This is a single pass over the input, without the general grammar of
+    /// [`Ranges::from_str`]. Anything that is not exactly a canonical form is rejected,
+    /// including values that [`FromStr`](std::str::FromStr) accepts but
+    /// [`Display`](std::fmt::Display) writes differently.
+    pub fn from_canonical(s: &str) -> Result<Self, InvalidCanonical> {
+        match s {
+            "-" => return Ok(Self::EMPTY),
+            "*" => return Ok(Self::full()),
+            _ => (),
+        }
+
+        let mut unbounded_below = false;
+        // Extremes in ascending order
+        let mut extremes: Vec<T> = vec![];
+        let mut unbounded_above = false;
+        for (idx, interval) in s.split('|').enumerate() {
+            if unbounded_above {
+                return NotNormalizedSnafu.fail();
+            }
+            if let Some(end) = interval.strip_prefix('<') {
+                if idx > 0 {
+                    return NotNormalizedSnafu.fail();
+                }
+                let end = value(end)?;
+                if is_min(&end) {
+                    return NotNormalizedSnafu.fail();
+                }
+                unbounded_below = true;
+                extremes.push(end);
+            } else if let Some(interval) = interval.strip_prefix(">=") {
+                let (start, end) = match interval.split_once(",<") {
+                    Some((start, end)) => (start, Some(end)),
+                    None => (interval, None),
+                };
+                let start = value(start)?;
+                if is_min(&start) {
+                    return NotNormalizedSnafu.fail();
+                }
+                extremes.push(start);
+                match end {
+                    Some(end) => extremes.push(value(end)?),
+                    None => unbounded_above = true,
+                }
+            } else {
+                return InvalidIntervalSnafu { interval }.fail();
+            }
+        }
+
+        // Strict ordering also excludes adjacent intervals, that should have been merged
+        if !extremes.windows(2).all(|pair| pair[0] < pair[1]) {
+            return NotNormalizedSnafu.fail();
+        }
+        extremes.reverse();
+        Ok(Self {
+            unbounded_below,
+            extremes: extremes.into(),
+        })
+    }
+}
+
+/// Parse a value, checking that it is written in the canonical form
+fn value<T>(s: &str) -> Result<T, InvalidCanonical>
+where
+    T: RangeExtremeCanonical,
+{
+    s.parse::<T>()
+        .ok()
+        .filter(|value| value.to_string() == s)
+        .ok_or_else(|| InvalidValueSnafu { value: s }.build())
+}
+
+#[derive(Debug, Clone, Snafu)]
+pub enum InvalidCanonical {
+    #[snafu(display("Invalid interval `{interval}`"))]
+    InvalidInterval { interval: String },
+    #[snafu(display("`{value}` is not a value in canonical form"))]
+    InvalidValue { value: String },
+    #[snafu(display("The intervals must be non empty, separated and in ascending order"))]
+    NotNormalized,
+}
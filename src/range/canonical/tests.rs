@@ -0,0 +1,187 @@
+use super::InvalidCanonical;
+use crate::{
+    range::{RangeExtremeCanonical, RangeExtremeParseable, Ranges},
+    version::{
+        calver::CalVer, extended::ExtendedVersion, pep440::Pep440Version, pure::PureVersion,
+    },
+};
+
+/// All the sets over `u8` with at most two intervals inside `0..6`, and some around the limits
+fn u8_samples() -> Vec<Ranges<u8>> {
+    let mut samples = vec![
+        Ranges::EMPTY,
+        Ranges::full(),
+        Ranges::single(u8::MAX),
+        Ranges::except(u8::MAX),
+        Ranges::from(200),
+    ];
+    for a in 0..6 {
+        for b in a..6 {
+            let first = Ranges::between(a, b);
+            samples.push(first.clone());
+            for c in b..6 {
+                samples.push(first.clone().or(&Ranges::from(c)));
+                for d in c..6 {
+                    samples.push(first.clone().or(&Ranges::between(c, d)));
+                }
+            }
+        }
+    }
+    samples
+}
+
+fn version_samples() -> Vec<Ranges<PureVersion>> {
+    [
+        "-",
+        "*",
+        "==1.2.3",
+        "!=1.2.3",
+        ">=1.0.0 && <2.0.0",
+        ">1.0.0-rc.1 && <=1.0.0",
+        "<1.0.0 || >=2.0.0-0 && <3.0.0 || >4.0.0",
+        ">=0.0.0 && <0.1.0",
+    ]
+    .into_iter()
+    .map(|s| Ranges::from_str(s).unwrap())
+    .collect()
+}
+
+#[test]
+fn roundtrips() {
+    for range in u8_samples() {
+        let s = range.to_canonical_string();
+        assert_eq!(Ranges::<u8>::from_canonical(&s).unwrap(), range, "{s}");
+    }
+    for range in version_samples() {
+        let s = range.to_canonical_string();
+        assert_eq!(Ranges::from_canonical(&s).unwrap(), range, "{s}");
+    }
+}
+
+/// Check that the requirements, and all their extremes, read back from their written form
+fn check_extremes_roundtrip<T>(reqs: &[&str])
+where
+    T: RangeExtremeCanonical + RangeExtremeParseable + std::fmt::Debug,
+{
+    for req in reqs {
+        let range = Ranges::<T>::from_str(req).unwrap();
+        for extreme in range
+            .intervals()
+            .flat_map(|(start, end)| [start, end])
+            .flatten()
+        {
+            let s = extreme.to_string();
+            assert_eq!(s.parse::<T>().ok().as_ref(), Some(extreme), "{req}: {s}");
+        }
+        let s = range.to_canonical_string();
+        assert_eq!(
+            Ranges::<T>::from_canonical(&s).unwrap(),
+            range,
+            "{req}: {s}"
+        );
+    }
+}
+
+macro_rules! check_integers_roundtrip {
+    ($($t:ty)*) => {
+        $(
+            check_extremes_roundtrip::<$t>(&["==5", "!=5", ">5", "<=5", ">=0", "<=0", ">100"]);
+        )*
+    };
+}
+
+#[test]
+fn every_extreme_type_roundtrips() {
+    check_integers_roundtrip!(u8 u16 u32 u64 u128 i8 i16 i32 i64 i128);
+    check_extremes_roundtrip::<PureVersion>(&["==1.2.3", "!=1.2.3-rc.1", ">1.2.3", "<=1.2.3-rc.1"]);
+    check_extremes_roundtrip::<CalVer>(&[
+        "==2024.01.1",
+        "!=2024.12.0",
+        ">2024.01.1-rc.1",
+        "<=2024.12.5",
+    ]);
+    check_extremes_roundtrip::<Pep440Version>(&[
+        "==1.0",
+        "!=1!2.0rc1",
+        ">1.0+ubuntu.1",
+        "<=1.0.post1",
+    ]);
+    #[cfg(feature = "deb")]
+    check_extremes_roundtrip::<crate::version::deb::DebVersion>(&[
+        "==1.0-1",
+        "!=1:2.0~rc1",
+        ">1.0",
+        "<=1.0+dfsg-2",
+    ]);
+    check_extremes_roundtrip::<ExtendedVersion>(&[
+        "==1.2.3.4",
+        "!=1.2",
+        ">1.2.3.4-rc.1",
+        "<=1.2.3.4.5",
+    ]);
+}
+
+#[test]
+fn unique_per_set() {
+    let samples = u8_samples();
+    for a in &samples {
+        for b in &samples {
+            assert_eq!(
+                a.to_canonical_string() == b.to_canonical_string(),
+                a == b,
+                "{a} and {b}"
+            );
+        }
+    }
+}
+
+#[test]
+fn format() {
+    let v = |s| {
+        Ranges::<PureVersion>::from_str(s)
+            .unwrap()
+            .to_canonical_string()
+    };
+    assert_eq!(v("-"), "-");
+    assert_eq!(v("*"), "*");
+    assert_eq!(v("==1.2.3"), ">=1.2.3,<1.2.4-0");
+    assert_eq!(v("<1.0.0 || >=2.0.0"), "<1.0.0|>=2.0.0");
+}
+
+#[test]
+fn rejects_non_canonical() {
+    for s in [
+        "",
+        " >=1",
+        ">=1 ,<3",
+        ">=1,<3|",
+        ">1",
+        ">=01",
+        ">=+1",
+        ">=0",
+        "<0",
+        ">=3,<3",
+        ">=3,<1",
+        ">=1,<3|>=3,<5",
+        ">=1,<3|>=2,<5",
+        ">=1|>=5",
+        ">=1,<3|<5",
+        "*|>=1",
+        "-|>=1",
+        ">=1,<3 || >=5",
+    ] {
+        assert!(Ranges::<u8>::from_canonical(s).is_err(), "{s:?}");
+    }
+}
+
+#[test]
+fn rejects_non_canonical_versions() {
+    assert!(matches!(
+        Ranges::<PureVersion>::from_canonical(">=1.2.3,<v2.0.0"),
+        Err(InvalidCanonical::InvalidValue { .. })
+    ));
+    assert!(matches!(
+        Ranges::<PureVersion>::from_canonical(">=0.0.0-0,<1.0.0"),
+        Err(InvalidCanonical::NotNormalized)
+    ));
+}
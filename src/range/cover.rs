@@ -0,0 +1,111 @@
+//! Range sets covering a given collection of values
+
+use std::collections::BTreeSet;
+
+use itertools::Itertools;
+
+use super::{RangeExtreme, Ranges, is_min};
+
+#[cfg(test)]
+mod tests;
+
+impl<T> Ranges<T>
+where
+    T: RangeExtreme,
+{
+    /// Create the set containing exactly the given values
+    ///
+    /// Same as [`Ranges::from_versions`].
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// let range = Ranges::covering([7u32, 3, 4]);
+    /// assert_eq!(range, Ranges::between(3, 5).or(&Ranges::single(7)));
+    /// ```
+    pub fn covering(values: impl IntoIterator<Item = T>) -> Self {
+        Self::from_versions(values)
+    }
+
+    /// Create the set containing exactly the given values
+    ///
+    /// The values are sorted once, and consecutive ones are merged in the same interval.
+    /// Duplicates are ignored.
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// let range = Ranges::from_versions([5u32, 3, 4, 3, 9]);
+    /// assert_eq!(range, Ranges::between(3, 6).or(&Ranges::single(9)));
+    /// ```
+    pub fn from_versions(values: impl IntoIterator<Item = T>) -> Self {
+        let mut values: Vec<T> = values.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+
+        let mut unbounded_below = false;
+        // Built in ascending order, and reversed at the end
+        let mut extremes: Vec<T> = Vec::with_capacity(values.len() * 2);
+        for value in values {
+            match extremes.last() {
+                // Adjacent to the last interval, that is extended
+                Some(end) if *end == value => {
+                    extremes.pop();
+                }
+                None if is_min(&value) => unbounded_below = true,
+                _ => extremes.push(value.clone()),
+            }
+            match value.next() {
+                Some(end) => extremes.push(end),
+                // The maximum value is the last one
+                None => break,
+            }
+        }
+        extremes.reverse();
+        Self {
+            unbounded_below,
+            extremes: extremes.into(),
+        }
+    }
+
+    /// Create the set containing everything but the given values
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// let range = Ranges::excluding([3u32, 4]);
+    /// assert_eq!(range, Ranges::to(3).or(&Ranges::from(5)));
+    /// ```
+    pub fn excluding(values: impl IntoIterator<Item = T>) -> Self {
+        Self::from_versions(values).not()
+    }
+
+    /// Create the set with the fewest intervals containing `supported`, and excluding every
+    /// other value of `universe`
+    ///
+    /// Supported values that are consecutive in `universe` are merged into a single interval,
+    /// spanning from the first to the last of them. Values outside `universe` are not
+    /// considered, apart from the supported ones, so the result may contain them.
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// let published = [1u32, 2, 5, 8, 9];
+    /// let range = Ranges::tightest_over(&[1, 2, 5, 9], &published);
+    /// assert_eq!(range, Ranges::between_include_end(1, 5).or(&Ranges::single(9)));
+    /// ```
+    pub fn tightest_over(supported: &[T], universe: &[T]) -> Self {
+        let supported_set: BTreeSet<&T> = supported.iter().collect();
+        let values: BTreeSet<&T> = universe.iter().chain(supported).collect();
+
+        let mut range = Self::EMPTY;
+        for (is_supported, mut run) in &values
+            .into_iter()
+            .chunk_by(|value| supported_set.contains(value))
+        {
+            if !is_supported {
+                continue;
+            }
+            let first = run.next().expect("Chunks are never empty");
+            let last = run.last().unwrap_or(first);
+            range.or_assign(&Self::between_include_end(first.clone(), last.clone()));
+        }
+        range
+    }
+}
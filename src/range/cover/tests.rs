@@ -0,0 +1,143 @@
+use crate::{range::Ranges, version::pure::PureVersion};
+
+fn v(s: &str) -> PureVersion {
+    s.parse().unwrap()
+}
+
+fn vs(versions: &[&str]) -> Vec<PureVersion> {
+    versions.iter().map(|s| v(s)).collect()
+}
+
+fn universe() -> Vec<PureVersion> {
+    vs(&[
+        "1.0.0",
+        "1.1.0",
+        "1.2.0",
+        "1.2.1",
+        "1.3.0",
+        "2.0.0-rc.1",
+        "2.0.0",
+        "2.1.0",
+    ])
+}
+
+#[test]
+fn covering_is_union_of_singles() {
+    let range = Ranges::covering(vs(&["1.2.0", "1.0.0", "1.2.0"]));
+    assert_eq!(
+        range,
+        Ranges::single(v("1.0.0")).or(&Ranges::single(v("1.2.0")))
+    );
+    assert!(Ranges::<PureVersion>::covering([]).is_empty());
+}
+
+#[test]
+fn tightest_over_interleaved() {
+    let supported = vs(&["1.0.0", "1.2.0", "1.2.1", "2.0.0", "2.1.0"]);
+    let range = Ranges::tightest_over(&supported, &universe());
+
+    assert_eq!(range.intervals().count(), 3);
+    for version in universe() {
+        assert_eq!(
+            range.contains(&version),
+            supported.contains(&version),
+            "{version}"
+        );
+    }
+    assert_eq!(
+        range.to_string(),
+        "==1.0.0 || >=1.2.0 && <=1.2.1 || >=2.0.0 && <=2.1.0"
+    );
+}
+
+#[test]
+fn tightest_over_contiguous() {
+    let supported = vs(&["1.1.0", "1.2.0", "1.2.1", "1.3.0"]);
+    let range = Ranges::tightest_over(&supported, &universe());
+    assert_eq!(range, Ranges::between_include_end(v("1.1.0"), v("1.3.0")));
+}
+
+#[test]
+fn tightest_over_outside_universe() {
+    let range = Ranges::tightest_over(&vs(&["1.1.0", "1.1.5"]), &universe());
+    assert_eq!(range, Ranges::between_include_end(v("1.1.0"), v("1.1.5")));
+    assert!(Ranges::<PureVersion>::tightest_over(&[], &universe()).is_empty());
+}
+
+/// Values around the limits of `u8`, and some consecutive ones
+const U8_VALUES: [u8; 8] = [0, 1, 2, 3, 5, 128, 254, 255];
+
+#[test]
+fn from_versions_on_every_subset() {
+    for mask in 0..1u32 << U8_VALUES.len() {
+        let members: Vec<u8> = U8_VALUES
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| mask & 1 << idx != 0)
+            .map(|(_, value)| *value)
+            .collect();
+        // Duplicates in reverse order
+        let input = members.iter().chain(members.iter().rev()).copied();
+
+        let range = Ranges::from_versions(input.clone());
+        let expected = members.iter().fold(Ranges::EMPTY, |range, value| {
+            range.or(&Ranges::single(*value))
+        });
+        assert_eq!(range, expected, "{members:?}");
+
+        let excluding = Ranges::excluding(input);
+        assert_eq!(excluding, expected.not(), "{members:?}");
+
+        for value in u8::MIN..=u8::MAX {
+            assert_eq!(range.contains(&value), members.contains(&value), "{value}");
+            assert_eq!(
+                excluding.contains(&value),
+                !members.contains(&value),
+                "{value}"
+            );
+        }
+    }
+}
+
+#[test]
+fn from_versions_merges_consecutive() {
+    assert_eq!(
+        Ranges::from_versions([4u8, 2, 3, 0, 255])
+            .intervals()
+            .count(),
+        3
+    );
+    assert!(Ranges::from_versions(u8::MIN..=u8::MAX).is_full());
+    assert!(Ranges::excluding(u8::MIN..=u8::MAX).is_empty());
+    assert!(Ranges::<u8>::excluding([]).is_full());
+}
+
+#[test]
+fn from_versions_with_prereleases() {
+    let members = vs(&[
+        "1.0.0-alpha",
+        "1.0.0-alpha.0",
+        "1.0.0",
+        "1.0.0-alpha",
+        "2.0.0-rc.1",
+    ]);
+    let range = Ranges::from_versions(members.clone());
+    let excluding = Ranges::excluding(members.clone());
+    // `1.0.0-alpha.0` is right after `1.0.0-alpha`
+    assert_eq!(range.intervals().count(), 3);
+
+    for version in &members {
+        assert!(range.contains(version), "{version}");
+        assert!(!excluding.contains(version), "{version}");
+    }
+    for version in vs(&[
+        "1.0.0-alpha.1",
+        "1.0.0-beta",
+        "1.0.1",
+        "2.0.0-rc.1.0",
+        "0.0.0",
+    ]) {
+        assert!(!range.contains(&version), "{version}");
+        assert!(excluding.contains(&version), "{version}");
+    }
+}
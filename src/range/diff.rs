@@ -0,0 +1,68 @@
+//! Changes between two range sets
+
+use std::fmt::Display;
+
+use super::{RangeExtreme, RangeExtremeDisplay, Ranges};
+
+#[cfg(test)]
+mod tests;
+
+/// The values allowed and disallowed by changing a range set, see [`Ranges::diff`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangesDiff<T> {
+    /// Values allowed only by the newer set
+    pub added: Ranges<T>,
+    /// Values allowed only by the older set
+    pub removed: Ranges<T>,
+}
+
+impl<T> RangesDiff<T>
+where
+    T: RangeExtreme,
+{
+    /// Return whether the two sets allow the same values
+    pub fn is_unchanged(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl<T> Ranges<T>
+where
+    T: RangeExtreme,
+{
+    /// Compare the set with a `newer` one, returning the values that are added and removed
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::pure::PureVersion};
+    /// let older = Ranges::<PureVersion>::from_str(">=1.2.0 && <2.0.0").unwrap();
+    /// let newer = Ranges::from_str(">=1.4.0 && <2.0.0 || ==2.1.0").unwrap();
+    /// assert_eq!(
+    ///     older.diff(&newer).to_string(),
+    ///     "now allows: ==2.1.0; no longer allows: >=1.2.0 && <1.4.0"
+    /// );
+    /// ```
+    pub fn diff(&self, newer: &Self) -> RangesDiff<T> {
+        RangesDiff {
+            added: newer.clone().and(&self.clone().not()),
+            removed: self.clone().and(&newer.clone().not()),
+        }
+    }
+}
+
+impl<T> Display for RangesDiff<T>
+where
+    T: RangeExtremeDisplay,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.added.is_empty(), self.removed.is_empty()) {
+            (true, true) => write!(f, "unchanged"),
+            (false, true) => write!(f, "now allows: {}", self.added),
+            (true, false) => write!(f, "no longer allows: {}", self.removed),
+            (false, false) => write!(
+                f,
+                "now allows: {}; no longer allows: {}",
+                self.added, self.removed
+            ),
+        }
+    }
+}
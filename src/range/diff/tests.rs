@@ -0,0 +1,42 @@
+use crate::{range::Ranges, version::pure::PureVersion};
+
+fn r(s: &str) -> Ranges<PureVersion> {
+    Ranges::from_str(s).unwrap()
+}
+
+#[test]
+fn unchanged() {
+    let diff = r(">=1.2.0 && <2.0.0").diff(&r("<2.0.0 && >=1.2.0"));
+    assert!(diff.is_unchanged());
+    assert_eq!(diff.to_string(), "unchanged");
+}
+
+#[test]
+fn widen_only() {
+    let diff = r(">=1.2.0 && <2.0.0").diff(&r(">=1.0.0 && <2.0.0"));
+    assert!(!diff.is_unchanged());
+    assert_eq!(diff.added, r(">=1.0.0 && <1.2.0"));
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.to_string(), "now allows: >=1.0.0 && <1.2.0");
+}
+
+#[test]
+fn narrow_only() {
+    let diff = r(">=1.0.0").diff(&r(">=1.0.0 && <3.0.0"));
+    assert!(diff.added.is_empty());
+    assert_eq!(diff.removed, r(">=3.0.0"));
+    assert_eq!(diff.to_string(), "no longer allows: >=3.0.0");
+}
+
+#[test]
+fn mixed() {
+    let older = r(">=1.2.0 && <2.0.0");
+    let newer = r(">=1.4.0 && <2.0.0 || ==2.1.0");
+    let diff = older.diff(&newer);
+    assert_eq!(diff.added, r("==2.1.0"));
+    assert_eq!(diff.removed, r(">=1.2.0 && <1.4.0"));
+
+    // Applying the diff to the older set gives the newer one
+    let applied = older.and(&diff.removed.clone().not()).or(&diff.added);
+    assert_eq!(applied, newer);
+}
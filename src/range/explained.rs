@@ -0,0 +1,112 @@
+//! Parsing with an explanation of why the result is empty
+
+use chumsky::error::Rich;
+use itertools::Itertools;
+use snafu::Snafu;
+
+use super::{
+    RangeExtremeParseable, Ranges,
+    parse::{is_parenthesized, split_top_level},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Error of [`Ranges::parse_explained`]
+#[derive(Debug, Clone, Snafu)]
+pub enum ExplainedParseError {
+    #[snafu(display("Invalid requirement: {}", errors.iter().join("; ")))]
+    Invalid { errors: Vec<Rich<'static, char>> },
+    #[snafu(transparent)]
+    Unsatisfiable { source: Unsatisfiable },
+}
+
+/// The requirement is valid, but no value satisfies it
+///
+/// Holds a minimal set of conflicting constraints for each alternative of the requirement.
+/// Removing any constraint of a set makes it satisfiable.
+#[derive(Debug, Clone, PartialEq, Eq, Snafu)]
+#[snafu(display("{}", conflicts.iter().map(|c| describe(c)).join("; ")))]
+pub struct Unsatisfiable {
+    pub conflicts: Vec<Vec<String>>,
+}
+
+fn describe(conflict: &[String]) -> String {
+    match conflict {
+        [single] => format!("`{single}` matches nothing"),
+        [a, b] => format!("`{a}` conflicts with `{b}`"),
+        [rest @ .., last] => format!(
+            "{} and `{last}` conflict",
+            rest.iter().map(|c| format!("`{c}`")).join(", ")
+        ),
+        [] => "no constraint".to_owned(),
+    }
+}
+
+impl<T> Ranges<T>
+where
+    T: RangeExtremeParseable,
+{
+    /// Parse a range, explaining why it is empty if no value satisfies it
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::pure::PureVersion};
+    /// let err = Ranges::<PureVersion>::parse_explained(">=2.0.0 && !=3.0.0 && <1.0.0").unwrap_err();
+    /// assert_eq!(err.to_string(), "`>=2.0.0` conflicts with `<1.0.0`");
+    /// ```
+    pub fn parse_explained(s: &str) -> Result<Self, ExplainedParseError> {
+        let ranges = Self::from_str(s).map_err(|errors| ExplainedParseError::Invalid {
+            errors: errors.into_iter().map(Rich::into_owned).collect(),
+        })?;
+        if !ranges.is_empty() {
+            return Ok(ranges);
+        }
+        let conflicts = split_top_level(unwrap_parens(s), "||")
+            .map(|(_, branch)| minimal_conflict::<T>(conjuncts(branch)))
+            .collect();
+        Err(Unsatisfiable { conflicts }.into())
+    }
+}
+
+/// Remove the parentheses wrapping the whole input
+fn unwrap_parens(mut s: &str) -> &str {
+    s = s.trim();
+    while is_parenthesized(s) {
+        s = s[1..s.len() - 1].trim();
+    }
+    s
+}
+
+/// Split an intersection in its terms, flattening the nested intersections
+fn conjuncts(s: &str) -> Vec<&str> {
+    let inner = unwrap_parens(s);
+    if split_top_level(inner, "||").nth(1).is_some() {
+        // Keep the parentheses, so the term reads unambiguously
+        return vec![s.trim()];
+    }
+    let terms: Vec<&str> = split_top_level(inner, ",")
+        .flat_map(|(_, group)| split_top_level(group, "&&"))
+        .map(|(_, term)| term.trim())
+        .collect();
+    if terms.len() == 1 {
+        return terms;
+    }
+    terms.into_iter().flat_map(conjuncts).collect()
+}
+
+/// Find a minimal subset of the terms whose intersection is empty
+fn minimal_conflict<T>(terms: Vec<&str>) -> Vec<String>
+where
+    T: RangeExtremeParseable,
+{
+    let parsed: Vec<Ranges<T>> = terms
+        .iter()
+        .map(|term| Ranges::from_str(term).expect("The whole requirement was valid"))
+        .collect();
+    terms
+        .into_iter()
+        .zip(Ranges::minimal_conflict(&parsed))
+        .filter(|(_, keep)| *keep)
+        .map(|(term, _)| term.to_owned())
+        .collect()
+}
@@ -0,0 +1,66 @@
+use super::{ExplainedParseError, Unsatisfiable};
+use crate::{range::Ranges, version::pure::PureVersion};
+
+fn conflicts(s: &str) -> Vec<Vec<String>> {
+    match Ranges::<PureVersion>::parse_explained(s) {
+        Err(ExplainedParseError::Unsatisfiable {
+            source: Unsatisfiable { conflicts },
+        }) => conflicts,
+        other => panic!("{s} should be unsatisfiable, got {other:?}"),
+    }
+}
+
+#[test]
+fn satisfiable_is_parsed() {
+    assert_eq!(
+        Ranges::<PureVersion>::parse_explained(">=1.0.0 && <2.0.0").unwrap(),
+        Ranges::from_str(">=1.0.0 && <2.0.0").unwrap()
+    );
+}
+
+#[test]
+fn invalid_is_reported() {
+    assert!(matches!(
+        Ranges::<PureVersion>::parse_explained(">=1.0.0 &&"),
+        Err(ExplainedParseError::Invalid { .. })
+    ));
+}
+
+#[test]
+fn two_constraints() {
+    assert_eq!(conflicts(">=2.0.0 && <1.0.0"), [[">=2.0.0", "<1.0.0"]]);
+    assert_eq!(
+        Ranges::<PureVersion>::parse_explained(">=1.5.0, >=2.0.0 && <1.0.0")
+            .unwrap_err()
+            .to_string(),
+        "`>=2.0.0` conflicts with `<1.0.0`"
+    );
+}
+
+#[test]
+fn three_constraints() {
+    // Every pair is satisfiable, but not all three together
+    let s = ">=1.0.0 && <3.0.0 && (<1.5.0 || >=2.0.0) && (>=1.5.0 && <2.0.0 || ==9.0.0)";
+    assert_eq!(
+        conflicts(s),
+        [[
+            "<3.0.0",
+            "(<1.5.0 || >=2.0.0)",
+            "(>=1.5.0 && <2.0.0 || ==9.0.0)"
+        ]]
+    );
+    assert_eq!(
+        Ranges::<PureVersion>::parse_explained(s)
+            .unwrap_err()
+            .to_string(),
+        "`<3.0.0`, `(<1.5.0 || >=2.0.0)` and `(>=1.5.0 && <2.0.0 || ==9.0.0)` conflict"
+    );
+}
+
+#[test]
+fn each_alternative_is_explained() {
+    assert_eq!(
+        conflicts("(>=2.0.0 && (<1.0.0 && >0.1.0)) || ==1.0.0 && !=1.0.0"),
+        [vec![">=2.0.0", "<1.0.0"], vec!["==1.0.0", "!=1.0.0"]]
+    );
+}
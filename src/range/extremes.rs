@@ -0,0 +1,106 @@
+//! Shared storage for the extremes of a range set
+
+use std::{fmt::Debug, ops::Deref, sync::Arc};
+
+/// Copy-on-write list of extremes
+///
+/// Cloning only bumps a reference count. The list is copied when a shared one
+/// is modified, while a list owned by a single set keeps its storage. The empty list
+/// does not allocate.
+#[derive(Clone)]
+pub(super) struct Extremes<T>(Option<Arc<Vec<T>>>);
+
+impl<T> Extremes<T> {
+    pub(super) const EMPTY: Self = Self(None);
+
+    /// Return the list, cloning it only if shared
+    pub(super) fn into_vec(self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.0
+            .map(|extremes| Arc::try_unwrap(extremes).unwrap_or_else(|shared| (*shared).clone()))
+            .unwrap_or_default()
+    }
+
+    /// Take the list out if no other set shares it, leaving the storage to be reused by
+    /// [`Extremes::set`]
+    pub(super) fn take_unique(&mut self) -> Option<Vec<T>> {
+        match &mut self.0 {
+            None => Some(vec![]),
+            Some(extremes) => Arc::get_mut(extremes).map(std::mem::take),
+        }
+    }
+
+    /// Replace the list, reusing the storage if no other set shares it
+    ///
+    /// A shared storage is kept if the list is unchanged.
+    pub(super) fn set(&mut self, extremes: Vec<T>)
+    where
+        T: PartialEq,
+    {
+        if extremes.is_empty() {
+            self.0 = None;
+            return;
+        }
+        match &mut self.0 {
+            Some(storage) => {
+                if let Some(unique) = Arc::get_mut(storage) {
+                    *unique = extremes;
+                } else if **storage != extremes {
+                    *storage = Arc::new(extremes);
+                }
+            }
+            None => self.0 = Some(Arc::new(extremes)),
+        }
+    }
+
+    /// Return whether the two lists share the same storage
+    #[cfg(test)]
+    pub(super) fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    /// Address of the storage, if the list is not empty
+    #[cfg(test)]
+    pub(super) fn storage_ptr(&self) -> Option<*const Vec<T>> {
+        self.0.as_ref().map(Arc::as_ptr)
+    }
+}
+
+impl<T> From<Vec<T>> for Extremes<T> {
+    fn from(extremes: Vec<T>) -> Self {
+        Self((!extremes.is_empty()).then(|| Arc::new(extremes)))
+    }
+}
+
+impl<T> FromIterator<T> for Extremes<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Vec::from_iter(iter).into()
+    }
+}
+
+impl<T> Deref for Extremes<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_deref().map_or(&[], Vec::as_slice)
+    }
+}
+
+impl<T: PartialEq> PartialEq for Extremes<T> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Eq> Eq for Extremes<T> {}
+
+impl<T: Debug> Debug for Extremes<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
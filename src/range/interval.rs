@@ -0,0 +1,327 @@
+use std::{fmt::Display, ops::Bound, str::FromStr};
+
+use chumsky::error::Rich;
+use itertools::Itertools;
+use snafu::Snafu;
+
+use super::{
+    DisplayStyle, RangeExtreme, RangeExtremeDisplay, RangeExtremeNumeric, RangeExtremeParseable,
+    Ranges, style,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A contiguous range of values
+///
+/// Contains all the values `v` such that `start <= v < end`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Range<T> {
+    /// Start and end of the range, or `None` if the range is empty
+    ///
+    /// If the start or the end is missing, the range is half-infinite
+    bounds: Option<(Option<T>, Option<T>)>,
+}
+
+impl<T> Range<T>
+where
+    T: RangeExtreme,
+{
+    /// Empty range
+    pub const EMPTY: Self = Self { bounds: None };
+
+    /// Create a new range from `start` to `end`, including `start` and excluding `end`
+    pub fn between(start: T, end: T) -> Self {
+        Ranges::between(start, end)
+            .try_into()
+            .expect("A single interval is always contiguous")
+    }
+
+    /// Create a new range from `start` to infinity, including `start`
+    pub fn from(start: T) -> Self {
+        Ranges::from(start)
+            .try_into()
+            .expect("A single interval is always contiguous")
+    }
+
+    /// Create a new range from negative infinity to `end`, excluding `end`
+    pub fn to(end: T) -> Self {
+        Ranges::to(end)
+            .try_into()
+            .expect("A single interval is always contiguous")
+    }
+
+    /// Create a new range from a pair of [`Bound`]s
+    ///
+    /// Excluded starts and included ends are converted using [`RangeExtreme::next`].
+    pub fn from_bounds(start: Bound<T>, end: Bound<T>) -> Self {
+        let start = match start {
+            Bound::Included(start) => Some(start),
+            Bound::Excluded(start) => match start.next() {
+                Some(start) => Some(start),
+                None => return Self::EMPTY,
+            },
+            Bound::Unbounded => None,
+        };
+        let end = match end {
+            Bound::Included(end) => end.next(),
+            Bound::Excluded(end) => Some(end),
+            Bound::Unbounded => None,
+        };
+        let ranges = match (start, end) {
+            (Some(start), Some(end)) => Ranges::between(start, end),
+            (Some(start), None) => Ranges::from(start),
+            (None, Some(end)) => Ranges::to(end),
+            (None, None) => Ranges::full(),
+        };
+        ranges
+            .try_into()
+            .expect("A single interval is always contiguous")
+    }
+
+    /// Return a range containing all values.
+    pub fn full() -> Self {
+        Self {
+            bounds: Some((None, None)),
+        }
+    }
+
+    /// Start and end of the range, or `None` if the range is empty
+    pub(super) fn bounds(&self) -> Option<(Option<&T>, Option<&T>)> {
+        self.bounds
+            .as_ref()
+            .map(|(start, end)| (start.as_ref(), end.as_ref()))
+    }
+
+    /// Start of the range, always included if bounded, or `None` if the range is empty
+    ///
+    /// A range starting from the minimum value is unbounded.
+    ///
+    /// ```
+    /// # use areq::range::Range;
+    /// use std::ops::Bound;
+    ///
+    /// assert_eq!(Range::between(3u32, 7).start_bound(), Some(Bound::Included(&3)));
+    /// assert_eq!(Range::between(0u32, 7).start_bound(), Some(Bound::Unbounded));
+    /// assert_eq!(Range::<u32>::EMPTY.start_bound(), None);
+    /// ```
+    pub fn start_bound(&self) -> Option<Bound<&T>> {
+        let (start, _) = self.bounds()?;
+        Some(start.map_or(Bound::Unbounded, Bound::Included))
+    }
+
+    /// End of the range, always excluded if bounded, or `None` if the range is empty
+    ///
+    /// A range including the maximum value is unbounded.
+    ///
+    /// ```
+    /// # use areq::range::Range;
+    /// use std::ops::Bound;
+    ///
+    /// assert_eq!(Range::between(3u32, 7).end_bound(), Some(Bound::Excluded(&7)));
+    /// assert_eq!(Range::from(3u32).end_bound(), Some(Bound::Unbounded));
+    /// ```
+    pub fn end_bound(&self) -> Option<Bound<&T>> {
+        let (_, end) = self.bounds()?;
+        Some(end.map_or(Bound::Unbounded, Bound::Excluded))
+    }
+
+    /// Return whether the range is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_none()
+    }
+
+    /// Return whether the range contains all possible values.
+    pub fn is_full(&self) -> bool {
+        matches!(self.bounds, Some((None, None)))
+    }
+
+    /// Return whether `value` is inside the range.
+    pub fn contains(&self, value: &T) -> bool {
+        self.bounds.as_ref().is_some_and(|(start, end)| {
+            start.as_ref().is_none_or(|start| start <= value)
+                && end.as_ref().is_none_or(|end| value < end)
+        })
+    }
+
+    /// Return the values outside the range, as up to two intervals
+    ///
+    /// ```
+    /// # use areq::range::{Range, Ranges};
+    /// let outside = Range::between(2, 5).complement();
+    /// assert_eq!(outside, Ranges::to(2).or(&Ranges::from(5)));
+    /// ```
+    pub fn complement(&self) -> Ranges<T> {
+        let ranges: Ranges<T> = self.clone().into();
+        ranges.not()
+    }
+
+    /// Translate all the values by `delta`, saturating at the numeric limits
+    ///
+    /// See [`Ranges::shift`].
+    pub fn shift(self, delta: T) -> Self
+    where
+        T: RangeExtremeNumeric,
+    {
+        let ranges: Ranges<T> = self.into();
+        ranges
+            .shift(delta)
+            .try_into()
+            .expect("Shifting a contiguous range keeps it contiguous")
+    }
+
+    /// Apply `f` to both ends of the range
+    ///
+    /// See [`Ranges::map`] for the requirements on `f`.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Range<U>
+    where
+        U: RangeExtreme,
+    {
+        let ranges: Ranges<T> = self.into();
+        ranges
+            .map(f)
+            .try_into()
+            .expect("Mapping a contiguous range keeps it contiguous")
+    }
+
+    /// Apply the fallible `f` to both ends of the range, stopping at the first error
+    ///
+    /// See [`Ranges::map`] for the requirements on `f`.
+    pub fn try_map<U, E>(self, f: impl FnMut(T) -> Result<U, E>) -> Result<Range<U>, E>
+    where
+        U: RangeExtreme,
+    {
+        let ranges: Ranges<T> = self.into();
+        Ok(ranges
+            .try_map(f)?
+            .try_into()
+            .expect("Mapping a contiguous range keeps it contiguous"))
+    }
+}
+
+impl Range<u64> {
+    /// Position of `value` inside the range, counting from the start
+    ///
+    /// Returns `None` if the value is not in the range. An unbounded start counts from `0`.
+    ///
+    /// ```
+    /// # use areq::range::Range;
+    /// let range = Range::between(10u64, 20);
+    /// assert_eq!(range.index_of(&13), Some(3));
+    /// assert_eq!(range.index_of(&20), None);
+    /// ```
+    pub fn index_of(&self, value: &u64) -> Option<u64> {
+        if !self.contains(value) {
+            return None;
+        }
+        let (start, _) = self.bounds.as_ref()?;
+        Some(value - start.unwrap_or(0))
+    }
+
+    /// Value at position `idx` inside the range, the inverse of [`Range::index_of`]
+    ///
+    /// Returns `None` if the range has less than `idx + 1` values.
+    pub fn nth(&self, idx: u64) -> Option<u64> {
+        let (start, _) = self.bounds.as_ref()?;
+        let value = start.unwrap_or(0).checked_add(idx)?;
+        self.contains(&value).then_some(value)
+    }
+}
+
+impl<T> Ranges<T>
+where
+    T: RangeExtreme,
+{
+    /// Return the smallest contiguous range containing the whole set
+    ///
+    /// ```
+    /// # use areq::range::{Range, Ranges};
+    /// let set = Ranges::<u32>::from_str("<3 || >=5 && <7").unwrap();
+    /// assert_eq!(set.hull(), Range::to(7));
+    /// ```
+    pub fn hull(&self) -> Range<T> {
+        if self.is_empty() {
+            return Range::EMPTY;
+        }
+        Range {
+            bounds: Some((self.lowest().cloned(), self.highest().cloned())),
+        }
+    }
+}
+
+impl<T> From<Range<T>> for Ranges<T>
+where
+    T: RangeExtreme,
+{
+    fn from(range: Range<T>) -> Self {
+        match range.bounds {
+            None => Ranges::EMPTY,
+            Some((None, None)) => Ranges::full(),
+            Some((None, Some(end))) => Ranges::to(end),
+            Some((Some(start), None)) => Ranges::from(start),
+            Some((Some(start), Some(end))) => Ranges::between(start, end),
+        }
+    }
+}
+
+impl<T> TryFrom<Ranges<T>> for Range<T>
+where
+    T: RangeExtreme,
+{
+    type Error = NotContiguous;
+
+    fn try_from(ranges: Ranges<T>) -> Result<Self, Self::Error> {
+        let intervals = ranges.num_intervals();
+        let mut extremes = ranges.extremes.into_vec();
+        let bounds = match (ranges.unbounded_below, extremes.len()) {
+            (false, 0) => None,
+            (true, 0) => Some((None, None)),
+            (true, 1) => Some((None, extremes.pop())),
+            (false, 1) => Some((extremes.pop(), None)),
+            (false, 2) => Some((extremes.pop(), extremes.pop())),
+            _ => return Err(NotContiguous { intervals }),
+        };
+        Ok(Self { bounds })
+    }
+}
+
+impl<T> Display for Range<T>
+where
+    T: RangeExtremeDisplay,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        style::display_range(self, &DisplayStyle::DEFAULT, f)
+    }
+}
+
+/// Parse a range using the same grammar as [`Ranges`]
+///
+/// The expression must describe a contiguous set of values.
+impl<T> FromStr for Range<T>
+where
+    T: RangeExtremeParseable,
+{
+    type Err = InvalidRange;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ranges = super::parse::parse::<T>(s).map_err(|errors| InvalidRange::Syntax {
+            errors: errors.into_iter().map(Rich::into_owned).collect(),
+        })?;
+        Ok(ranges.try_into()?)
+    }
+}
+
+/// The set of values cannot be represented by a single [`Range`]
+#[derive(Debug, Clone, Snafu)]
+#[snafu(display("The set of values is made of {intervals} disjoint intervals, not one"))]
+pub struct NotContiguous {
+    pub intervals: usize,
+}
+
+#[derive(Debug, Clone, Snafu)]
+pub enum InvalidRange {
+    #[snafu(display("Invalid range syntax: {}", errors.iter().join("; ")))]
+    Syntax { errors: Vec<Rich<'static, char>> },
+    #[snafu(transparent)]
+    NotContiguous { source: NotContiguous },
+}
@@ -0,0 +1,212 @@
+use std::{ops::Bound, str::FromStr};
+
+use super::{InvalidRange, Range};
+use crate::{range::Ranges, version::pure::PureVersion};
+
+#[test]
+fn parse_contiguous() {
+    assert_eq!(
+        Range::<u64>::from_str(">=3 && <10").unwrap(),
+        Range::between(3, 10)
+    );
+    assert_eq!(
+        Range::<u64>::from_str(">3 && <=10").unwrap(),
+        Range::between(4, 11)
+    );
+    assert_eq!(Range::<u64>::from_str(">=3").unwrap(), Range::from(3));
+    assert_eq!(Range::<u64>::from_str("<3").unwrap(), Range::to(3));
+    assert_eq!(Range::<u64>::from_str("==3").unwrap(), Range::between(3, 4));
+    assert_eq!(Range::<u64>::from_str("*").unwrap(), Range::full());
+    assert_eq!(Range::<u64>::from_str("-").unwrap(), Range::EMPTY);
+}
+
+#[test]
+fn parse_negation() {
+    assert_eq!(Range::<u64>::from_str("!(<3)").unwrap(), Range::from(3));
+    assert_eq!(Range::<u64>::from_str("!*").unwrap(), Range::EMPTY);
+}
+
+#[test]
+fn parse_versions() {
+    let range = Range::<PureVersion>::from_str(">=1.0.0 && <2.0.0").unwrap();
+
+    assert!(range.contains(&PureVersion::new(1, 5, 0)));
+    assert!(!range.contains(&PureVersion::new(2, 0, 0)));
+}
+
+#[test]
+fn non_contiguous_is_error() {
+    assert!(matches!(
+        Range::<u64>::from_str("!=3"),
+        Err(InvalidRange::NotContiguous { source }) if source.intervals == 2
+    ));
+    assert!(matches!(
+        Range::<u64>::from_str("<3 || >5 && <7 || >10"),
+        Err(InvalidRange::NotContiguous { source }) if source.intervals == 3
+    ));
+}
+
+#[test]
+fn invalid_syntax_is_error() {
+    assert!(matches!(
+        Range::<u64>::from_str(">=3 &&"),
+        Err(InvalidRange::Syntax { .. })
+    ));
+}
+
+#[test]
+fn agrees_with_ranges() {
+    for s in [">=3 && <10", ">3", "<=10", "==5", "*", "-", "!(>=4)"] {
+        let range = Range::<u64>::from_str(s).unwrap();
+        let ranges: Ranges<u64> = range.clone().into();
+        assert_eq!(ranges, Ranges::from_str(s).unwrap());
+        assert_eq!(range.to_string(), ranges.to_string());
+    }
+}
+
+#[test]
+fn shift() {
+    assert_eq!(Range::between(1u64, 5).shift(10), Range::between(11, 15));
+    assert_eq!(Range::from(250u8).shift(10), Range::from(255));
+    assert_eq!(Range::<u8>::full().shift(10), Range::from(10));
+    assert_eq!(Range::<u8>::EMPTY.shift(10), Range::EMPTY);
+}
+
+#[test]
+fn from_bounds() {
+    use std::ops::{Bound, RangeBounds};
+
+    let bounds = |v: u8| [Bound::Included(v), Bound::Excluded(v), Bound::Unbounded];
+    for (low, high) in [
+        (3, 7),
+        (7, 3),
+        (5, 5),
+        (0, 255),
+        (255, 0),
+        (0, 0),
+        (255, 255),
+    ] {
+        for start in bounds(low) {
+            for end in bounds(high) {
+                let range = Range::from_bounds(start, end);
+                for v in u8::MIN..=u8::MAX {
+                    assert_eq!(
+                        range.contains(&v),
+                        (start, end).contains(&v),
+                        "{v} in ({start:?}, {end:?})"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn index_of_and_nth_roundtrip() {
+    let range = Range::<u64>::from_str(">=10, <20").unwrap();
+    for (idx, value) in (10..20).enumerate() {
+        assert_eq!(range.index_of(&value), Some(idx as u64));
+        assert_eq!(range.nth(idx as u64), Some(value));
+    }
+    assert_eq!(range.index_of(&9), None);
+    assert_eq!(range.index_of(&20), None);
+    assert_eq!(range.nth(10), None);
+}
+
+#[test]
+fn index_of_and_nth_at_the_limits() {
+    let full = Range::<u64>::full();
+    assert_eq!(full.index_of(&0), Some(0));
+    assert_eq!(full.index_of(&u64::MAX), Some(u64::MAX));
+    assert_eq!(full.nth(u64::MAX), Some(u64::MAX));
+
+    let upper = Range::from(u64::MAX - 1);
+    assert_eq!(upper.nth(1), Some(u64::MAX));
+    assert_eq!(upper.nth(2), None);
+
+    assert_eq!(Range::<u64>::EMPTY.index_of(&0), None);
+    assert_eq!(Range::<u64>::EMPTY.nth(0), None);
+}
+
+#[test]
+fn map() {
+    assert_eq!(
+        Range::<u32>::between(3, 10).map(u64::from),
+        Range::<u64>::between(3, 10)
+    );
+    assert_eq!(Range::<u32>::EMPTY.map(u64::from), Range::<u64>::EMPTY);
+    assert_eq!(
+        Range::<u64>::from(300).try_map(u8::try_from),
+        Err(u8::try_from(300u64).unwrap_err())
+    );
+}
+
+#[test]
+fn from_bounds_at_max() {
+    use std::ops::Bound;
+
+    assert_eq!(
+        Range::<u8>::from_bounds(Bound::Excluded(u8::MAX), Bound::Unbounded),
+        Range::EMPTY
+    );
+    assert_eq!(
+        Range::<u8>::from_bounds(Bound::Included(3), Bound::Included(u8::MAX)),
+        Range::from(3)
+    );
+}
+
+#[test]
+fn complement() {
+    let outside = Range::<u64>::between(3, 10).complement();
+    assert_eq!(outside, Ranges::to(3).or(&Ranges::from(10)));
+    assert_eq!(outside.num_intervals(), 2);
+
+    assert_eq!(Range::<u64>::from(3).complement(), Ranges::to(3));
+    assert_eq!(Range::<u64>::to(3).complement(), Ranges::from(3));
+    // Starting from the minimum is the same as unbounded below
+    assert_eq!(Range::<u64>::from(0).complement(), Ranges::EMPTY);
+
+    assert_eq!(Range::<u64>::EMPTY.complement(), Ranges::full());
+    assert_eq!(Range::<u64>::full().complement(), Ranges::EMPTY);
+
+    let range: Range<PureVersion> = Range::from_str(">=1.0.0 && <2.0.0").unwrap();
+    assert_eq!(range.complement().to_string(), "<1.0.0 || >=2.0.0");
+}
+
+#[test]
+fn hull() {
+    let set = Ranges::<u64>::from_str(">=2 && <4 || >=6 && <9").unwrap();
+    assert_eq!(set.hull(), Range::between(2, 9));
+    assert_eq!(Ranges::<u64>::except(5).hull(), Range::full());
+    assert_eq!(Ranges::<u64>::from(3).hull(), Range::from(3));
+    assert_eq!(Ranges::<u64>::EMPTY.hull(), Range::EMPTY);
+    assert_eq!(Ranges::<u64>::between(0, 3).hull(), Range::to(3));
+}
+
+#[test]
+fn start_end_bounds() {
+    let range = Range::<u64>::between(3, 10);
+    assert_eq!(range.start_bound(), Some(Bound::Included(&3)));
+    assert_eq!(range.end_bound(), Some(Bound::Excluded(&10)));
+
+    let range = Range::<u64>::from(3);
+    assert_eq!(range.start_bound(), Some(Bound::Included(&3)));
+    assert_eq!(range.end_bound(), Some(Bound::Unbounded));
+
+    let range = Range::<u64>::to(3);
+    assert_eq!(range.start_bound(), Some(Bound::Unbounded));
+    assert_eq!(range.end_bound(), Some(Bound::Excluded(&3)));
+
+    // The minimum and the maximum are the same as unbounded
+    let range = Range::from_bounds(Bound::Included(0u64), Bound::Included(u64::MAX));
+    assert_eq!(range.start_bound(), Some(Bound::Unbounded));
+    assert_eq!(range.end_bound(), Some(Bound::Unbounded));
+
+    // Inclusive ends are normalized to exclusive ones
+    let range = Range::from_bounds(Bound::Excluded(2u64), Bound::Included(5));
+    assert_eq!(range.start_bound(), Some(Bound::Included(&3)));
+    assert_eq!(range.end_bound(), Some(Bound::Excluded(&6)));
+
+    assert_eq!(Range::<u64>::EMPTY.start_bound(), None);
+    assert_eq!(Range::<u64>::EMPTY.end_bound(), None);
+}
@@ -0,0 +1,104 @@
+//! Requirements parsed on first use
+
+use std::{fmt::Debug, sync::OnceLock};
+
+use itertools::Itertools;
+
+use super::Ranges;
+use crate::version::pure::{PrereleasePolicy, PureVersion};
+
+#[cfg(test)]
+mod tests;
+
+/// Create a `&'static` [`LazyReq`] from a requirement literal
+///
+/// The requirement is parsed the first time it is used.
+///
+/// ```
+/// use areq::version::pure::PureVersion;
+///
+/// fn supported(version: &PureVersion) -> bool {
+///     areq::static_req!(">=1.2.0 && <2.0.0").matches(version)
+/// }
+///
+/// assert!(supported(&"1.4.0".parse().unwrap()));
+/// assert!(!supported(&"2.0.0".parse().unwrap()));
+/// ```
+///
+/// The literal is not checked at compile time, as the full requirement syntax cannot be
+/// parsed in a const context. An invalid requirement compiles, and panics on its first use:
+///
+/// ```should_panic
+/// let req = areq::static_req!(">=1.2.0 &&");
+/// req.matches(&"1.4.0".parse().unwrap()); // Panics: `>=1.2.0 &&` is not a requirement
+/// ```
+///
+/// To check a release against a requirement during compilation, see
+/// [`assert_version_req!`](crate::assert_version_req).
+#[macro_export]
+macro_rules! static_req {
+    ($req:expr) => {{
+        static REQ: $crate::range::LazyReq = $crate::range::LazyReq::new($req);
+        &REQ
+    }};
+}
+
+/// A requirement on [`PureVersion`]s, parsed on first use
+///
+/// Suitable for `static`s: the parsing happens at most once, even with concurrent access.
+pub struct LazyReq {
+    source: &'static str,
+    ranges: OnceLock<Ranges<PureVersion>>,
+}
+
+impl LazyReq {
+    /// Create a requirement that will be parsed from `source`
+    pub const fn new(source: &'static str) -> Self {
+        Self {
+            source,
+            ranges: OnceLock::new(),
+        }
+    }
+
+    /// The requirement, as written
+    pub const fn source(&self) -> &'static str {
+        self.source
+    }
+
+    /// The parsed requirement
+    ///
+    /// # Panics
+    /// If the source is not a valid requirement
+    pub fn get(&self) -> &Ranges<PureVersion> {
+        self.ranges
+            .get_or_init(|| match Ranges::from_str(self.source) {
+                Ok(ranges) => ranges,
+                Err(errors) => panic!(
+                    "Invalid static requirement `{}`: {}",
+                    self.source,
+                    errors.iter().join("; ")
+                ),
+            })
+    }
+
+    /// Check if the requirement matches `version`
+    ///
+    /// Like [`Version::matches`](crate::version::Version::matches), prereleases match only
+    /// if they are explicitly allowed, following the default [`PrereleasePolicy`].
+    ///
+    /// # Panics
+    /// If the source is not a valid requirement
+    pub fn matches(&self, version: &PureVersion) -> bool {
+        self.get()
+            .contains_with_policy(version, PrereleasePolicy::default())
+    }
+}
+
+impl Debug for LazyReq {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyReq")
+            .field("source", &self.source)
+            .field("ranges", &self.ranges.get())
+            .finish()
+    }
+}
@@ -0,0 +1,63 @@
+use std::thread;
+
+use super::*;
+
+fn pv(s: &str) -> PureVersion {
+    s.parse().unwrap()
+}
+
+#[test]
+fn parsed_on_first_use() {
+    let req = LazyReq::new(">=1.2.0, <2.0.0");
+    assert!(req.ranges.get().is_none());
+
+    assert!(req.matches(&pv("1.5.0")));
+    assert!(!req.matches(&pv("2.0.0")));
+    assert_eq!(req.get(), &Ranges::from_str(">=1.2.0 && <2.0.0").unwrap());
+    assert!(req.ranges.get().is_some());
+}
+
+#[test]
+fn macro_gives_a_static() {
+    fn req() -> &'static LazyReq {
+        crate::static_req!(">=1.2.0 && <2.0.0")
+    }
+    assert!(std::ptr::eq(req(), req()));
+    assert_eq!(req().source(), ">=1.2.0 && <2.0.0");
+    assert!(req().matches(&pv("1.9.0")));
+}
+
+#[test]
+fn prereleases_follow_the_default_policy() {
+    let req = crate::static_req!(">=1.2.0 && <2.0.0");
+    assert!(!req.matches(&pv("1.5.0-beta")));
+    assert!(req.get().contains(&pv("1.5.0-beta")));
+    assert_eq!(
+        req.matches(&pv("1.5.0-beta")),
+        "1.5.0-beta"
+            .parse::<crate::version::Version>()
+            .unwrap()
+            .matches(req.get())
+    );
+
+    let req = crate::static_req!(">=1.5.0-alpha && <2.0.0");
+    assert!(req.matches(&pv("1.5.0-beta")));
+}
+
+#[test]
+fn concurrent_get() {
+    static REQ: LazyReq = LazyReq::new(">=1.0.0 && <3.0.0 || ==5.0.0");
+    let results: Vec<_> = thread::scope(|s| {
+        let handles: Vec<_> = (0..8).map(|_| s.spawn(|| REQ.get())).collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    // Every thread sees the same, single, parsed value
+    assert!(results.iter().all(|&r| std::ptr::eq(r, REQ.get())));
+    assert!(REQ.matches(&pv("5.0.0")));
+}
+
+#[test]
+#[should_panic(expected = "Invalid static requirement `>=1.x.0`")]
+fn invalid_panics_with_source() {
+    LazyReq::new(">=1.x.0").get();
+}
@@ -1,4 +1,9 @@
-use super::{RangeExtreme, RangeExtremeDisplay};
+use chumsky::{Parser, error::Rich, prelude::*, text::digits};
+
+use super::{
+    ParserExtra, RangeExtreme, RangeExtremeCanonical, RangeExtremeDisplay, RangeExtremeNumeric,
+    RangeExtremeParseable,
+};
 
 macro_rules! impl_numeric {
     (
@@ -6,15 +11,16 @@ macro_rules! impl_numeric {
     ) => {
         $(
             impl RangeExtreme for $t {
-                const MIN: Self = <$t>::MIN;
-                const MAX: Self = <$t>::MAX;
+                fn minimum() -> Option<Self> {
+                    Some(<$t>::MIN)
+                }
 
-                fn next(self) -> Self {
-                    self + 1
+                fn next(self) -> Option<Self> {
+                    self.checked_add(1)
                 }
 
                 fn compare_next_to(&self, other: &Self) -> bool {
-                    self.next() == *other
+                    self.next() == Some(*other)
                 }
             }
 
@@ -28,8 +34,46 @@ macro_rules! impl_numeric {
                 }
             }
 
+            impl RangeExtremeNumeric for $t {
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn saturating_add(self, delta: Self) -> Self {
+                    <$t>::saturating_add(self, delta)
+                }
+
+                fn prev(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+            }
+
+            impl RangeExtremeCanonical for $t {}
+
         )*
     };
 }
 
 impl_numeric!(u8 u16 u32 u64 u128 i8 i16 i32 i64 i128);
+
+/// Parse a decimal integer, reporting overflows as custom errors
+macro_rules! impl_numeric_parseable {
+    (
+       $sign:expr => $( $t:ty )*
+    ) => {
+        $(
+            impl RangeExtremeParseable for $t {
+                fn parser<'a>() -> impl Parser<'a, &'a str, Self, ParserExtra<'a>> + Clone {
+                    $sign
+                        .then(digits(10))
+                        .to_slice()
+                        .try_map(|s: &str, span| {
+                            s.parse::<$t>().map_err(|err| Rich::custom(span, err))
+                        })
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_parseable!(empty() => u8 u16 u32 u64 u128);
+impl_numeric_parseable!(just('-').or_not() => i8 i16 i32 i64 i128);
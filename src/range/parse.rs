@@ -2,9 +2,84 @@ use chumsky::{Parser, prelude::*};
 
 use super::{RangeExtremeParseable, Ranges};
 
-pub type Extra<'a> = chumsky::extra::Full<chumsky::error::Rich<'a, char>, (), ()>;
+/// Extra type of all the parsers of this crate
+pub type Extra<'a> = chumsky::extra::Full<Error<'a>, (), ()>;
 
-pub fn parser<'a, T>() -> impl Parser<'a, &'a str, Ranges<T>, Extra<'a>>
+/// Error type of all the parsers of this crate
+pub type Error<'a> = Rich<'a, char>;
+
+/// Parse a whole range, rejecting empty inputs with a clear error
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(input = s)))]
+pub fn parse<'a, T>(s: &'a str) -> Result<Ranges<T>, Vec<Rich<'a, char>>>
+where
+    T: RangeExtremeParseable + 'a,
+{
+    if s.trim().is_empty() {
+        return Err(vec![Rich::custom(
+            SimpleSpan::from(0..s.len()),
+            "The range is empty",
+        )]);
+    }
+    parser()
+        .parse(s)
+        .into_result()
+        .map_err(|errors| match unbalanced_parenthesis(s) {
+            Some(error) => vec![error],
+            None => errors,
+        })
+}
+
+/// Find the first parenthesis without a match
+///
+/// This gives a clearer message than the generic one about the expected tokens.
+fn unbalanced_parenthesis(s: &str) -> Option<Rich<'_, char>> {
+    let mut open = vec![];
+    for (idx, ch) in s.char_indices() {
+        match ch {
+            '(' => open.push(idx),
+            ')' if open.pop().is_none() => {
+                return Some(Rich::custom(
+                    SimpleSpan::from(idx..idx + 1),
+                    format!("Unexpected closing parenthesis at position {idx}"),
+                ));
+            }
+            _ => (),
+        }
+    }
+    let idx = *open.first()?;
+    Some(Rich::custom(
+        SimpleSpan::from(idx..idx + 1),
+        format!("Unclosed parenthesis at position {idx}"),
+    ))
+}
+
+/// Emit a trace event each time `parser` succeeds, with the matched input and its span
+///
+/// Backtracking means that events can be emitted for matches that are later discarded.
+/// Parsers whose output is discarded, like the left side of `ignore_then`, run in check mode
+/// and emit nothing.
+#[cfg(feature = "tracing")]
+pub(crate) fn traced<'a, O, P>(
+    parser: P,
+    what: &'static str,
+) -> impl Parser<'a, &'a str, O, Extra<'a>> + Clone
+where
+    P: Parser<'a, &'a str, O, Extra<'a>> + Clone,
+{
+    parser.map_with(move |value, e| {
+        let span: SimpleSpan = e.span();
+        tracing::trace!(what, input = e.slice(), span = ?span.into_range(), "parsed");
+        value
+    })
+}
+
+/// Without the `tracing` feature, the parser is returned unchanged
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn traced<P>(parser: P, _what: &'static str) -> P {
+    parser
+}
+
+pub fn parser<'a, T>() -> impl Parser<'a, &'a str, Ranges<T>, Extra<'a>> + Clone
 where
     T: RangeExtremeParseable + 'a,
 {
@@ -17,15 +92,24 @@ where
             .or(just(">=").to(Ranges::from as fn(T) -> Ranges<T>))
             .or(just(">").to(Ranges::from_exclusive as fn(T) -> Ranges<T>))
             .or(just("==").to(Ranges::single as fn(T) -> Ranges<T>))
-            .or(just("!=").to(Ranges::except as fn(T) -> Ranges<T>));
+            .or(just("!=").to(Ranges::except as fn(T) -> Ranges<T>))
+            // Single `=` is accepted as an alias of `==`, as in many other ecosystems
+            .or(just("=")
+                .and_is(just("==").not())
+                .to(Ranges::single as fn(T) -> Ranges<T>));
 
-        let atom = operator
-            .then_ignore(whitespace)
-            .then(T::parser())
-            .map(|(op, t)| op(t))
+        let constraint = traced(
+            operator
+                .then_ignore(whitespace)
+                .then(traced(T::parser(), "extreme"))
+                .map(|(op, t)| op(t)),
+            "constraint",
+        );
+
+        let atom = constraint
             .or(just("-").to(Ranges::EMPTY))
             .or(just("*").to(Ranges::full()))
-            .or(parser.delimited_by(just('('), just(')')));
+            .or(traced(parser.delimited_by(just('('), just(')')), "group"));
 
         let negated = just("!")
             .and_is(just("!=").not())
@@ -34,19 +118,29 @@ where
             .count()
             .then(atom)
             .map(|(negs, atom)| if negs % 2 == 0 { atom } else { atom.not() });
+        let negated = traced(negated, "term");
 
         let anded = negated.clone().foldl(
-            just("&&")
-                .padded_by(whitespace)
-                .ignore_then(negated)
+            traced(just("&&").padded_by(whitespace), "and")
+                .then(negated)
+                .map(|(_, term)| term)
                 .repeated(),
             |a, b| a.and(&b),
         );
 
-        let orred = anded.clone().foldl(
-            just("||")
-                .padded_by(whitespace)
-                .ignore_then(anded)
+        // Commas are the legacy syntax for `&&`, binding looser than it
+        let commaed = anded.clone().foldl(
+            traced(just(",").padded_by(whitespace), "comma")
+                .then(anded)
+                .map(|(_, term)| term)
+                .repeated(),
+            |a, b| a.and(&b),
+        );
+
+        let orred = commaed.clone().foldl(
+            traced(just("||").padded_by(whitespace), "or")
+                .then(commaed)
+                .map(|(_, term)| term)
                 .repeated(),
             |a, b| a.or(&b),
         );
@@ -54,3 +148,139 @@ where
         orred.padded_by(whitespace)
     })
 }
+
+/// A constraint dropped by [`Ranges::parse_lossy`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// Byte range of the dropped constraint in the input
+    pub span: std::ops::Range<usize>,
+    /// Why the constraint could not be parsed
+    pub reason: String,
+}
+
+pub fn lossy<T>(s: &str) -> (Ranges<T>, Vec<ParseWarning>)
+where
+    T: RangeExtremeParseable,
+{
+    let mut warnings = vec![];
+    let ranges = lossy_or(s, 0, &mut warnings).unwrap_or(Ranges::EMPTY);
+    (ranges, warnings)
+}
+
+/// Parse the alternatives in `s`, returning `None` if all of them were dropped
+fn lossy_or<T>(s: &str, offset: usize, warnings: &mut Vec<ParseWarning>) -> Option<Ranges<T>>
+where
+    T: RangeExtremeParseable,
+{
+    split_top_level(s, "||")
+        .filter_map(|(start, branch)| lossy_comma(branch, offset + start, warnings))
+        .reduce(|a, b| a.or(&b))
+}
+
+/// Parse the comma separated groups of an intersection, returning `None` if all of them were dropped
+fn lossy_comma<T>(s: &str, offset: usize, warnings: &mut Vec<ParseWarning>) -> Option<Ranges<T>>
+where
+    T: RangeExtremeParseable,
+{
+    split_top_level(s, ",")
+        .filter_map(|(start, group)| lossy_and(group, offset + start, warnings))
+        .reduce(|a, b| a.and(&b))
+}
+
+/// Parse the terms of an intersection, returning `None` if all of them were dropped
+fn lossy_and<T>(s: &str, offset: usize, warnings: &mut Vec<ParseWarning>) -> Option<Ranges<T>>
+where
+    T: RangeExtremeParseable,
+{
+    split_top_level(s, "&&")
+        .filter_map(|(start, term)| lossy_term(term, offset + start, warnings))
+        .reduce(|a, b| a.and(&b))
+}
+
+fn lossy_term<T>(s: &str, offset: usize, warnings: &mut Vec<ParseWarning>) -> Option<Ranges<T>>
+where
+    T: RangeExtremeParseable,
+{
+    let mut offset = offset + (s.len() - s.trim_start().len());
+    let mut term = s.trim();
+
+    let mut negated = false;
+    while let Some(rest) = term.strip_prefix('!').filter(|_| !term.starts_with("!=")) {
+        negated = !negated;
+        offset += term.len() - rest.trim_start().len();
+        term = rest.trim_start();
+    }
+
+    let ranges = if is_parenthesized(term) {
+        lossy_or(&term[1..term.len() - 1], offset + 1, warnings)?
+    } else {
+        match parser::<T>().parse(term).into_result() {
+            Ok(ranges) => ranges,
+            Err(errors) => {
+                warnings.push(ParseWarning {
+                    span: offset..offset + term.len(),
+                    reason: errors
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join("; "),
+                });
+                return None;
+            }
+        }
+    };
+    Some(if negated { ranges.not() } else { ranges })
+}
+
+/// Check if the whole string is wrapped in a matching pair of parentheses
+pub(super) fn is_parenthesized(s: &str) -> bool {
+    if !s.starts_with('(') {
+        return false;
+    }
+    let mut depth = 0usize;
+    for (idx, b) in s.bytes().enumerate() {
+        match b {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return idx == s.len() - 1;
+                }
+            }
+            _ => (),
+        }
+    }
+    false
+}
+
+/// Split `s` on the occurrences of `sep` outside of parentheses, together with their offsets
+pub(super) fn split_top_level<'s>(
+    s: &'s str,
+    sep: &'s str,
+) -> impl Iterator<Item = (usize, &'s str)> {
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut idx = 0;
+    let mut done = false;
+    std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+        while idx < s.len() {
+            match s.as_bytes()[idx] {
+                b'(' => depth += 1,
+                b')' => depth = depth.saturating_sub(1),
+                _ if depth == 0 && s.as_bytes()[idx..].starts_with(sep.as_bytes()) => {
+                    let part = (start, &s[start..idx]);
+                    idx += sep.len();
+                    start = idx;
+                    return Some(part);
+                }
+                _ => (),
+            }
+            idx += 1;
+        }
+        done = true;
+        Some((start, &s[start..]))
+    })
+}
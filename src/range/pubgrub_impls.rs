@@ -0,0 +1,86 @@
+//! Integration with the [`pubgrub`] dependency resolver
+
+use std::{fmt::Debug, ops::Bound};
+
+use pubgrub::{Ranges as PubgrubRanges, VersionSet};
+
+use super::{RangeExtreme, RangeExtremeDisplay, Ranges};
+
+#[cfg(test)]
+mod tests;
+
+impl<T> VersionSet for Ranges<T>
+where
+    T: RangeExtremeDisplay + Debug,
+{
+    type V = T;
+
+    fn empty() -> Self {
+        Ranges::EMPTY
+    }
+
+    fn singleton(v: Self::V) -> Self {
+        Ranges::single(v)
+    }
+
+    fn complement(&self) -> Self {
+        self.clone().not()
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        self.clone().and(other)
+    }
+
+    fn contains(&self, v: &Self::V) -> bool {
+        Ranges::contains(self, v)
+    }
+
+    fn full() -> Self {
+        Ranges::full()
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        self.clone().or(other)
+    }
+}
+
+impl<T> From<Ranges<T>> for PubgrubRanges<T>
+where
+    T: RangeExtreme,
+{
+    fn from(ranges: Ranges<T>) -> Self {
+        ranges
+            .ranges()
+            .map(|(start, end)| {
+                PubgrubRanges::from_range_bounds((
+                    start.map_or(Bound::Unbounded, |start| Bound::Included(start.clone())),
+                    end.map_or(Bound::Unbounded, |end| Bound::Excluded(end.clone())),
+                ))
+            })
+            .fold(PubgrubRanges::empty(), |acc, range| acc.union(&range))
+    }
+}
+
+impl<T> From<PubgrubRanges<T>> for Ranges<T>
+where
+    T: RangeExtreme,
+{
+    fn from(ranges: PubgrubRanges<T>) -> Self {
+        ranges
+            .iter()
+            .map(|(start, end)| {
+                let start = match start {
+                    Bound::Included(start) => Ranges::from(start.clone()),
+                    Bound::Excluded(start) => Ranges::from_exclusive(start.clone()),
+                    Bound::Unbounded => Ranges::full(),
+                };
+                let end = match end {
+                    Bound::Included(end) => Ranges::to_inclusive(end.clone()),
+                    Bound::Excluded(end) => Ranges::to(end.clone()),
+                    Bound::Unbounded => Ranges::full(),
+                };
+                start.and(&end)
+            })
+            .fold(Ranges::EMPTY, |acc, range| acc.or(&range))
+    }
+}
@@ -0,0 +1,70 @@
+use std::str::FromStr;
+
+use pubgrub::{OfflineDependencyProvider, Ranges as PubgrubRanges, resolve};
+
+use crate::{range::Ranges, version::pure::PureVersion};
+
+fn v(s: &str) -> PureVersion {
+    PureVersion::from_str(s).unwrap()
+}
+
+fn r(s: &str) -> Ranges<PureVersion> {
+    Ranges::from_str(s).unwrap()
+}
+
+#[test]
+fn resolve_with_areq_ranges() {
+    let mut provider = OfflineDependencyProvider::<&str, Ranges<PureVersion>>::new();
+
+    provider.add_dependencies(
+        "root",
+        v("1.0.0"),
+        [("menu", r(">=1.0.0 && <2.0.0")), ("icons", r("*"))],
+    );
+    provider.add_dependencies("menu", v("1.0.0"), [("dropdown", r("<2.0.0"))]);
+    provider.add_dependencies("menu", v("1.1.0"), [("dropdown", r(">=2.0.0"))]);
+    provider.add_dependencies("menu", v("2.0.0"), []);
+    provider.add_dependencies("dropdown", v("1.8.0"), []);
+    provider.add_dependencies("dropdown", v("2.0.0"), [("icons", r("==2.0.0"))]);
+    provider.add_dependencies("icons", v("1.0.0"), []);
+    provider.add_dependencies("icons", v("2.0.0"), []);
+    provider.add_dependencies("icons", v("3.0.0-rc.1"), []);
+
+    let solution = resolve(&provider, "root", v("1.0.0")).unwrap();
+
+    assert_eq!(solution["menu"], v("1.1.0"));
+    assert_eq!(solution["dropdown"], v("2.0.0"));
+    assert_eq!(solution["icons"], v("2.0.0"));
+}
+
+#[test]
+fn unsatisfiable() {
+    let mut provider = OfflineDependencyProvider::<&str, Ranges<PureVersion>>::new();
+
+    provider.add_dependencies("root", v("1.0.0"), [("a", r("*")), ("b", r(">=2.0.0"))]);
+    provider.add_dependencies("a", v("1.0.0"), [("b", r("<2.0.0"))]);
+    provider.add_dependencies("b", v("1.0.0"), []);
+    provider.add_dependencies("b", v("2.0.0"), []);
+
+    assert!(resolve(&provider, "root", v("1.0.0")).is_err());
+}
+
+#[test]
+fn conversions_roundtrip() {
+    for s in [
+        "*",
+        "-",
+        ">=1.0.0 && <2.0.0 || >=3.0.0",
+        "<1.0.0 || ==1.5.0",
+        "!=1.2.3",
+    ] {
+        let ranges = r(s);
+        let pubgrub: PubgrubRanges<PureVersion> = ranges.clone().into();
+        let back: Ranges<PureVersion> = pubgrub.clone().into();
+
+        assert_eq!(back, ranges, "{s}");
+        for version in ["0.1.0", "1.0.0", "1.2.3", "1.5.0", "2.0.0", "3.1.0"] {
+            assert_eq!(pubgrub.contains(&v(version)), ranges.contains(&v(version)));
+        }
+    }
+}
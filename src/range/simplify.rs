@@ -0,0 +1,64 @@
+//! Simplification of range sets against a known set of values
+
+use itertools::Itertools;
+
+use super::{RangeExtreme, Ranges};
+
+#[cfg(test)]
+mod tests;
+
+impl<T> Ranges<T>
+where
+    T: RangeExtreme,
+{
+    /// Return the simplest set matching the same values of `published` as this one
+    ///
+    /// Each run of consecutive matched values becomes a single interval, so the result has
+    /// the fewest intervals possible. Intervals are widened up to the unmatched values
+    /// around them, except for runs of a single value, that are pinned with `==`.
+    /// Values outside `published` can change membership, and `published` needs not be sorted.
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::pure::PureVersion};
+    /// let published: Vec<PureVersion> = ["1.1.0", "1.2.0", "1.5.0", "1.7.3", "1.8.0"]
+    ///     .into_iter()
+    ///     .map(|v| v.parse().unwrap())
+    ///     .collect();
+    /// let req = Ranges::from_str(">=1.2.0 && <1.6.0 || ==1.7.3").unwrap();
+    /// assert_eq!(req.simplify_against(&published).to_string(), ">1.1.0 && <1.8.0");
+    /// ```
+    pub fn simplify_against(&self, published: &[T]) -> Self {
+        let mut simplified = Self::EMPTY;
+        let mut before = None;
+        // First and last value of the current run, and its length
+        let mut run: Option<(&T, &T, usize)> = None;
+        let mut close_run = |before: Option<&T>, run: (&T, &T, usize), after: Option<&T>| {
+            let interval = match run {
+                (single, _, 1) => Self::single(single.clone()),
+                _ => {
+                    let start = before.map_or_else(Self::full, |v| Self::from_exclusive(v.clone()));
+                    let end = after.map_or_else(Self::full, |v| Self::to(v.clone()));
+                    start.and(&end)
+                }
+            };
+            simplified.or_assign(&interval);
+        };
+        for value in published.iter().sorted().dedup() {
+            if self.contains(value) {
+                run = Some(match run {
+                    Some((first, _, len)) => (first, value, len + 1),
+                    None => (value, value, 1),
+                });
+            } else {
+                if let Some(run) = run.take() {
+                    close_run(before, run, Some(value));
+                }
+                before = Some(value);
+            }
+        }
+        if let Some(run) = run {
+            close_run(before, run, None);
+        }
+        simplified
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{range::Ranges, version::pure::PureVersion};
+
+fn versions(vs: &[&str]) -> Vec<PureVersion> {
+    vs.iter().map(|v| v.parse().unwrap()).collect()
+}
+
+fn r(s: &str) -> Ranges<PureVersion> {
+    Ranges::from_str(s).unwrap()
+}
+
+/// Check the guarantees of the simplification, returning the simplified set
+fn check(req: &str, published: &[PureVersion]) -> Ranges<PureVersion> {
+    let req = r(req);
+    let simplified = req.simplify_against(published);
+    for v in published {
+        assert_eq!(
+            simplified.contains(v),
+            req.contains(v),
+            "{req} and {simplified} on {v}"
+        );
+    }
+    assert!(simplified.num_intervals() <= req.num_intervals());
+    simplified
+}
+
+#[test]
+fn merges_intervals_with_nothing_between() {
+    let published = versions(&["1.1.0", "1.2.0", "1.5.0", "1.6.0", "1.7.3", "1.8.0"]);
+    // 1.6.0 is published, so the intervals stay separated
+    assert_eq!(
+        check(">=1.2.0 && <1.6.0 || ==1.7.3", &published),
+        r(">1.1.0 && <1.6.0 || ==1.7.3")
+    );
+    let published = versions(&["1.1.0", "1.2.0", "1.5.0", "1.7.3", "1.8.0"]);
+    assert_eq!(
+        check(">=1.2.0 && <1.6.0 || ==1.7.3", &published),
+        r(">1.1.0 && <1.8.0")
+    );
+}
+
+#[test]
+fn collapses_to_a_single_version() {
+    let published = versions(&["1.0.0", "1.1.0", "1.2.0"]);
+    let simplified = check(">=1.0.5 && <1.1.5 || >=1.1.8 && <1.1.9", &published);
+    assert_eq!(simplified.to_string(), "==1.1.0");
+}
+
+#[test]
+fn already_minimal() {
+    let published = versions(&["1.0.0", "1.2.0", "1.3.0"]);
+    assert_eq!(check("<1.3.0", &published), r("<1.3.0"));
+    let published = versions(&["1.0.0", "1.5.0", "1.6.0", "2.0.0"]);
+    assert_eq!(check(">1.0.0 && <2.0.0", &published), r(">1.0.0 && <2.0.0"));
+}
+
+#[test]
+fn unbounded_ends() {
+    let published = versions(&["1.0.0", "2.0.0", "3.0.0", "4.0.0"]);
+    assert_eq!(check(">=3.0.0", &published), r(">2.0.0"));
+    assert_eq!(check("<3.0.0", &published), r("<3.0.0"));
+    assert!(check("*", &published).is_full());
+    assert!(check("<1.0.0 || >4.0.0", &published).is_empty());
+    assert!(check("*", &[]).is_empty());
+}
+
+#[test]
+fn unsorted_and_duplicated_input() {
+    let published = versions(&["3.0.0", "1.0.0", "2.0.0", "1.0.0", "2.5.0"]);
+    assert_eq!(
+        check(">=2.0.0 && <3.0.0", &published),
+        r(">1.0.0 && <3.0.0")
+    );
+}
+
+#[test]
+fn membership_is_preserved() {
+    let published = versions(&[
+        "0.1.0",
+        "0.2.0",
+        "1.0.0-rc.1",
+        "1.0.0",
+        "1.0.1",
+        "1.1.0",
+        "1.2.0-beta",
+        "1.2.0",
+        "1.3.0",
+        "2.0.0",
+        "2.1.0",
+        "3.0.0",
+    ]);
+    for req in [
+        "*",
+        "-",
+        ">=1.0.0 && <2.0.0",
+        "==1.0.1 || ==1.2.0 || >=2.1.0",
+        "!=1.1.0",
+        "<0.2.0 || >1.0.0 && <1.2.0 || ==3.0.0",
+        ">=1.0.0-0 && <1.2.0 || >=1.3.0 && <2.0.0 || ==2.0.0",
+    ] {
+        check(req, &published);
+    }
+}
@@ -0,0 +1,71 @@
+//! Matching ranges against sorted slices of values
+
+use snafu::Snafu;
+
+use super::{RangeExtreme, Ranges};
+
+#[cfg(test)]
+mod tests;
+
+/// The slice passed to [`Ranges::try_matching_slice`] was not sorted
+#[derive(Debug, Clone, PartialEq, Eq, Snafu)]
+#[snafu(display("The values are not sorted: the one at index {index} is greater than the next"))]
+pub struct NotSorted {
+    pub index: usize,
+}
+
+impl<T> Ranges<T>
+where
+    T: RangeExtreme,
+{
+    /// Return the sub-slices of `sorted` that are inside the range, in ascending order
+    ///
+    /// `sorted` must be sorted in ascending order. This is checked only with debug
+    /// assertions, see [`Ranges::try_matching_slice`] for a checked variant.
+    /// Each interval is located with a binary search, so the cost does not depend on
+    /// the number of values outside the range.
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// let published = [1u32, 3, 5, 7, 9, 11];
+    /// let range = Ranges::between(2, 6).or(&Ranges::from(10));
+    /// let slices: Vec<_> = range.matching_slice(&published).collect();
+    /// assert_eq!(slices, [&[3, 5][..], &[11][..]]);
+    /// ```
+    pub fn matching_slice<'a>(&self, sorted: &'a [T]) -> impl Iterator<Item = &'a [T]> {
+        debug_assert!(sorted.is_sorted(), "The values must be sorted");
+        let mut searched = 0;
+        self.intervals().filter_map(move |(start, end)| {
+            let rest = &sorted[searched..];
+            let first = start.map_or(0, |start| rest.partition_point(|v| v < start));
+            let last = end.map_or(rest.len(), |end| rest.partition_point(|v| v < end));
+            searched += last;
+            (first < last).then(|| &rest[first..last])
+        })
+    }
+
+    /// Same as [`Ranges::matching_slice`], but checks that `sorted` is sorted
+    pub fn try_matching_slice<'a>(
+        &self,
+        sorted: &'a [T],
+    ) -> Result<impl Iterator<Item = &'a [T]>, NotSorted> {
+        if let Some(index) = sorted.windows(2).position(|pair| pair[0] > pair[1]) {
+            return Err(NotSorted { index });
+        }
+        Ok(self.matching_slice(sorted))
+    }
+
+    /// Return the greatest value of `sorted` inside the range
+    ///
+    /// See [`Ranges::matching_slice`] for the requirements on `sorted`.
+    pub fn max_satisfying_sorted<'a>(&self, sorted: &'a [T]) -> Option<&'a T> {
+        self.matching_slice(sorted).last()?.last()
+    }
+
+    /// Count the values of `sorted` inside the range
+    ///
+    /// See [`Ranges::matching_slice`] for the requirements on `sorted`.
+    pub fn count_satisfying_sorted(&self, sorted: &[T]) -> usize {
+        self.matching_slice(sorted).map(<[T]>::len).sum()
+    }
+}
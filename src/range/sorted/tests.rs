@@ -0,0 +1,67 @@
+use super::NotSorted;
+use crate::{range::Ranges, version::pure::PureVersion};
+
+fn versions(list: &[&str]) -> Vec<PureVersion> {
+    list.iter().map(|v| v.parse().unwrap()).collect()
+}
+
+#[test]
+fn matches_like_filter() {
+    let sorted: Vec<u32> = (0..100).filter(|v| v % 3 != 0).collect();
+    let ranges = [
+        Ranges::full(),
+        Ranges::EMPTY,
+        Ranges::to(10),
+        Ranges::from(95),
+        Ranges::between(10, 20)
+            .or(&Ranges::between(40, 41))
+            .or(&Ranges::from(90)),
+        Ranges::except(50),
+        Ranges::single(51),
+    ];
+    for range in ranges {
+        let expected: Vec<u32> = sorted
+            .iter()
+            .copied()
+            .filter(|v| range.contains(v))
+            .collect();
+        let found: Vec<u32> = range.matching_slice(&sorted).flatten().copied().collect();
+        assert_eq!(found, expected, "{range}");
+        assert_eq!(range.count_satisfying_sorted(&sorted), expected.len());
+        assert_eq!(range.max_satisfying_sorted(&sorted), expected.last());
+    }
+}
+
+#[test]
+fn intervals_between_adjacent_values() {
+    let published = versions(&["1.0.0", "1.1.0", "1.2.0", "2.0.0"]);
+    let range = Ranges::<PureVersion>::from_str(">1.1.0 && <1.2.0 || >2.0.0").unwrap();
+    assert_eq!(range.matching_slice(&published).count(), 0);
+    assert_eq!(range.count_satisfying_sorted(&published), 0);
+    assert_eq!(range.max_satisfying_sorted(&published), None);
+
+    let range = Ranges::<PureVersion>::from_str(">1.0.0 && <1.1.0 || >=1.2.0").unwrap();
+    let slices: Vec<_> = range.matching_slice(&published).collect();
+    assert_eq!(slices, [&published[2..]]);
+}
+
+#[test]
+fn duplicates_are_kept() {
+    let sorted = [1u8, 2, 2, 2, 3];
+    assert_eq!(Ranges::single(2).count_satisfying_sorted(&sorted), 3);
+}
+
+#[test]
+fn checked_variant() {
+    assert_eq!(
+        Ranges::<u8>::full().try_matching_slice(&[1, 3, 2]).err(),
+        Some(NotSorted { index: 1 })
+    );
+    assert_eq!(
+        Ranges::<u8>::from(2)
+            .try_matching_slice(&[1, 2, 3])
+            .unwrap()
+            .collect::<Vec<_>>(),
+        [&[2, 3][..]]
+    );
+}
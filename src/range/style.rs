@@ -0,0 +1,191 @@
+//! Customizable tokens for displaying ranges
+
+use std::{borrow::Cow, fmt::Display};
+
+use super::{Range, RangeExtremeDisplay, Ranges, single_value};
+
+#[cfg(test)]
+mod tests;
+
+/// Tokens used to display ranges, see [`Ranges::display_with`]
+///
+/// The default style is the one of [`Display`], that [`Ranges::from_str`] parses back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayStyle {
+    /// The empty set, `-` by default
+    pub empty: Cow<'static, str>,
+    /// The set of all values, `*` by default
+    pub full: Cow<'static, str>,
+    /// Prefix of a single value, `==` by default
+    pub single: Cow<'static, str>,
+    /// Prefix of an excluded lower bound, `>` by default
+    pub greater: Cow<'static, str>,
+    /// Prefix of an included lower bound, `>=` by default
+    pub greater_eq: Cow<'static, str>,
+    /// Prefix of an excluded upper bound, `<` by default
+    pub less: Cow<'static, str>,
+    /// Prefix of an included upper bound, `<=` by default
+    pub less_eq: Cow<'static, str>,
+    /// Separator between the bounds of an interval, ` && ` by default
+    pub and: Cow<'static, str>,
+    /// Separator between intervals, ` || ` by default
+    pub or: Cow<'static, str>,
+}
+
+impl DisplayStyle {
+    /// The style of [`Display`]: `-`, `*`, `==`, `>`, `>=`, `<`, `<=`, ` && ` and ` || `
+    pub const DEFAULT: Self = Self {
+        empty: Cow::Borrowed("-"),
+        full: Cow::Borrowed("*"),
+        single: Cow::Borrowed("=="),
+        greater: Cow::Borrowed(">"),
+        greater_eq: Cow::Borrowed(">="),
+        less: Cow::Borrowed("<"),
+        less_eq: Cow::Borrowed("<="),
+        and: Cow::Borrowed(" && "),
+        or: Cow::Borrowed(" || "),
+    };
+}
+
+impl Default for DisplayStyle {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl<T> Ranges<T>
+where
+    T: RangeExtremeDisplay,
+{
+    /// Display the range set with custom tokens
+    ///
+    /// ```
+    /// # use areq::{range::{DisplayStyle, Ranges}, version::pure::PureVersion};
+    /// let style = DisplayStyle {
+    ///     empty: "<none>".into(),
+    ///     single: "=".into(),
+    ///     and: ", ".into(),
+    ///     ..Default::default()
+    /// };
+    /// let range = Ranges::<PureVersion>::from_str(">=1.0.0 && <2.0.0 || ==3.0.0").unwrap();
+    /// assert_eq!(range.display_with(&style).to_string(), ">=1.0.0, <2.0.0 || =3.0.0");
+    /// assert_eq!(Ranges::<PureVersion>::EMPTY.display_with(&style).to_string(), "<none>");
+    /// ```
+    pub fn display_with<'a>(&'a self, style: &'a DisplayStyle) -> impl Display + 'a {
+        Styled { value: self, style }
+    }
+}
+
+impl<T> Range<T>
+where
+    T: RangeExtremeDisplay,
+{
+    /// Display the range with custom tokens
+    ///
+    /// See [`Ranges::display_with`].
+    pub fn display_with<'a>(&'a self, style: &'a DisplayStyle) -> impl Display + 'a {
+        Styled { value: self, style }
+    }
+}
+
+/// A value displayed with a style
+struct Styled<'a, R> {
+    value: &'a R,
+    style: &'a DisplayStyle,
+}
+
+impl<T> Display for Styled<'_, Ranges<T>>
+where
+    T: RangeExtremeDisplay,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        display_ranges(self.value, self.style, f)
+    }
+}
+
+impl<T> Display for Styled<'_, Range<T>>
+where
+    T: RangeExtremeDisplay,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        display_range(self.value, self.style, f)
+    }
+}
+
+pub(super) fn display_ranges<T>(
+    ranges: &Ranges<T>,
+    style: &DisplayStyle,
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result
+where
+    T: RangeExtremeDisplay,
+{
+    if ranges.is_empty() {
+        return f.write_str(&style.empty);
+    }
+
+    if ranges.is_full() {
+        return f.write_str(&style.full);
+    }
+
+    for (i, (start, end)) in ranges.ranges().enumerate() {
+        if i > 0 {
+            f.write_str(&style.or)?;
+        }
+        display_interval(start, end, style, f)?;
+    }
+    Ok(())
+}
+
+pub(super) fn display_range<T>(
+    range: &Range<T>,
+    style: &DisplayStyle,
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result
+where
+    T: RangeExtremeDisplay,
+{
+    match range.bounds() {
+        None => f.write_str(&style.empty),
+        Some((None, None)) => f.write_str(&style.full),
+        Some((start, end)) => display_interval(start, end, style, f),
+    }
+}
+
+/// Display a single non-empty interval, from `start` included to `end` excluded
+fn display_interval<T>(
+    start: Option<&T>,
+    end: Option<&T>,
+    style: &DisplayStyle,
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result
+where
+    T: RangeExtremeDisplay,
+{
+    if let Some(value) = single_value(start, end) {
+        return write!(f, "{}{}", style.single, value);
+    }
+
+    if let Some(start) = start {
+        if start.has_prev() {
+            f.write_str(&style.greater)?;
+            start.display_prev(f)?;
+        } else {
+            write!(f, "{}{}", style.greater_eq, start)?;
+        }
+
+        if end.is_some() {
+            f.write_str(&style.and)?;
+        }
+    }
+
+    if let Some(end) = end {
+        if end.has_prev() {
+            f.write_str(&style.less_eq)?;
+            end.display_prev(f)?;
+        } else {
+            write!(f, "{}{}", style.less, end)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use super::DisplayStyle;
+use crate::{
+    range::{Range, Ranges},
+    version::pure::PureVersion,
+};
+
+fn words() -> DisplayStyle {
+    DisplayStyle {
+        empty: "<none>".into(),
+        full: "any".into(),
+        single: "=".into(),
+        greater: "above ".into(),
+        greater_eq: "from ".into(),
+        less: "before ".into(),
+        less_eq: "up to ".into(),
+        and: " and ".into(),
+        or: "; or ".into(),
+    }
+}
+
+#[test]
+fn default_style_is_display() {
+    for s in [
+        "-",
+        "*",
+        "==1.2.3",
+        ">=1.0.0 && <2.0.0 || >3.0.0",
+        "<=1.2.3",
+    ] {
+        let range = Ranges::<PureVersion>::from_str(s).unwrap();
+        assert_eq!(
+            range.display_with(&DisplayStyle::default()).to_string(),
+            range.to_string()
+        );
+    }
+}
+
+#[test]
+fn same_range_two_styles() {
+    let range = Ranges::<PureVersion>::from_str(">=1.0.0 && <=2.0.0 || >3.0.0 && !=4.0.0").unwrap();
+    assert_eq!(
+        range.display_with(&DisplayStyle::default()).to_string(),
+        ">=1.0.0 && <=2.0.0 || >3.0.0 && <4.0.0 || >4.0.0"
+    );
+    assert_eq!(
+        range.display_with(&words()).to_string(),
+        "from 1.0.0 and up to 2.0.0; or above 3.0.0 and before 4.0.0; or above 4.0.0"
+    );
+
+    let single = Ranges::single(PureVersion::new(1, 2, 3));
+    assert_eq!(
+        single.display_with(&DisplayStyle::default()).to_string(),
+        "==1.2.3"
+    );
+    assert_eq!(single.display_with(&words()).to_string(), "=1.2.3");
+}
+
+#[test]
+fn empty_and_full() {
+    assert_eq!(
+        Ranges::<u32>::EMPTY.display_with(&words()).to_string(),
+        "<none>"
+    );
+    assert_eq!(
+        Ranges::<u32>::full().display_with(&words()).to_string(),
+        "any"
+    );
+    assert_eq!(
+        Range::<u32>::EMPTY.display_with(&words()).to_string(),
+        "<none>"
+    );
+    assert_eq!(
+        Range::<u32>::full().display_with(&words()).to_string(),
+        "any"
+    );
+}
+
+#[test]
+fn single_range() {
+    let range = Range::<u32>::between(3, 10);
+    assert_eq!(
+        range.display_with(&DisplayStyle::default()).to_string(),
+        range.to_string()
+    );
+    assert_eq!(
+        range.display_with(&words()).to_string(),
+        "above 2 and up to 9"
+    );
+    assert_eq!(
+        Range::<u32>::between(3, 4)
+            .display_with(&words())
+            .to_string(),
+        "=3"
+    );
+}
@@ -1,4 +1,5 @@
 use super::Ranges;
+use crate::version::pure::PureVersion;
 
 #[test]
 fn empty() {
@@ -9,3 +10,585 @@ fn empty() {
 fn empty_eval_equal() {
     assert_eq!(Ranges::between(4, 2), Ranges::between(400, 20));
 }
+
+#[test]
+fn parse_integers() {
+    let range = Ranges::<i32>::from_str(">=3 && <10 || ==42").unwrap();
+
+    for v in 3..10 {
+        assert!(range.contains(&v));
+    }
+    assert!(range.contains(&42));
+    assert!(!range.contains(&2));
+    assert!(!range.contains(&10));
+    assert!(!range.contains(&43));
+}
+
+#[test]
+fn parse_integers_parentheses_and_negation() {
+    let range = Ranges::<i32>::from_str("!(>=3 && <10) && (<20 || ==30)").unwrap();
+
+    assert_eq!(
+        range,
+        Ranges::from_str("<3 || >=10 && <20 || ==30").unwrap()
+    );
+    assert!(range.contains(&-100));
+    assert!(!range.contains(&5));
+    assert!(range.contains(&15));
+    assert!(range.contains(&30));
+}
+
+#[test]
+fn parse_negative_integers() {
+    let range = Ranges::<i32>::from_str(">-5 && <=-2").unwrap();
+
+    assert_eq!(range, Ranges::between(-4, -1));
+    assert_eq!(Ranges::<i32>::from_str("!=-1").unwrap(), Ranges::except(-1));
+}
+
+#[test]
+fn parse_integer_overflow_is_error() {
+    assert!(Ranges::<u8>::from_str("<256").is_err());
+    assert!(Ranges::<i8>::from_str(">-129").is_err());
+    assert!(Ranges::<u8>::from_str("<-1").is_err());
+    assert!(Ranges::<i32>::from_str("==99999999999999999999999").is_err());
+}
+
+/// A selection of ranges over `u8`, touching both ends of the type
+fn u8_samples() -> Vec<Ranges<u8>> {
+    vec![
+        Ranges::EMPTY,
+        Ranges::full(),
+        Ranges::single(0),
+        Ranges::single(u8::MAX),
+        Ranges::single(42),
+        Ranges::except(0),
+        Ranges::except(u8::MAX),
+        Ranges::between(3, 10),
+        Ranges::from(200),
+        Ranges::to(50),
+        Ranges::between(0, 7).or(&Ranges::between(100, 120)),
+        Ranges::to_inclusive(u8::MAX),
+    ]
+}
+
+#[test]
+fn operations_agree_with_membership() {
+    for a in u8_samples() {
+        for v in u8::MIN..=u8::MAX {
+            assert_eq!(a.clone().not().contains(&v), !a.contains(&v));
+        }
+        for b in u8_samples() {
+            let or = a.clone().or(&b);
+            let and = a.clone().and(&b);
+            let xor = Ranges::xor([&a, &b]);
+            for v in u8::MIN..=u8::MAX {
+                assert_eq!(or.contains(&v), a.contains(&v) || b.contains(&v));
+                assert_eq!(and.contains(&v), a.contains(&v) && b.contains(&v));
+                assert_eq!(xor.contains(&v), a.contains(&v) != b.contains(&v));
+            }
+        }
+    }
+}
+
+#[test]
+fn equal_sets_are_equal() {
+    assert_eq!(Ranges::<u8>::from(0), Ranges::full());
+    assert_eq!(Ranges::<u8>::to(0), Ranges::EMPTY);
+    assert_eq!(Ranges::<u8>::between(0, 10), Ranges::to(10));
+    assert_eq!(Ranges::<u8>::to_inclusive(u8::MAX), Ranges::full());
+    assert_eq!(Ranges::<u8>::except(0).not(), Ranges::single(0));
+    assert_eq!(Ranges::<u8>::to(10).or(&Ranges::from(10)), Ranges::full());
+}
+
+#[test]
+fn constructors_do_not_overflow_at_max() {
+    assert_eq!(Ranges::<u8>::from_exclusive(u8::MAX), Ranges::EMPTY);
+    assert_eq!(Ranges::<i8>::from_exclusive(i8::MAX), Ranges::EMPTY);
+    assert_eq!(
+        Ranges::<u8>::between_include_end(250, u8::MAX),
+        Ranges::from(250)
+    );
+    assert!(Ranges::<u8>::between_include_end(250, u8::MAX).contains(&u8::MAX));
+    assert_eq!(
+        Ranges::<u8>::between_exclude_start(u8::MAX, u8::MAX),
+        Ranges::EMPTY
+    );
+    assert_eq!(
+        Ranges::<u8>::between_exclude_start_include_end(u8::MAX - 1, u8::MAX),
+        Ranges::single(u8::MAX)
+    );
+    assert_eq!(
+        Ranges::<u8>::between_exclude_start_include_end(u8::MAX, u8::MAX),
+        Ranges::EMPTY
+    );
+    assert_eq!(Ranges::<u128>::from_exclusive(u128::MAX), Ranges::EMPTY);
+    assert_eq!(
+        Ranges::<u8>::single(u8::MAX).shift(1),
+        Ranges::single(u8::MAX)
+    );
+}
+
+#[test]
+fn max_is_a_valid_extreme() {
+    let range = Ranges::<u64>::single(u64::MAX);
+
+    assert!(range.is_single());
+    assert!(range.contains(&u64::MAX));
+    assert!(!range.contains(&(u64::MAX - 1)));
+    assert_eq!(range.to_string(), format!("=={}", u64::MAX));
+    assert_eq!(Ranges::<u64>::from_str(&range.to_string()).unwrap(), range);
+}
+
+#[test]
+fn display_roundtrips() {
+    for range in u8_samples() {
+        let s = range.to_string();
+        assert_eq!(Ranges::<u8>::from_str(&s).unwrap(), range, "{s}");
+    }
+    assert!(Ranges::<u8>::single(0).is_single());
+    assert_eq!(Ranges::<u8>::single(0).to_string(), "==0");
+    assert_eq!(Ranges::<u8>::except(u8::MAX).to_string(), "<=254");
+}
+
+macro_rules! top_value_tests {
+    ($($name:ident: $t:ty),*) => {
+        $(
+            #[test]
+            fn $name() {
+                const MAX: $t = <$t>::MAX;
+
+                assert!(Ranges::<$t>::from_exclusive(MAX).is_empty());
+                assert!(Ranges::<$t>::between_exclude_start(MAX, MAX).is_empty());
+
+                assert!(Ranges::<$t>::to_inclusive(MAX).is_full());
+
+                let single = Ranges::<$t>::single(MAX);
+                assert!(single.is_single());
+                assert!(single.contains(&MAX));
+                assert!(!single.contains(&(MAX - 1)));
+
+                let between = Ranges::<$t>::between_include_end(10, MAX);
+                assert!(between.contains(&10));
+                assert!(between.contains(&MAX));
+                assert!(!between.contains(&9));
+                assert_eq!(between, Ranges::from(10));
+            }
+        )*
+    };
+}
+
+top_value_tests!(top_value_u8: u8, top_value_i8: i8);
+
+#[test]
+fn sorting() {
+    let mut ranges: Vec<Ranges<u64>> = [
+        "-",
+        ">=5",
+        "<3",
+        ">=5 && <10",
+        "*",
+        ">=1 && <2 || >=5 && <10",
+        ">=1 && <10",
+        "-",
+        "==5",
+        ">=1 && <10",
+    ]
+    .into_iter()
+    .map(|s| Ranges::from_str(s).unwrap())
+    .collect();
+
+    ranges.sort();
+
+    let sorted: Vec<String> = ranges.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        sorted,
+        [
+            "<=2",
+            "*",
+            "==1 || >4 && <=9",
+            ">0 && <=9",
+            ">0 && <=9",
+            "==5",
+            ">4 && <=9",
+            ">4",
+            "-",
+            "-",
+        ]
+    );
+}
+
+#[test]
+fn ordering_is_consistent_with_equality() {
+    for a in u8_samples() {
+        for b in u8_samples() {
+            assert_eq!(a.cmp(&b).is_eq(), a == b, "{a} {b}");
+            assert_eq!(a.cmp(&b), b.cmp(&a).reverse(), "{a} {b}");
+        }
+    }
+}
+
+#[test]
+fn lossy_parsing_agrees_with_strict() {
+    for s in [
+        ">=3 && <10 || ==42",
+        "!(>=3 && <10) && (<20 || ==30)",
+        "!!<5",
+        "* && !=4",
+        "-",
+    ] {
+        let (ranges, warnings) = Ranges::<i32>::parse_lossy(s);
+        assert!(warnings.is_empty(), "{s}: {warnings:?}");
+        assert_eq!(ranges, Ranges::from_str(s).unwrap(), "{s}");
+    }
+}
+
+#[test]
+fn lossy_parsing_drops_broken_or_branch() {
+    let s = ">=3 && <10 || >=abc";
+    let (range, warnings) = Ranges::<i32>::parse_lossy(s);
+
+    assert_eq!(range, Ranges::between(3, 10));
+    assert!(range.contains(&5));
+    assert!(!range.contains(&100));
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(&s[warnings[0].span.clone()], ">=abc");
+}
+
+#[test]
+fn lossy_parsing_drops_broken_and_term() {
+    let s = "(>=3 && !<=x) || ==42";
+    let (range, warnings) = Ranges::<i32>::parse_lossy(s);
+
+    assert_eq!(range, Ranges::from_str(">=3 || ==42").unwrap());
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(&s[warnings[0].span.clone()], "<=x");
+}
+
+#[test]
+fn lossy_parsing_of_garbage_is_empty() {
+    let (range, warnings) = Ranges::<i32>::parse_lossy("what || (is && this)");
+    assert!(range.is_empty());
+    assert_eq!(warnings.len(), 3);
+}
+
+#[test]
+fn empty_input() {
+    for s in ["", "   ", "\t"] {
+        let errors = Ranges::<i32>::from_str(s).unwrap_err();
+        assert_eq!(errors.len(), 1, "{s:?}");
+        assert_eq!(errors[0].to_string(), "The range is empty", "{s:?}");
+    }
+}
+
+#[test]
+fn shift() {
+    let range = Ranges::<u64>::from_str(">=1 && <5").unwrap();
+    assert_eq!(range.shift(10), Ranges::between(11, 15));
+
+    let range = Ranges::<i32>::from_str("<0 || ==7").unwrap();
+    assert_eq!(range.shift(-3), Ranges::from_str("<=-4 || ==4").unwrap());
+}
+
+#[test]
+fn shift_saturates() {
+    let range = Ranges::<u8>::from_str("<5 || ==250 || ==252").unwrap();
+    assert_eq!(
+        range.shift(10),
+        Ranges::from_str(">=10 && <15 || ==255").unwrap()
+    );
+
+    let range = Ranges::<i8>::from_str(">=-125 && <-120 || >=100").unwrap();
+    assert_eq!(
+        range.shift(-5),
+        Ranges::from_str("<-125 || >=95 && <=122").unwrap()
+    );
+}
+
+#[test]
+fn unclosed_parenthesis() {
+    let errors = Ranges::<i32>::from_str("(>=1 && (<5)").unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].to_string(), "Unclosed parenthesis at position 0");
+    assert_eq!(errors[0].span().start, 0);
+}
+
+#[test]
+fn unexpected_closing_parenthesis() {
+    let errors = Ranges::<i32>::from_str(">=1 && <5) || ==7").unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "Unexpected closing parenthesis at position 9"
+    );
+    assert_eq!(errors[0].span().start, 9);
+}
+
+#[test]
+fn parse_prefix() {
+    let (range, rest) = Ranges::<i32>::parse_prefix(">=3 && <10 ; comment").unwrap();
+    assert_eq!(range, Ranges::between(3, 10));
+    assert_eq!(rest, "; comment");
+
+    let (range, rest) = Ranges::<i32>::parse_prefix("==4").unwrap();
+    assert_eq!(range, Ranges::single(4));
+    assert_eq!(rest, "");
+
+    assert!(Ranges::<i32>::parse_prefix("x >=3").is_err());
+}
+
+#[test]
+fn comma_is_and() {
+    assert_eq!(
+        Ranges::<u32>::from_str(">=3,<10").unwrap(),
+        Ranges::from_str(">=3 && <10").unwrap()
+    );
+    assert_eq!(
+        Ranges::<u32>::from_str(">=3 , <10 , !=5").unwrap(),
+        Ranges::from_str(">=3 && <10 && !=5").unwrap()
+    );
+}
+
+#[test]
+fn comma_binds_tighter_than_or() {
+    assert_eq!(
+        Ranges::<u32>::from_str(">=3, <10 || ==42").unwrap(),
+        Ranges::from_str("(>=3 && <10) || ==42").unwrap()
+    );
+    assert_eq!(
+        Ranges::<u32>::from_str("<3 || >=5, <7 || >20").unwrap(),
+        Ranges::from_str("<3 || (>=5 && <7) || >20").unwrap()
+    );
+}
+
+#[test]
+fn comma_binds_looser_than_and() {
+    assert_eq!(
+        Ranges::<u32>::from_str(">=3 && !=4, <10 && !=7").unwrap(),
+        Ranges::from_str("(>=3 && !=4) && (<10 && !=7)").unwrap()
+    );
+    assert_eq!(
+        Ranges::<u32>::from_str(">=3 && <10, (<5 || ==42)").unwrap(),
+        Ranges::from_str(">=3 && <5").unwrap()
+    );
+    assert_eq!(
+        Ranges::<u32>::from_str("(>=3, <10) || (>=20, <30)").unwrap(),
+        Ranges::from_str(">=3 && <10 || >=20 && <30").unwrap()
+    );
+}
+
+#[test]
+fn single_equals_is_exact() {
+    assert_eq!(Ranges::<u32>::from_str("=3").unwrap(), Ranges::single(3));
+    assert_eq!(Ranges::<u32>::from_str("==3").unwrap(), Ranges::single(3));
+    assert_eq!(
+        Ranges::<u32>::from_str("= 3 || >=10, <=12").unwrap(),
+        Ranges::from_str("==3 || >=10 && <=12").unwrap()
+    );
+    assert_eq!(
+        Ranges::<PureVersion>::from_str("=1.2.3").unwrap(),
+        Ranges::single("1.2.3".parse().unwrap())
+    );
+    assert!(
+        Ranges::<PureVersion>::from_str("==1.2.3")
+            .unwrap()
+            .is_single()
+    );
+    for invalid in ["===3", "= =3", "=<3", "=>3"] {
+        assert!(Ranges::<u32>::from_str(invalid).is_err(), "{invalid:?}");
+    }
+}
+
+#[test]
+fn lossy_parsing_splits_on_commas() {
+    let (range, warnings) = Ranges::<u32>::parse_lossy(">=3, ?, <10 || ==42");
+    assert_eq!(range, Ranges::from_str(">=3 && <10 || ==42").unwrap());
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].span, 5..6);
+}
+
+#[test]
+fn assign_operations_agree_with_the_others() {
+    for a in u8_samples() {
+        for b in u8_samples() {
+            let mut and = a.clone();
+            and.and_assign(&b);
+            let mut or = a.clone();
+            or.or_assign(&b);
+            for v in u8::MIN..=u8::MAX {
+                assert_eq!(and.contains(&v), a.contains(&v) && b.contains(&v));
+                assert_eq!(or.contains(&v), a.contains(&v) || b.contains(&v));
+            }
+            assert_eq!(and, a.clone().not().or(&b.clone().not()).not());
+            assert_eq!(or, a.clone().not().and(&b.clone().not()).not());
+        }
+    }
+}
+
+#[test]
+fn and_assign_many() {
+    let mut range = Ranges::<u32>::full();
+    for bound in 0..100 {
+        range.and_assign(&Ranges::from(bound));
+        range.and_assign(&Ranges::to(1000 - bound));
+    }
+    assert_eq!(range, Ranges::between(99, 901));
+}
+
+#[test]
+fn map_integers() {
+    let ranges = Ranges::<u32>::from_str("<3 || >=7 && <10 || >20").unwrap();
+    let mapped = ranges.clone().map(u64::from);
+    assert_eq!(
+        mapped,
+        Ranges::<u64>::from_str("<3 || >=7 && <10 || >20").unwrap()
+    );
+    for v in 0..30 {
+        assert_eq!(ranges.contains(&v), mapped.contains(&u64::from(v)));
+    }
+}
+
+#[test]
+fn try_map_stops_at_first_error() {
+    let ranges = Ranges::<u64>::between(3, 300);
+    assert_eq!(
+        ranges.clone().try_map(u8::try_from).unwrap_err(),
+        u8::try_from(300u64).unwrap_err()
+    );
+    assert_eq!(
+        Ranges::<u64>::between(3, 30).try_map(u8::try_from),
+        Ok(Ranges::<u8>::between(3, 30))
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic = "strictly monotone"]
+fn map_checks_monotony() {
+    let _ = Ranges::<u64>::between(3, 10).map(|v| 100 - v);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Wrapped(PureVersion);
+
+impl super::RangeExtreme for Wrapped {
+    fn minimum() -> Option<Self> {
+        PureVersion::minimum().map(Wrapped)
+    }
+
+    fn next(self) -> Option<Self> {
+        self.0.next().map(Wrapped)
+    }
+}
+
+#[test]
+fn map_into_newtype() {
+    let ranges = Ranges::<PureVersion>::from_str(">=1.2.3 && <2.0.0 || ==3.0.0").unwrap();
+    let wrapped = ranges.clone().map(Wrapped);
+    for v in ["1.2.3", "1.9.0", "2.0.0", "3.0.0", "3.0.1", "0.1.0"] {
+        let v: PureVersion = v.parse().unwrap();
+        assert_eq!(ranges.contains(&v), wrapped.contains(&Wrapped(v.clone())));
+    }
+    assert_eq!(wrapped.map(|Wrapped(v)| v), ranges);
+    // The minimum is mapped to the minimum, so the result stays unbounded below
+    assert_eq!(
+        Ranges::<PureVersion>::to(PureVersion::new(1, 0, 0)).map(Wrapped),
+        Ranges::to(Wrapped(PureVersion::new(1, 0, 0)))
+    );
+}
+
+#[test]
+fn num_intervals() {
+    let r = |s: &str| Ranges::<PureVersion>::from_str(s).unwrap();
+    let caret = r(">=1.0.0 && <2.0.0-0");
+    assert_eq!(caret.num_intervals(), 1);
+    assert!(caret.is_contiguous());
+
+    let not_eq = r("!=1.2.3");
+    assert_eq!(not_eq.num_intervals(), 2);
+    assert!(!not_eq.is_contiguous());
+
+    assert_eq!(
+        r("<1.0.0 || >=2.0.0 && <3.0.0 || ==4.0.0").num_intervals(),
+        3
+    );
+    assert!(Ranges::<PureVersion>::full().is_contiguous());
+    assert!(Ranges::<u8>::to(4).is_contiguous());
+    assert!(!Ranges::<PureVersion>::EMPTY.is_contiguous());
+    for range in [not_eq, caret, Ranges::full(), Ranges::EMPTY] {
+        assert_eq!(range.num_intervals(), range.intervals().count());
+    }
+}
+
+#[test]
+fn clones_share_the_extremes() {
+    let original = Ranges::<PureVersion>::from_str(">=1.0.0 && <2.0.0 || >=3.0.0").unwrap();
+    let clone = original.clone();
+    assert!(clone.extremes.ptr_eq(&original.extremes));
+
+    // Operations that do not change the set keep sharing the storage
+    let mut unchanged = original.clone();
+    unchanged.and_assign(&Ranges::full());
+    unchanged.or_assign(&Ranges::from_str(">=1.5.0 && <1.6.0").unwrap());
+    assert!(unchanged.extremes.ptr_eq(&original.extremes));
+}
+
+#[test]
+fn mutating_a_clone_does_not_affect_the_original() {
+    let original = Ranges::<PureVersion>::from_str(">=1.0.0 && <2.0.0 || >=3.0.0").unwrap();
+    let snapshot = original.to_string();
+    let other = Ranges::from_str(">=1.5.0 && <3.5.0").unwrap();
+
+    let mut and = original.clone();
+    and.and_assign(&other);
+    let mut or = original.clone();
+    or.or_assign(&other);
+    let not = original.clone().not();
+    let mapped = original.clone().map(|v| v);
+    let xor = Ranges::xor([&original, &other]);
+
+    assert_eq!(original.to_string(), snapshot);
+    assert_eq!(and.to_string(), ">=1.5.0 && <2.0.0 || >=3.0.0 && <3.5.0");
+    assert_eq!(or.to_string(), ">=1.0.0");
+    assert!(!not.contains(&"1.5.0".parse().unwrap()));
+    assert_eq!(mapped, original);
+    assert!(!xor.contains(&"1.6.0".parse().unwrap()));
+    assert!(original.contains(&"1.6.0".parse().unwrap()));
+}
+
+#[test]
+fn unique_sets_are_modified_in_place() {
+    let mut unique = Ranges::<u32>::between(1, 5);
+    let storage = unique.extremes.storage_ptr();
+    unique.and_assign(&Ranges::between(3, 9));
+    assert_eq!(unique, Ranges::between(3, 5));
+    unique.or_assign(&Ranges::between(7, 9));
+    assert_eq!(unique, Ranges::between(3, 5).or(&Ranges::between(7, 9)));
+    assert_eq!(unique.extremes.storage_ptr(), storage);
+
+    // Once shared, the storage is copied instead
+    let shared = unique.clone();
+    unique.and_assign(&Ranges::between(4, 9));
+    assert_ne!(unique.extremes.storage_ptr(), storage);
+    assert_eq!(shared.extremes.storage_ptr(), storage);
+    assert_eq!(shared, Ranges::between(3, 5).or(&Ranges::between(7, 9)));
+}
+
+#[test]
+fn is_exactly() {
+    let v: PureVersion = "1.2.3".parse().unwrap();
+    let r = |s: &str| Ranges::<PureVersion>::from_str(s).unwrap();
+
+    assert!(r("==1.2.3").is_exactly(&v));
+    assert!(r(">=1.2.3 && <=1.2.3").is_exactly(&v));
+    assert!(Ranges::single(v.clone()).is_exactly(&v));
+    assert!(!r(">=1.2.3, <1.3.0").is_exactly(&v));
+    assert!(!r("==1.2.4").is_exactly(&v));
+    assert!(!r("==1.2.3 || ==1.2.4").is_exactly(&v));
+    assert!(!Ranges::EMPTY.is_exactly(&v));
+    assert!(!Ranges::full().is_exactly(&v));
+
+    assert!(Ranges::between(5u8, 6).is_exactly(&5));
+    assert!(Ranges::single(0u8).is_exactly(&0));
+    assert!(Ranges::single(u8::MAX).is_exactly(&u8::MAX));
+}
@@ -0,0 +1,146 @@
+//! Requirement files
+//!
+//! A requirement file lists one named requirement per line, like
+//! ```text
+//! serde >=1.0.0 && <2.0.0
+//! tokio >=1.38.0  # async runtime
+//! ```
+//! Everything after a `#` is a comment, and blank lines are ignored.
+
+use std::fmt::Display;
+
+use chumsky::error::Rich;
+use derive_more::{Deref, DerefMut};
+use itertools::Itertools;
+use snafu::Snafu;
+
+use crate::{range::Ranges, version::pure::PureVersion};
+
+#[cfg(test)]
+mod tests;
+
+/// A parsed requirement file
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deref, DerefMut)]
+pub struct ReqFile(pub Vec<(String, Ranges<PureVersion>)>);
+
+impl Display for ReqFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, requirement) in &self.0 {
+            writeln!(f, "{name} {requirement}")?;
+        }
+        Ok(())
+    }
+}
+
+/// What to do when a name appears more than once
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Duplicates {
+    /// Report an error
+    #[default]
+    Error,
+    /// Keep the last requirement, in the position of the first
+    LastWins,
+}
+
+/// Options for [`parse_with_options`]
+#[derive(Debug, Clone, Copy)]
+pub struct ReqFileOptions {
+    /// Check if a package name is valid
+    pub valid_name: fn(&str) -> bool,
+    /// What to do when a name appears more than once
+    pub duplicates: Duplicates,
+}
+
+impl Default for ReqFileOptions {
+    fn default() -> Self {
+        Self {
+            valid_name: |name| !name.is_empty() && !name.contains(char::is_whitespace),
+            duplicates: Duplicates::default(),
+        }
+    }
+}
+
+/// Parse a requirement file with the default options
+pub fn parse(s: &str) -> Result<ReqFile, ReqFileError> {
+    parse_with_options(s, &ReqFileOptions::default())
+}
+
+/// Parse a requirement file
+pub fn parse_with_options(s: &str, options: &ReqFileOptions) -> Result<ReqFile, ReqFileError> {
+    let mut file = ReqFile::default();
+    let mut first_lines = vec![];
+
+    for (idx, line) in s.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (name, requirement) = line
+            .split_once(char::is_whitespace)
+            .map_or((line, ""), |(name, requirement)| (name, requirement.trim()));
+
+        if !(options.valid_name)(name) {
+            return Err(ReqFileError::InvalidName {
+                line: line_no,
+                name: name.to_owned(),
+            });
+        }
+        if requirement.is_empty() {
+            return Err(ReqFileError::MissingRequirement {
+                line: line_no,
+                name: name.to_owned(),
+            });
+        }
+        let requirement =
+            Ranges::from_str(requirement).map_err(|errors| ReqFileError::InvalidRequirement {
+                line: line_no,
+                name: name.to_owned(),
+                errors: errors.into_iter().map(Rich::into_owned).collect(),
+            })?;
+
+        match file.iter().position(|(other, _)| other == name) {
+            None => {
+                file.push((name.to_owned(), requirement));
+                first_lines.push(line_no);
+            }
+            Some(idx) => match options.duplicates {
+                Duplicates::Error => {
+                    return Err(ReqFileError::DuplicateName {
+                        line: line_no,
+                        name: name.to_owned(),
+                        first_line: first_lines[idx],
+                    });
+                }
+                Duplicates::LastWins => file[idx].1 = requirement,
+            },
+        }
+    }
+
+    Ok(file)
+}
+
+#[derive(Debug, Clone, Snafu)]
+pub enum ReqFileError {
+    #[snafu(display("Line {line}: invalid package name `{name}`"))]
+    InvalidName { line: usize, name: String },
+    #[snafu(display("Line {line}: missing requirement for `{name}`"))]
+    MissingRequirement { line: usize, name: String },
+    #[snafu(display(
+        "Line {line}: invalid requirement for `{name}`: {}",
+        errors.iter().join("; ")
+    ))]
+    InvalidRequirement {
+        line: usize,
+        name: String,
+        /// Errors in the requirement, with spans relative to the start of the requirement
+        errors: Vec<Rich<'static, char>>,
+    },
+    #[snafu(display("Line {line}: `{name}` was already required at line {first_line}"))]
+    DuplicateName {
+        line: usize,
+        name: String,
+        first_line: usize,
+    },
+}
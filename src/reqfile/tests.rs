@@ -0,0 +1,99 @@
+use super::{Duplicates, ReqFileError, ReqFileOptions, parse, parse_with_options};
+use crate::{range::Ranges, version::pure::PureVersion};
+
+fn r(s: &str) -> Ranges<PureVersion> {
+    Ranges::from_str(s).unwrap()
+}
+
+#[test]
+fn comments_and_blank_lines() {
+    let file = parse(
+        "# dependencies\n\
+         serde >=1.0.0 && <2.0.0\n\
+         \n\
+         tokio   >=1.38.0 # async runtime\n\
+         \t   \n\
+         log *\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        *file,
+        [
+            ("serde".to_owned(), r(">=1.0.0 && <2.0.0")),
+            ("tokio".to_owned(), r(">=1.38.0")),
+            ("log".to_owned(), r("*")),
+        ]
+    );
+}
+
+#[test]
+fn crlf() {
+    let file = parse("serde >=1.0.0\r\n\r\ntokio ==1.38.0 # pinned\r\n").unwrap();
+
+    assert_eq!(
+        *file,
+        [
+            ("serde".to_owned(), r(">=1.0.0")),
+            ("tokio".to_owned(), r("==1.38.0")),
+        ]
+    );
+}
+
+#[test]
+fn canonical_display() {
+    let file = parse("serde   >=1.0.0&&<2.0.0  # comment\ntokio ==1.38.0\n").unwrap();
+
+    assert_eq!(
+        file.to_string(),
+        "serde >=1.0.0 && <2.0.0\ntokio ==1.38.0\n"
+    );
+    assert_eq!(parse(&file.to_string()).unwrap(), file);
+}
+
+#[test]
+fn duplicates() {
+    let s = "serde >=1.0.0\ntokio *\nserde >=1.0.100\n";
+
+    assert!(matches!(
+        parse(s),
+        Err(ReqFileError::DuplicateName {
+            line: 3,
+            first_line: 1,
+            ..
+        })
+    ));
+
+    let options = ReqFileOptions {
+        duplicates: Duplicates::LastWins,
+        ..Default::default()
+    };
+    assert_eq!(
+        *parse_with_options(s, &options).unwrap(),
+        [
+            ("serde".to_owned(), r(">=1.0.100")),
+            ("tokio".to_owned(), r("*")),
+        ]
+    );
+}
+
+#[test]
+fn errors() {
+    assert!(matches!(
+        parse("serde >=1.0.0\ntokio >=>1.0\n"),
+        Err(ReqFileError::InvalidRequirement { line: 2, .. })
+    ));
+    assert!(matches!(
+        parse("\n\nserde # no requirement\n"),
+        Err(ReqFileError::MissingRequirement { line: 3, .. })
+    ));
+
+    let options = ReqFileOptions {
+        valid_name: |name| name.chars().all(|c| c.is_ascii_lowercase()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        parse_with_options("Serde *\n", &options),
+        Err(ReqFileError::InvalidName { line: 1, .. })
+    ));
+}
@@ -0,0 +1,102 @@
+//! Minimal resolution of requirements without transitive dependencies
+
+use std::{collections::HashMap, fmt::Display, hash::Hash};
+
+use snafu::Snafu;
+
+use crate::{
+    range::Ranges,
+    version::pure::{PrereleasePolicy, PureVersion},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Options for [`select_highest`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ResolveOptions {
+    /// How prerelease versions are matched against the requirements
+    pub prerelease: PrereleasePolicy,
+    /// Select the lowest satisfying version instead of the highest
+    ///
+    /// Useful to test that the lower bounds of the requirements are correct.
+    pub prefer_lowest: bool,
+}
+
+/// Select the highest available version satisfying each requirement
+///
+/// Only the packages in `requirements` are selected. If several requirements cannot
+/// be satisfied, the one of the package that sorts first by name is reported.
+pub fn select_highest<N>(
+    available: &HashMap<N, Vec<PureVersion>>,
+    requirements: &HashMap<N, Ranges<PureVersion>>,
+    options: ResolveOptions,
+) -> Result<HashMap<N, PureVersion>, Box<Unsatisfiable<N>>>
+where
+    N: Ord + Hash + Clone + Display,
+{
+    let mut requirements: Vec<_> = requirements.iter().collect();
+    requirements.sort_unstable_by_key(|(name, _)| *name);
+    requirements
+        .into_iter()
+        .map(|(name, requirement)| {
+            let versions = available.get(name).map(Vec::as_slice).unwrap_or_default();
+            let satisfying = versions
+                .iter()
+                .filter(|v| requirement.contains_with_policy(v, options.prerelease));
+            let selected = if options.prefer_lowest {
+                satisfying.min()
+            } else {
+                satisfying.max()
+            };
+
+            match selected {
+                Some(selected) => Ok((name.clone(), selected.clone())),
+                None => Err(Box::new(Unsatisfiable::new(name, requirement, versions))),
+            }
+        })
+        .collect()
+}
+
+/// No available version satisfies a requirement
+#[derive(Debug, Clone, Snafu)]
+#[snafu(display("No available version of {package} satisfies `{requirement}`"))]
+pub struct Unsatisfiable<N>
+where
+    N: Display,
+{
+    /// The package that could not be selected
+    pub package: N,
+    /// The requirement on the package
+    pub requirement: Ranges<PureVersion>,
+    /// The highest available version below all the versions allowed by the requirement
+    pub below: Option<PureVersion>,
+    /// The lowest available version above all the versions allowed by the requirement
+    pub above: Option<PureVersion>,
+}
+
+impl<N> Unsatisfiable<N>
+where
+    N: Display + Clone,
+{
+    fn new(package: &N, requirement: &Ranges<PureVersion>, available: &[PureVersion]) -> Self {
+        let (below, above) = if requirement.is_empty() {
+            (None, None)
+        } else {
+            (
+                requirement
+                    .lowest()
+                    .and_then(|lowest| available.iter().filter(|v| *v < lowest).max().cloned()),
+                requirement
+                    .highest()
+                    .and_then(|highest| available.iter().filter(|v| *v >= highest).min().cloned()),
+            )
+        };
+        Self {
+            package: package.clone(),
+            requirement: requirement.clone(),
+            below,
+            above,
+        }
+    }
+}
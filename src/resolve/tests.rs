@@ -0,0 +1,114 @@
+use std::{collections::HashMap, str::FromStr};
+
+use super::{ResolveOptions, select_highest};
+use crate::{
+    range::Ranges,
+    version::pure::{PrereleasePolicy, PureVersion},
+};
+
+fn v(s: &str) -> PureVersion {
+    PureVersion::from_str(s).unwrap()
+}
+
+fn available() -> HashMap<&'static str, Vec<PureVersion>> {
+    HashMap::from([
+        (
+            "serde",
+            vec![v("1.0.0"), v("1.0.200"), v("1.1.0-rc.1"), v("2.0.0")],
+        ),
+        ("tokio", vec![v("0.2.0"), v("1.0.0"), v("1.38.0")]),
+        ("nightly", vec![v("0.1.0-alpha.1"), v("0.1.0-alpha.2")]),
+    ])
+}
+
+fn requirements(reqs: &[(&'static str, &str)]) -> HashMap<&'static str, Ranges<PureVersion>> {
+    reqs.iter()
+        .map(|(name, req)| (*name, Ranges::from_str(req).unwrap()))
+        .collect()
+}
+
+#[test]
+fn satisfiable() {
+    let reqs = requirements(&[("serde", ">=1.0.0 && <2.0.0"), ("tokio", ">=1.0.0")]);
+
+    let selected = select_highest(&available(), &reqs, ResolveOptions::default()).unwrap();
+    assert_eq!(selected.len(), 2);
+    assert_eq!(selected["serde"], v("1.0.200"));
+    assert_eq!(selected["tokio"], v("1.38.0"));
+
+    let options = ResolveOptions {
+        prefer_lowest: true,
+        ..Default::default()
+    };
+    let selected = select_highest(&available(), &reqs, options).unwrap();
+    assert_eq!(selected["serde"], v("1.0.0"));
+    assert_eq!(selected["tokio"], v("1.0.0"));
+}
+
+#[test]
+fn unsatisfiable() {
+    let reqs = requirements(&[("tokio", ">=0.3.0 && <1.0.0")]);
+
+    let err = select_highest(&available(), &reqs, ResolveOptions::default()).unwrap_err();
+    assert_eq!(err.package, "tokio");
+    assert_eq!(err.requirement, reqs["tokio"]);
+    assert_eq!(err.below, Some(v("0.2.0")));
+    assert_eq!(err.above, Some(v("1.0.0")));
+
+    let reqs = requirements(&[("missing", "*")]);
+    let err = select_highest(&available(), &reqs, ResolveOptions::default()).unwrap_err();
+    assert_eq!(err.package, "missing");
+    assert_eq!((err.below, err.above), (None, None));
+}
+
+#[test]
+fn first_unsatisfiable_by_name_is_reported() {
+    let reqs = requirements(&[
+        ("tokio", ">=3.0.0"),
+        ("serde", ">=3.0.0"),
+        ("nightly", ">=3.0.0"),
+        ("missing", "*"),
+    ]);
+
+    for _ in 0..16 {
+        let err = select_highest(&available(), &reqs, ResolveOptions::default()).unwrap_err();
+        assert_eq!(err.package, "missing");
+    }
+}
+
+#[test]
+fn prerelease_only() {
+    let reqs = requirements(&[("nightly", ">=0.1.0-alpha.1")]);
+
+    let selected = select_highest(&available(), &reqs, ResolveOptions::default()).unwrap();
+    assert_eq!(selected["nightly"], v("0.1.0-alpha.2"));
+
+    let reqs = requirements(&[("nightly", "*")]);
+    assert!(select_highest(&available(), &reqs, ResolveOptions::default()).is_err());
+
+    let options = ResolveOptions {
+        prerelease: PrereleasePolicy::Include,
+        ..Default::default()
+    };
+    let selected = select_highest(&available(), &reqs, options).unwrap();
+    assert_eq!(selected["nightly"], v("0.1.0-alpha.2"));
+
+    let options = ResolveOptions {
+        prerelease: PrereleasePolicy::Exclude,
+        ..Default::default()
+    };
+    let reqs = requirements(&[("nightly", ">=0.1.0-alpha.1")]);
+    assert!(select_highest(&available(), &reqs, options).is_err());
+}
+
+#[test]
+fn prereleases_are_skipped_by_default() {
+    let reqs = requirements(&[("serde", ">=1.0.0 && <2.0.0")]);
+
+    let options = ResolveOptions {
+        prerelease: PrereleasePolicy::Include,
+        ..Default::default()
+    };
+    let selected = select_highest(&available(), &reqs, options).unwrap();
+    assert_eq!(selected["serde"], v("1.1.0-rc.1"));
+}
@@ -0,0 +1,149 @@
+//! Matching of versions and requirements that are either parsed or raw strings
+//!
+//! Functions generic over [`AsVersion`] and [`AsRequirement`] accept both forms at the
+//! same call site. Strings are parsed on demand, so these functions return a [`Result`].
+//!
+//! ```
+//! # use areq::{range::Ranges, satisfies, version::Version};
+//! let req = Ranges::from_str(">=1.2.0 && <2.0.0").unwrap();
+//! let version: Version = "1.4.0".parse().unwrap();
+//!
+//! assert!(satisfies::matches(&version, &req).unwrap());
+//! assert!(satisfies::matches("1.4.0", ">=1.2.0 && <2.0.0").unwrap());
+//! assert!(satisfies::matches(&version, "<1.0.0").is_ok_and(|matches| !matches));
+//! assert!(satisfies::matches("latest", &req).is_err());
+//! ```
+
+use std::borrow::Cow;
+
+use snafu::{ResultExt, Snafu};
+
+use crate::{
+    cache::InvalidRequirement,
+    range::Ranges,
+    version::{InvalidVersion, Version, pure::PureVersion},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A requirement, or something that can be parsed as one
+pub trait AsRequirement {
+    fn as_requirement(&self) -> Result<Cow<'_, Ranges<PureVersion>>, SatisfiesError>;
+}
+
+/// A version, or something that can be parsed as one
+///
+/// Build metadata does not influence matching, so only the [`PureVersion`] is returned.
+pub trait AsVersion {
+    fn as_version(&self) -> Result<Cow<'_, PureVersion>, SatisfiesError>;
+}
+
+impl AsRequirement for Ranges<PureVersion> {
+    fn as_requirement(&self) -> Result<Cow<'_, Ranges<PureVersion>>, SatisfiesError> {
+        Ok(Cow::Borrowed(self))
+    }
+}
+
+impl AsRequirement for str {
+    fn as_requirement(&self) -> Result<Cow<'_, Ranges<PureVersion>>, SatisfiesError> {
+        Ranges::from_str(self).map(Cow::Owned).map_err(|errors| {
+            InvalidRequirement {
+                requirement: self.to_owned(),
+                errors: errors.into_iter().map(|err| err.into_owned()).collect(),
+            }
+            .into()
+        })
+    }
+}
+
+impl AsRequirement for String {
+    fn as_requirement(&self) -> Result<Cow<'_, Ranges<PureVersion>>, SatisfiesError> {
+        self.as_str().as_requirement()
+    }
+}
+
+impl<T: AsRequirement + ?Sized> AsRequirement for &T {
+    fn as_requirement(&self) -> Result<Cow<'_, Ranges<PureVersion>>, SatisfiesError> {
+        (**self).as_requirement()
+    }
+}
+
+impl AsVersion for PureVersion {
+    fn as_version(&self) -> Result<Cow<'_, PureVersion>, SatisfiesError> {
+        Ok(Cow::Borrowed(self))
+    }
+}
+
+impl AsVersion for Version {
+    fn as_version(&self) -> Result<Cow<'_, PureVersion>, SatisfiesError> {
+        Ok(Cow::Borrowed(&self.pure))
+    }
+}
+
+impl AsVersion for str {
+    fn as_version(&self) -> Result<Cow<'_, PureVersion>, SatisfiesError> {
+        let version: Version = self
+            .parse()
+            .context(InvalidVersionSnafu { version: self })?;
+        Ok(Cow::Owned(version.pure))
+    }
+}
+
+impl AsVersion for String {
+    fn as_version(&self) -> Result<Cow<'_, PureVersion>, SatisfiesError> {
+        self.as_str().as_version()
+    }
+}
+
+impl<T: AsVersion + ?Sized> AsVersion for &T {
+    fn as_version(&self) -> Result<Cow<'_, PureVersion>, SatisfiesError> {
+        (**self).as_version()
+    }
+}
+
+/// Check if the version matches the requirement, with the default prerelease policy
+///
+/// This is [`Version::matches`], for parsed or raw arguments.
+pub fn matches(version: impl AsVersion, req: impl AsRequirement) -> Result<bool, SatisfiesError> {
+    let req = req.as_requirement()?;
+    Ok(req.contains_with_policy(&*version.as_version()?, Default::default()))
+}
+
+/// Return the highest of the versions matching the requirement
+///
+/// Every version is checked, so an invalid one is an error even if a higher version matches.
+///
+/// ```
+/// # use areq::satisfies;
+/// let versions = ["1.2.0", "1.9.3", "2.0.0", "1.10.0-rc.1"];
+/// let max = satisfies::max_satisfying(versions, ">=1.0.0 && <2.0.0").unwrap();
+/// assert_eq!(max, Some("1.9.3"));
+/// ```
+pub fn max_satisfying<V: AsVersion>(
+    versions: impl IntoIterator<Item = V>,
+    req: impl AsRequirement,
+) -> Result<Option<V>, SatisfiesError> {
+    let req = req.as_requirement()?;
+    let mut max: Option<(V, PureVersion)> = None;
+    for version in versions {
+        let pure = version.as_version()?.into_owned();
+        if req.contains_with_policy(&pure, Default::default())
+            && max.as_ref().is_none_or(|(_, max)| pure > *max)
+        {
+            max = Some((version, pure));
+        }
+    }
+    Ok(max.map(|(version, _)| version))
+}
+
+#[derive(Debug, Clone, Snafu)]
+pub enum SatisfiesError {
+    #[snafu(display("Invalid version `{version}`"))]
+    InvalidVersion {
+        version: String,
+        source: InvalidVersion,
+    },
+    #[snafu(transparent)]
+    InvalidRequirement { source: InvalidRequirement },
+}
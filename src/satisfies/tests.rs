@@ -0,0 +1,68 @@
+use super::{SatisfiesError, matches, max_satisfying};
+use crate::{
+    range::Ranges,
+    version::{Version, pure::PureVersion},
+};
+
+fn req() -> Ranges<PureVersion> {
+    Ranges::from_str(">=1.2.0 && <2.0.0").unwrap()
+}
+
+#[test]
+fn parsed_and_raw_arguments() {
+    let version: Version = "1.4.0+build".parse().unwrap();
+    let pure = version.pure.clone();
+    let raw = String::from(">=1.2.0 && <2.0.0");
+
+    let req = req();
+    assert!(matches(&version, &req).unwrap());
+    assert!(matches(&pure, &req).unwrap());
+    assert!(matches("1.4.0+build", &raw).unwrap());
+    assert!(matches(String::from("1.4.0"), raw.as_str()).unwrap());
+    assert!(!matches("2.0.0", &req).unwrap());
+    // Prereleases must be explicitly requested
+    assert!(!matches("1.5.0-beta", req).unwrap());
+}
+
+#[test]
+fn errors_are_propagated() {
+    assert!(matches!(
+        matches("1.2", req()),
+        Err(SatisfiesError::InvalidVersion { version, .. }) if version == "1.2"
+    ));
+    assert!(matches!(
+        matches("1.2.0", ">= 1.2.0 &&"),
+        Err(SatisfiesError::InvalidRequirement { .. })
+    ));
+    assert_eq!(
+        max_satisfying(["1.2.0", "latest", "1.3.0"], req())
+            .unwrap_err()
+            .to_string(),
+        "Invalid version `latest`"
+    );
+}
+
+#[test]
+fn max_of_mixed_sources() {
+    let versions: Vec<Version> = ["1.2.0", "1.9.3+b", "2.0.0", "1.9.3-rc.1", "0.9.0"]
+        .into_iter()
+        .map(|v| v.parse().unwrap())
+        .collect();
+    assert_eq!(
+        max_satisfying(&versions, "<2.0.0").unwrap(),
+        Some(&versions[1])
+    );
+    assert_eq!(
+        max_satisfying(["1.2.0", "1.10.0", "1.9.0"], req()).unwrap(),
+        Some("1.10.0")
+    );
+    assert_eq!(max_satisfying(&versions, ">=3.0.0").unwrap(), None);
+    assert_eq!(
+        max_satisfying(Vec::<&str>::new(), "invalid")
+            .unwrap_err()
+            .to_string(),
+        max_satisfying(["1.0.0"], "invalid")
+            .unwrap_err()
+            .to_string()
+    );
+}
@@ -0,0 +1,114 @@
+//! Sorting collections of version strings, like the tags of a repository
+
+use std::cmp::Ordering;
+
+use crate::version::{InvalidVersion, ParseOptions, Version};
+
+#[cfg(test)]
+mod tests;
+
+/// Options of [`sort_versions`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SortOptions {
+    /// Sort from the highest version
+    pub descending: bool,
+    /// Trim whitespace, and accept `v` prefixes and leading zeros, like `v1.02.3`
+    pub lenient: bool,
+    pub dedup: Dedup,
+}
+
+/// Which versions are considered duplicates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Dedup {
+    /// Keep all the versions
+    #[default]
+    None,
+    /// Keep only the first of the versions that are equal, including the build metadata
+    Exact,
+    /// Keep only the first of the versions that differ only in their build metadata
+    IgnoreBuild,
+}
+
+/// Result of [`sort_versions`]
+#[derive(Debug, Clone, Default)]
+pub struct SortOutcome<'a> {
+    /// The parsed versions in order, with the strings they come from
+    pub sorted: Vec<(Version, &'a str)>,
+    /// The strings that could not be parsed, in input order
+    pub rejected: Vec<Rejected<'a>>,
+}
+
+/// A string that is not a valid version
+#[derive(Debug, Clone)]
+pub struct Rejected<'a> {
+    /// Position of the string in the input
+    pub index: usize,
+    pub string: &'a str,
+    pub error: InvalidVersion,
+}
+
+/// Parse and sort a list of version strings
+///
+/// Versions are sorted by precedence, and versions with the same precedence by their build
+/// metadata, as in [`Version::cmp_including_build`]. When deduplicating, the version that
+/// comes first in the result is kept.
+///
+/// ```
+/// # use areq::sort::{Dedup, SortOptions, sort_versions};
+/// let options = SortOptions {
+///     lenient: true,
+///     dedup: Dedup::IgnoreBuild,
+///     ..Default::default()
+/// };
+/// let outcome = sort_versions(&["v1.10.0", "1.2.0", "latest", "v1.2.0+build.5"], options);
+/// let sorted: Vec<_> = outcome.sorted.iter().map(|(_, s)| *s).collect();
+/// assert_eq!(sorted, ["1.2.0", "v1.10.0"]);
+/// assert_eq!(outcome.rejected[0].string, "latest");
+/// ```
+pub fn sort_versions<'a>(strings: &[&'a str], options: SortOptions) -> SortOutcome<'a> {
+    let parse_options = ParseOptions {
+        allow_leading_zeros: options.lenient,
+        ..Default::default()
+    };
+
+    let mut outcome = SortOutcome::default();
+    for (index, &string) in strings.iter().enumerate() {
+        let input = if options.lenient {
+            let trimmed = string.trim();
+            trimmed.strip_prefix(['v', 'V']).unwrap_or(trimmed)
+        } else {
+            string
+        };
+        match Version::parse_with_options(input, &parse_options) {
+            Ok(version) => outcome.sorted.push((version, string)),
+            Err(error) => outcome.rejected.push(Rejected {
+                index,
+                string,
+                error,
+            }),
+        }
+    }
+
+    let order = |(a, _): &(Version, &str), (b, _): &(Version, &str)| {
+        let ord = a.cmp_including_build(b);
+        if options.descending {
+            ord.reverse()
+        } else {
+            ord
+        }
+    };
+    outcome.sorted.sort_by(order);
+    match options.dedup {
+        Dedup::None => (),
+        Dedup::Exact => outcome
+            .sorted
+            .dedup_by(|(a, _), (b, _)| a.cmp_including_build(b) == Ordering::Equal),
+        Dedup::IgnoreBuild => outcome.sorted.dedup_by(|(a, _), (b, _)| a.pure == b.pure),
+    }
+    outcome
+}
+
+/// Sort versions by precedence, and versions with the same precedence by their build metadata
+pub fn sort_in_place(versions: &mut [Version]) {
+    versions.sort_by(Version::cmp_including_build);
+}
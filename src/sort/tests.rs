@@ -0,0 +1,120 @@
+use super::{Dedup, SortOptions, sort_in_place, sort_versions};
+use crate::version::Version;
+
+/// Tags as found in a real repository
+const TAGS: &[&str] = &[
+    "v1.2.0",
+    "v1.10.0",
+    "1.2.0",
+    "v2.0.0-rc.1",
+    "latest",
+    "v1.9.3",
+    "v1.2.0+build.7",
+    " v2.0.0 ",
+    "release-2019",
+    "v1.02.1",
+    "v2.0.0",
+];
+
+fn strings<'a>(sorted: &[(Version, &'a str)]) -> Vec<&'a str> {
+    sorted.iter().map(|(_, s)| *s).collect()
+}
+
+#[test]
+fn strict() {
+    let outcome = sort_versions(TAGS, SortOptions::default());
+    assert_eq!(strings(&outcome.sorted), ["1.2.0"]);
+    assert_eq!(outcome.rejected.len(), TAGS.len() - 1);
+    assert_eq!(outcome.rejected[0].index, 0);
+    assert_eq!(outcome.rejected[0].string, "v1.2.0");
+}
+
+#[test]
+fn lenient() {
+    let options = SortOptions {
+        lenient: true,
+        ..Default::default()
+    };
+    let outcome = sort_versions(TAGS, options);
+    assert_eq!(
+        strings(&outcome.sorted),
+        [
+            "v1.2.0",
+            "1.2.0",
+            "v1.2.0+build.7",
+            "v1.02.1",
+            "v1.9.3",
+            "v1.10.0",
+            "v2.0.0-rc.1",
+            " v2.0.0 ",
+            "v2.0.0",
+        ]
+    );
+    let rejected: Vec<_> = outcome
+        .rejected
+        .iter()
+        .map(|r| (r.index, r.string))
+        .collect();
+    assert_eq!(rejected, [(4, "latest"), (8, "release-2019")]);
+}
+
+#[test]
+fn descending() {
+    let options = SortOptions {
+        lenient: true,
+        descending: true,
+        dedup: Dedup::IgnoreBuild,
+    };
+    let outcome = sort_versions(TAGS, options);
+    // Equal versions keep the input order, and the first one in the result is kept
+    assert_eq!(
+        strings(&outcome.sorted),
+        [
+            " v2.0.0 ",
+            "v2.0.0-rc.1",
+            "v1.10.0",
+            "v1.9.3",
+            "v1.02.1",
+            "v1.2.0+build.7"
+        ]
+    );
+}
+
+#[test]
+fn dedup() {
+    let options = |dedup| SortOptions {
+        lenient: true,
+        dedup,
+        ..Default::default()
+    };
+    let count = |dedup| sort_versions(TAGS, options(dedup)).sorted.len();
+    assert_eq!(count(Dedup::None), 9);
+    // `v1.2.0` and `1.2.0`, `v2.0.0` and ` v2.0.0 `
+    assert_eq!(count(Dedup::Exact), 7);
+    // Also `v1.2.0+build.7`
+    assert_eq!(count(Dedup::IgnoreBuild), 6);
+
+    let outcome = sort_versions(TAGS, options(Dedup::IgnoreBuild));
+    assert_eq!(
+        strings(&outcome.sorted),
+        [
+            "v1.2.0",
+            "v1.02.1",
+            "v1.9.3",
+            "v1.10.0",
+            "v2.0.0-rc.1",
+            " v2.0.0 "
+        ]
+    );
+}
+
+#[test]
+fn in_place() {
+    let mut versions: Vec<Version> = ["1.10.0", "1.2.0+b", "1.2.0", "1.2.0-rc.1"]
+        .into_iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+    sort_in_place(&mut versions);
+    let sorted: Vec<_> = versions.iter().map(ToString::to_string).collect();
+    assert_eq!(sorted, ["1.2.0-rc.1", "1.2.0", "1.2.0+b", "1.10.0"]);
+}
@@ -1,14 +1,40 @@
-use std::{fmt::Display, str::FromStr};
+use std::{borrow::Cow, fmt::Display, io::BufRead, str::FromStr, sync::Arc};
 
+use chumsky::error::Rich;
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
-use lazy_regex::regex_captures;
-use snafu::Snafu;
+use lazy_regex::{regex_captures, regex_find};
+use snafu::{ResultExt, Snafu};
 
 pub mod build;
+pub mod calver;
+#[doc(hidden)]
+pub mod const_parse;
+#[cfg(feature = "deb")]
+pub mod deb;
+pub mod extended;
+pub mod git;
+pub mod go;
+pub mod maven;
+pub mod pep440;
 pub mod pure;
 
 use build::{BuildMetadata, InvalidBuildMetadata};
-use pure::{InvalidPureVersion, PureVersion};
+use git::{GitDescribeError, GitDescribed};
+use pure::{
+    BumpKind, Granularity, InvalidPureVersion, NumericPart, PrereleasePolicy, PureVersion, UInt,
+    VersionChange, prerelease::Prerelease,
+};
+
+use crate::range::Ranges;
+
+pub use options::ParseOptions;
+
+mod options;
+#[cfg(feature = "serde")]
+mod serde_impls;
+
+#[cfg(test)]
+mod tests;
 
 /// A semantic version
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deref, AsRef, AsMut, DerefMut)]
@@ -21,34 +47,344 @@ pub struct Version {
     pub build: Vec<BuildMetadata>,
 }
 
-impl Display for Version {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.pure.fmt(f)?;
-        if !self.build.is_empty() {
-            write!(f, "+{}", self.build[0])?;
-            for build in &self.build[1..] {
-                write!(f, ".{}", build)?;
-            }
+impl Version {
+    /// The version `0.0.0`, with no build metadata
+    ///
+    /// ```
+    /// # use areq::version::Version;
+    /// assert_eq!(Version::ZERO, "0.0.0".parse().unwrap());
+    /// ```
+    pub const ZERO: Self = Self {
+        pure: PureVersion::ZERO,
+        build: vec![],
+    };
+
+    /// Compare two versions, using build metadata as a tiebreaker
+    ///
+    /// Versions are compared by precedence first. Only if they have the same precedence
+    /// the build metadata is compared, identifier by identifier, as ASCII strings.
+    /// This is not the SemVer ordering, where build metadata is ignored.
+    pub fn cmp_including_build(&self, other: &Self) -> std::cmp::Ordering {
+        self.pure
+            .cmp(&other.pure)
+            .then_with(|| self.build.cmp(&other.build))
+    }
+
+    /// Build a version from its parts
+    ///
+    /// Every combination is valid: identifiers are checked when they are created,
+    /// and the numeric parts can take any value, `u64::MAX` included.
+    ///
+    /// ```
+    /// # use areq::version::Version;
+    /// let version: Version = "1.2.3-rc.1+build.5".parse().unwrap();
+    /// let (major, minor, patch, pre, build) = version.clone().into_parts();
+    /// assert_eq!((major, minor, patch), (1, 2, 3));
+    /// assert_eq!(Version::from_parts(major, minor, patch, pre, build), version);
+    /// ```
+    pub fn from_parts(
+        major: UInt,
+        minor: UInt,
+        patch: UInt,
+        pre: Vec<Prerelease>,
+        build: Vec<BuildMetadata>,
+    ) -> Self {
+        Self {
+            pure: PureVersion {
+                major,
+                minor,
+                patch,
+                pre: Cow::Owned(pre),
+            },
+            build,
         }
-        Ok(())
     }
-}
 
-impl FromStr for Version {
-    type Err = InvalidVersion;
+    /// Split the version into its parts, see [`Version::from_parts`]
+    pub fn into_parts(self) -> (UInt, UInt, UInt, Vec<Prerelease>, Vec<BuildMetadata>) {
+        let PureVersion {
+            major,
+            minor,
+            patch,
+            pre,
+        } = self.pure;
+        (major, minor, patch, pre.into_owned(), self.build)
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    /// Return whether this version is a prerelease of the release `release`
+    ///
+    /// ```
+    /// # use areq::version::Version;
+    /// let rc: Version = "1.2.0-rc.1".parse().unwrap();
+    /// assert!(rc.is_prerelease_of(&"1.2.0".parse().unwrap()));
+    /// assert!(!rc.is_prerelease_of(&"1.3.0".parse().unwrap()));
+    /// ```
+    pub fn is_prerelease_of(&self, release: &PureVersion) -> bool {
+        self.is_prerelease()
+            && !release.is_prerelease()
+            && (self.major, self.minor, self.patch) == (release.major, release.minor, release.patch)
+    }
+
+    /// Encode the version in bytes that sort lexicographically in the same order as the versions
+    ///
+    /// The order is the SemVer precedence, with the build metadata as a tiebreaker as in
+    /// [`Version::cmp_including_build`], so distinct versions get distinct keys.
+    /// This makes the bytes usable as keys of ordered key-value stores.
+    ///
+    /// The major, minor and patch versions are encoded as 8 big-endian bytes each. A tag byte
+    /// follows, bigger for releases than for prereleases. Each prerelease identifier is
+    /// tagged as numeric or alphanumeric, numeric identifiers being prefixed with their length,
+    /// and the list of identifiers is terminated by a zero byte. The build metadata is encoded
+    /// in the same way as alphanumeric identifiers.
+    ///
+    /// ```
+    /// # use areq::version::Version;
+    /// let rc: Version = "1.0.0-rc.1".parse().unwrap();
+    /// let release: Version = "1.0.0".parse().unwrap();
+    /// assert!(rc.to_sortable_bytes() < release.to_sortable_bytes());
+    /// ```
+    pub fn to_sortable_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(32);
+        self.pure.write_sortable_bytes(&mut out);
+        for identifier in &self.build {
+            out.push(1);
+            out.extend_from_slice(identifier.as_str().as_bytes());
+            out.push(0);
+        }
+        out.push(0);
+        out
+    }
+
+    /// Replace the major, minor and patch versions, keeping prerelease and build metadata
+    ///
+    /// As in [`Version::from_parts`], the numeric parts can take any value, `u64::MAX` included.
+    ///
+    /// ```
+    /// # use areq::version::Version;
+    /// let rc: Version = "1.2.0-rc.1+build.5".parse().unwrap();
+    /// assert_eq!(rc.with_core(1, 3, 0).to_string(), "1.3.0-rc.1+build.5");
+    /// ```
+    pub fn with_core(mut self, major: UInt, minor: UInt, patch: UInt) -> Self {
+        (self.pure.major, self.pure.minor, self.pure.patch) = (major, minor, patch);
+        self
+    }
+
+    /// Replace the build metadata with already validated identifiers
+    pub fn set_build(&mut self, build: impl IntoIterator<Item = BuildMetadata>) {
+        self.build = build.into_iter().collect();
+    }
+
+    /// Iterate over the prereleases of a release train, starting from this version
+    ///
+    /// Each version increments the trailing numeric prerelease identifier of the previous one,
+    /// or appends `.1` if the last identifier is not numeric. Only this version keeps its build
+    /// metadata. The iterator is endless for prereleases, and yields only this version if it
+    /// is a release: chain the release to close the train.
+    ///
+    /// ```
+    /// # use areq::version::Version;
+    /// let first: Version = "1.0.0-alpha.1".parse().unwrap();
+    /// let mut release = first.clone();
+    /// release.pure.set_prerelease([]);
+    /// let train: Vec<_> = first
+    ///     .prerelease_series()
+    ///     .take(3)
+    ///     .chain([release])
+    ///     .map(|v| v.to_string())
+    ///     .collect();
+    /// assert_eq!(train, ["1.0.0-alpha.1", "1.0.0-alpha.2", "1.0.0-alpha.3", "1.0.0"]);
+    /// ```
+    pub fn prerelease_series(&self) -> impl Iterator<Item = Version> + use<> {
+        std::iter::successors(Some(self.clone()), |prev| {
+            Some(Version {
+                pure: prev.next_prerelease()?,
+                build: vec![],
+            })
+        })
+    }
+
+    /// Classify the update from this version to `newer`
+    ///
+    /// See [`BumpKind`] for the classification of updates leaving a prerelease.
+    ///
+    /// ```
+    /// # use areq::version::{Version, pure::BumpKind};
+    /// let v = |s: &str| s.parse::<Version>().unwrap();
+    /// assert_eq!(v("1.2.3").bump_kind(&v("1.4.0")), BumpKind::Minor);
+    /// assert_eq!(v("1.2.3-rc.1").bump_kind(&v("1.2.3")), BumpKind::PrereleaseOnly);
+    /// assert_eq!(v("1.2.3").bump_kind(&v("1.2.3+build.2")), BumpKind::BuildOnly);
+    /// assert_eq!(v("1.2.3").bump_kind(&v("1.2.2")), BumpKind::Downgrade);
+    /// ```
+    pub fn bump_kind(&self, newer: &Version) -> BumpKind {
+        if newer.pure < self.pure {
+            return BumpKind::Downgrade;
+        }
+        match self.pure.diff(&newer.pure) {
+            VersionChange::Major => BumpKind::Major,
+            VersionChange::Minor => BumpKind::Minor,
+            VersionChange::Patch => BumpKind::Patch,
+            VersionChange::Prerelease => BumpKind::PrereleaseOnly,
+            VersionChange::None if self.build != newer.build => BumpKind::BuildOnly,
+            VersionChange::None => BumpKind::None,
+        }
+    }
+
+    /// Iterate over the releases from this version up to `other`, excluded
+    ///
+    /// The first release is this version without prerelease and build metadata. Each next
+    /// one increments the component chosen by `granularity`, resetting the following ones.
+    /// When incrementing that component can no longer reach `other`, as a coarser component
+    /// is still below the one of `other`, the coarser component is incremented instead.
+    /// So the iteration always ends.
+    ///
+    /// ```
+    /// # use areq::version::{Version, pure::Granularity};
+    /// let v = |s: &str| s.parse::<Version>().unwrap();
+    /// let releases: Vec<_> = v("1.0.0")
+    ///     .releases_between(&v("1.3.0"), Granularity::Minor)
+    ///     .map(|v| v.to_string())
+    ///     .collect();
+    /// assert_eq!(releases, ["1.0.0", "1.1.0", "1.2.0"]);
+    ///
+    /// let releases: Vec<_> = v("1.0.5")
+    ///     .releases_between(&v("1.2.2"), Granularity::Patch)
+    ///     .map(|v| v.to_string())
+    ///     .collect();
+    /// assert_eq!(releases, ["1.0.5", "1.1.0", "1.2.0", "1.2.1"]);
+    /// ```
+    pub fn releases_between(
+        &self,
+        other: &Version,
+        granularity: Granularity,
+    ) -> impl Iterator<Item = Version> + use<> {
+        let end = other.pure.clone();
+        let first = PureVersion::new(self.major, self.minor, self.patch);
+        let (end_major, end_minor) = (end.major, end.minor);
+        std::iter::successors(Some(first), move |prev| {
+            let PureVersion {
+                major,
+                minor,
+                patch,
+                ..
+            } = *prev;
+            let step = if major != end_major {
+                Granularity::Major
+            } else if minor != end_minor && granularity == Granularity::Patch {
+                Granularity::Minor
+            } else {
+                granularity
+            };
+            Some(match step {
+                Granularity::Major => PureVersion::new(major.checked_add(1)?, 0, 0),
+                Granularity::Minor => PureVersion::new(major, minor.checked_add(1)?, 0),
+                Granularity::Patch => PureVersion::new(major, minor, patch.checked_add(1)?),
+            })
+        })
+        .take_while(move |v| *v < end)
+        .map(|pure| Version {
+            pure,
+            build: vec![],
+        })
+    }
+
+    /// Return whether the two versions are equal after a [`Display`] round-trip
+    ///
+    /// This is the same as `==`, which compares the build metadata too.
+    /// To compare only the precedence, ignoring the build, compare the [`PureVersion`]s.
+    ///
+    /// ```
+    /// # use areq::version::Version;
+    /// let a: Version = "1.0.0+a".parse().unwrap();
+    /// let b: Version = "1.0.0+b".parse().unwrap();
+    /// assert!(!a.canonical_eq(&b));
+    /// assert_eq!(a.pure, b.pure);
+    /// ```
+    pub fn canonical_eq(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    /// Return whether this version satisfies a requirement
+    ///
+    /// Build metadata is ignored, and prereleases follow the default [`PrereleasePolicy`].
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::Version};
+    /// let req = Ranges::from_str(">=1.2.0 && <2.0.0").unwrap();
+    /// assert!("1.4.1+build.7".parse::<Version>().unwrap().matches(&req));
+    /// assert!(!"2.0.0".parse::<Version>().unwrap().matches(&req));
+    /// // Prereleases must be explicitly requested
+    /// assert!(!"1.5.0-beta".parse::<Version>().unwrap().matches(&req));
+    /// ```
+    pub fn matches(&self, req: &Ranges<PureVersion>) -> bool {
+        req.contains_with_policy(&self.pure, PrereleasePolicy::default())
+    }
+
+    /// Parse `req` and return whether this version satisfies it
+    ///
+    /// Same as [`Version::matches`], without formatting the version.
+    ///
+    /// ```
+    /// # use areq::version::Version;
+    /// let version: Version = "1.4.1".parse().unwrap();
+    /// assert_eq!(version.satisfies_str(">=1.2.0 && <2.0.0"), Ok(true));
+    /// assert!(version.satisfies_str(">=1.2.0 &&").is_err());
+    /// ```
+    pub fn satisfies_str<'a>(&self, req: &'a str) -> Result<bool, Vec<Rich<'a, char>>> {
+        Ranges::from_str(req).map(|req| self.matches(&req))
+    }
+
+    /// Parse a release version in a const context
+    ///
+    /// Prereleases and build metadata cannot be built at compile time, so they make this
+    /// panic, like invalid versions. See [`crate_version!`](crate::crate_version).
+    pub const fn parse_release_const(s: &str) -> Self {
+        let (major, minor, patch) = const_parse::parse_release(s);
+        Version {
+            pure: PureVersion {
+                major,
+                minor,
+                patch,
+                pre: Cow::Borrowed(&[]),
+            },
+            build: Vec::new(),
+        }
+    }
+
+    /// Parse a version, with additional options
+    ///
+    /// With the default options this is the same as [`Version::from_str`].
+    pub fn parse_with_options(s: &str, options: &ParseOptions) -> Result<Self, InvalidVersion> {
+        Self::parse_limited(&options.preprocess(s)?, options.max_prerelease_digits)
+    }
+
+    /// Parse a strict version, with the given limit on the digits of numeric prereleases
+    fn parse_limited(s: &str, max_prerelease_digits: usize) -> Result<Self, InvalidVersion> {
         let Some((_, major, minor, patch, pre, build)) = regex_captures!(
             r"^(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)(?:-((?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*)(?:\.(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*))*))?(?:\+([0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*))?$",
             s
         ) else {
-            let (pure, build) = s.split_once('+').unwrap_or((s, ""));
+            if let Some(position) = pure::find_stray_whitespace(s) {
+                return Err(InvalidVersion::InvalidPureVersion {
+                    source: InvalidPureVersion::UnexpectedWhitespace { position },
+                });
+            }
+
+            // A valid version followed by something that cannot be part of it
+            if let Ok((_, rest)) = Version::parse_prefix(s)
+                && rest.starts_with(|c: char| {
+                    !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'))
+                })
+            {
+                return TrailingContentSnafu { rest }.fail();
+            }
+
+            let (pure, build) = split_build(s);
 
             if let Err(source) = pure.parse::<PureVersion>() {
                 return Err(InvalidVersion::InvalidPureVersion { source });
             }
 
-            if !build.is_empty() {
+            if let Some(build) = build {
                 for build in build.split('.') {
                     if let Err(source) = build.parse::<BuildMetadata>() {
                         return Err(InvalidVersion::InvalidBuildMetadata { source });
@@ -56,12 +392,15 @@ impl FromStr for Version {
                 }
             }
 
-            unreachable!(
-                "At least one of the preceding conditions should fail if the regex failed. The passing version is {s}"
-            );
+            return Err(InvalidVersion::InvalidPureVersion {
+                source: InvalidPureVersion::Malformed {
+                    version: s.to_string(),
+                },
+            });
         };
 
-        let pure = PureVersion::from_checked_parts(major, minor, patch, pre)?;
+        let pure =
+            PureVersion::from_checked_parts(major, minor, patch, pre, max_prerelease_digits)?;
 
         let build = if !build.is_empty() {
             build
@@ -77,6 +416,264 @@ impl FromStr for Version {
 
         Ok(Self { pure, build })
     }
+
+    /// Parse the output of `git describe --tags`, like `v1.4.2-7-g3a5b9c1-dirty`
+    ///
+    /// See [`GitDescribed::to_version`] to get back a [`Version`].
+    pub fn from_git_describe(s: &str) -> Result<GitDescribed, GitDescribeError> {
+        s.parse()
+    }
+
+    /// Parse the longest version at the start of `s`, returning the rest of the input
+    ///
+    /// ```
+    /// # use areq::version::Version;
+    /// let (version, rest) = Version::parse_prefix("1.2.3-alpha+build rest").unwrap();
+    /// assert_eq!(version.to_string(), "1.2.3-alpha+build");
+    /// assert_eq!(rest, " rest");
+    /// ```
+    pub fn parse_prefix(s: &str) -> Result<(Self, &str), InvalidVersion> {
+        let (pure, rest) = PureVersion::parse_prefix(s)?;
+        let Some((build, rest)) = rest.strip_prefix('+').and_then(|tail| {
+            let build = regex_find!(r"^[0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*", tail)?;
+            Some((build, &tail[build.len()..]))
+        }) else {
+            return Ok((
+                Self {
+                    pure,
+                    build: vec![],
+                },
+                rest,
+            ));
+        };
+
+        let build = build
+            .split('.')
+            .map(|p| p.parse().context(InvalidBuildMetadataSnafu))
+            .collect::<Result<_, _>>()?;
+        Ok((Self { pure, build }, rest))
+    }
+
+    /// Parse the longest version at the start of `s`, returning the number of bytes consumed
+    ///
+    /// Like [`Version::parse_prefix`], but convenient to slice the surrounding input.
+    ///
+    /// ```
+    /// # use areq::version::Version;
+    /// let token = "name@1.2.3 extra";
+    /// let (version, len) = Version::parse_prefix_len(&token[5..]).unwrap();
+    /// assert_eq!(version.to_string(), "1.2.3");
+    /// assert_eq!(&token[5 + len..], " extra");
+    /// ```
+    pub fn parse_prefix_len(s: &str) -> Result<(Self, usize), InvalidVersion> {
+        let (version, rest) = Self::parse_prefix(s)?;
+        Ok((version, s.len() - rest.len()))
+    }
+
+    /// Lazily parse a newline-delimited list of versions
+    ///
+    /// Each line is trimmed, and blank lines are skipped. The iterator ends after
+    /// the first error reading from `reader`.
+    pub fn parse_stream<R: BufRead>(
+        reader: R,
+    ) -> impl Iterator<Item = Result<Version, InvalidVersion>> {
+        reader
+            .lines()
+            .scan(false, |failed, line| {
+                if *failed {
+                    return None;
+                }
+                *failed = line.is_err();
+                Some(line)
+            })
+            .filter_map(|line| {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(source) => {
+                        return Some(Err(InvalidVersion::Read {
+                            source: Arc::new(source),
+                        }));
+                    }
+                };
+                let line = line.trim();
+                if line.is_empty() {
+                    return None;
+                }
+                Some(line.parse())
+            })
+    }
+}
+
+impl Ranges<PureVersion> {
+    /// Create a range pinned to the precedence of `version`, ignoring its build metadata
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::Version};
+    /// let version: Version = "1.2.3-rc.1+build.5".parse().unwrap();
+    /// let pin = Ranges::pinned_to(&version);
+    /// assert_eq!(pin.to_string(), "==1.2.3-rc.1");
+    /// assert!(pin.contains(&version));
+    /// ```
+    pub fn pinned_to(version: &Version) -> Self {
+        Self::exact(version.pure.clone())
+    }
+
+    /// Return the versions matching the requirement, in the order they are given
+    ///
+    /// Matching follows [`Version::matches`], so prereleases are kept only if explicitly allowed.
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::Version};
+    /// let req = Ranges::from_str(">=1.2.0 && <2.0.0").unwrap();
+    /// let versions: Vec<Version> = ["1.1.0", "1.4.0", "1.5.0-rc.1", "2.0.0"]
+    ///     .into_iter()
+    ///     .map(|v| v.parse().unwrap())
+    ///     .collect();
+    /// assert_eq!(req.filter(&versions), [&versions[1]]);
+    /// ```
+    pub fn filter<'a>(&self, versions: &'a [Version]) -> Vec<&'a Version> {
+        versions.iter().filter(|v| v.matches(self)).collect()
+    }
+
+    /// Return the lowest version not below `current` that matches the requirement
+    ///
+    /// Matching follows [`Version::matches`], so prereleases are returned only if explicitly
+    /// allowed. If `current` already matches it is returned unchanged, otherwise the result
+    /// has no build metadata. Returns `None` if every matching version is below `current`.
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::Version};
+    /// let req = Ranges::from_str(">1.2.3 && <1.3.0 || >=2.0.0").unwrap();
+    /// let current: Version = "1.0.0".parse().unwrap();
+    /// assert_eq!(req.minimal_bump_from(&current).unwrap().to_string(), "1.2.4");
+    /// ```
+    pub fn minimal_bump_from(&self, current: &Version) -> Option<Version> {
+        if current.matches(self) {
+            return Some(current.clone());
+        }
+        let policy = PrereleasePolicy::default();
+        self.intervals()
+            .filter(|(_, end)| end.is_none_or(|end| *end > current.pure))
+            .find_map(|(start, _)| {
+                let lowest = match start {
+                    Some(start) if *start > current.pure => start.clone(),
+                    _ => current.pure.clone(),
+                };
+                if self.contains_with_policy(&lowest, policy) {
+                    return Some(lowest);
+                }
+                // Only the release follows its prereleases, if they are not allowed
+                let mut release = lowest;
+                release.set_prerelease([]);
+                self.contains_with_policy(&release, policy)
+                    .then_some(release)
+            })
+            .map(|pure| Version {
+                pure,
+                build: vec![],
+            })
+    }
+}
+
+/// Check that a slice of versions is sorted by precedence
+///
+/// Build metadata is ignored, so versions differing only by it are considered in order.
+/// On failure, returns the index of the first out-of-order pair, together with the pair.
+pub fn is_sorted(versions: &[Version]) -> Result<(), (usize, &Version, &Version)> {
+    match versions
+        .windows(2)
+        .position(|pair| pair[0].pure > pair[1].pure)
+    {
+        Some(idx) => Err((idx, &versions[idx], &versions[idx + 1])),
+        None => Ok(()),
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        padded(f, |f| {
+            self.pure.fmt(f)?;
+            if !self.build.is_empty() {
+                write!(f, "+{}", self.build[0])?;
+                for build in &self.build[1..] {
+                    write!(f, ".{}", build)?;
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Display with `write`, applying the width, fill, alignment and precision of `f` to the whole output
+///
+/// Without those flags the output is written directly, with no intermediate buffer.
+pub(crate) fn padded(
+    f: &mut std::fmt::Formatter<'_>,
+    write: impl Fn(&mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+) -> std::fmt::Result {
+    struct Unpadded<W>(W);
+
+    impl<W> Display for Unpadded<W>
+    where
+        W: Fn(&mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+    {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            (self.0)(f)
+        }
+    }
+
+    if f.width().is_none() && f.precision().is_none() {
+        write(f)
+    } else {
+        f.pad(&Unpadded(write).to_string())
+    }
+}
+
+/// Convert a version into its string representation
+///
+/// ```
+/// # use areq::version::Version;
+/// let version: Version = "1.2.3-alpha.1+build.5".parse().unwrap();
+/// let s: String = version.into();
+/// assert_eq!(s, "1.2.3-alpha.1+build.5");
+/// ```
+impl From<Version> for String {
+    fn from(value: Version) -> Self {
+        value.to_string()
+    }
+}
+
+impl From<&Version> for String {
+    fn from(value: &Version) -> Self {
+        value.to_string()
+    }
+}
+
+impl FromStr for Version {
+    type Err = InvalidVersion;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_limited(s, ParseOptions::DEFAULT.max_prerelease_digits)
+    }
+}
+
+impl TryFrom<&[u8]> for Version {
+    type Error = InvalidVersion;
+
+    /// Parse a version from raw bytes, without going through `str::from_utf8`
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        // The grammar is ASCII only, so any other byte can be rejected upfront
+        if let Some(position) = bytes.iter().position(|b| !b.is_ascii()) {
+            return NonAsciiSnafu {
+                position,
+                byte: bytes[position],
+            }
+            .fail();
+        }
+        std::str::from_utf8(bytes)
+            .expect("ASCII bytes are valid UTF-8")
+            .parse()
+    }
 }
 
 #[derive(Debug, Clone, Snafu)]
@@ -85,4 +682,94 @@ pub enum InvalidVersion {
     InvalidPureVersion { source: InvalidPureVersion },
     #[snafu(display("Invalid build metadata"))]
     InvalidBuildMetadata { source: InvalidBuildMetadata },
+    #[snafu(display("Unexpected content after the version: `{rest}`"))]
+    TrailingContent { rest: String },
+    #[snafu(display("Non-ASCII byte 0x{byte:02x} at position {position}"))]
+    NonAscii { position: usize, byte: u8 },
+    #[snafu(display("Could not read the versions"))]
+    Read { source: Arc<std::io::Error> },
+}
+
+impl InvalidVersion {
+    /// Locate the error in the `input` that failed to parse, as a range of bytes
+    ///
+    /// Errors that are not about a specific part, like a version missing its patch,
+    /// are located where the missing part should have been, or cover the whole input.
+    ///
+    /// ```
+    /// # use areq::version::Version;
+    /// let input = "1.02.3";
+    /// let err = input.parse::<Version>().unwrap_err();
+    /// assert_eq!(&input[err.span(input)], "02");
+    /// ```
+    pub fn span(&self, input: &str) -> std::ops::Range<usize> {
+        let (pure, build) = split_build(input);
+        match self {
+            InvalidVersion::InvalidPureVersion { source } => {
+                let part_span = |part: NumericPart| {
+                    let start = match part {
+                        NumericPart::Major => 0,
+                        NumericPart::Minor => nth_component(pure, 1),
+                        NumericPart::Patch => nth_component(pure, 2),
+                    };
+                    let end = pure[start..]
+                        .find(['.', '-'])
+                        .map_or(pure.len(), |len| start + len);
+                    start..end
+                };
+                match source {
+                    InvalidPureVersion::NumericPartTooLong { part, .. }
+                    | InvalidPureVersion::TooManyDigits { part, .. }
+                    | InvalidPureVersion::InvalidNumericPart { part, .. }
+                    | InvalidPureVersion::LeadingZeros { part, .. }
+                    | InvalidPureVersion::NonDigitNumericPart { part, .. } => part_span(*part),
+                    InvalidPureVersion::MissingNumericPart { .. } => pure.len()..pure.len(),
+                    InvalidPureVersion::ExtraBeforePrereleases { extra } => {
+                        let start = nth_component(pure, 3);
+                        start..start + extra.len()
+                    }
+                    InvalidPureVersion::InvalidPrerelease { .. } => {
+                        pure.find('-').map_or(0, |idx| idx + 1)..pure.len()
+                    }
+                    InvalidPureVersion::UnexpectedWhitespace { position } => {
+                        let len = input
+                            .get(*position..)
+                            .and_then(|rest| rest.chars().next())
+                            .map_or(0, char::len_utf8);
+                        *position..position + len
+                    }
+                    InvalidPureVersion::Empty | InvalidPureVersion::Malformed { .. } => {
+                        0..input.len()
+                    }
+                }
+            }
+            InvalidVersion::InvalidBuildMetadata { .. } => match build {
+                Some(build) => input.len() - build.len()..input.len(),
+                None => 0..input.len(),
+            },
+            InvalidVersion::TrailingContent { rest } => input.len() - rest.len()..input.len(),
+            InvalidVersion::NonAscii { position, .. } => *position..position + 1,
+            InvalidVersion::Read { .. } => 0..input.len(),
+        }
+    }
+}
+
+/// Split the build metadata from a version that might be invalid
+///
+/// A `+` starting a component is a sign, not the start of the build metadata.
+fn split_build(s: &str) -> (&str, Option<&str>) {
+    match s
+        .match_indices('+')
+        .find(|&(idx, _)| s[..idx].ends_with(|c: char| c.is_ascii_alphanumeric()))
+    {
+        Some((idx, _)) => (&s[..idx], Some(&s[idx + 1..])),
+        None => (s, None),
+    }
+}
+
+/// Start of the `n`th dot separated component of `s`, or its end if there are fewer
+fn nth_component(s: &str, n: usize) -> usize {
+    s.match_indices('.')
+        .nth(n - 1)
+        .map_or(s.len(), |(idx, _)| idx + 1)
 }
@@ -10,6 +10,12 @@ use snafu::Snafu;
 #[repr(transparent)]
 pub struct BuildMetadata(String);
 
+impl BuildMetadata {
+    pub(crate) fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
 impl FromStr for BuildMetadata {
     type Err = InvalidBuildMetadata;
 
@@ -0,0 +1,303 @@
+//! Calendar versioning
+//!
+//! Calendar versions are made of a year, a month and a micro number, like `2024.06.2`,
+//! optionally followed by a modifier like `-rc.1`. Years can be written in full (`YYYY`)
+//! or shortened to the years since 2000 (`YY`, so `24.11.0` is `2024.11.0`). Other year lengths
+//! are rejected.
+
+use std::{borrow::Cow, fmt::Display, num::ParseIntError, str::FromStr};
+
+use chumsky::{Parser, error::Rich, prelude::*, text::digits};
+use lazy_regex::regex_captures;
+use snafu::{ResultExt, Snafu};
+
+use super::{
+    padded,
+    pure::prerelease::{InvalidPrerelease, Prerelease},
+};
+use crate::range::{self, ParserExtra};
+
+#[cfg(test)]
+mod tests;
+
+/// A calendar version
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CalVer {
+    /// Full year, like `2024`
+    ///
+    /// Years before 1000 are displayed zero padded to 4 digits. Years after 9999 cannot
+    /// be written in either [`YearFormat`], so they do not survive a round trip through
+    /// [`Display`] and [`FromStr`].
+    pub year: u16,
+    /// Month, from 1 to 12
+    pub month: u8,
+    pub micro: u64,
+    /// Identifiers of the modifier, that precede the release like prereleases do
+    pub modifier: Cow<'static, [Prerelease]>,
+}
+
+/// How the year of a [`CalVer`] is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum YearFormat {
+    /// The full year, like `2024`
+    #[default]
+    Full,
+    /// The years since 2000, like `24`
+    Short,
+}
+
+impl CalVer {
+    pub fn new(year: u16, month: u8, micro: u64) -> Self {
+        Self {
+            year,
+            month,
+            micro,
+            modifier: Cow::Borrowed(&[]),
+        }
+    }
+
+    pub fn has_modifier(&self) -> bool {
+        !self.modifier.is_empty()
+    }
+
+    /// Display with the given year format
+    ///
+    /// The short format needs a year from 2000 to 2099, and falls back to the full year.
+    pub fn display_with(&self, format: YearFormat) -> impl Display + '_ {
+        struct WithFormat<'a>(&'a CalVer, YearFormat);
+
+        impl Display for WithFormat<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let Self(version, format) = self;
+                let year = match format {
+                    YearFormat::Short if (2000..2100).contains(&version.year) => {
+                        (version.year - 2000, 2)
+                    }
+                    _ => (version.year, 4),
+                };
+                display_impl(year, version.month, version.micro, &version.modifier, f)
+            }
+        }
+
+        WithFormat(self, format)
+    }
+
+    /// Parse a calendar version, accepting only years in the given format
+    ///
+    /// ```
+    /// # use areq::version::calver::{CalVer, YearFormat};
+    /// assert!(CalVer::from_str_with("24.11.0", YearFormat::Short).is_ok());
+    /// assert!(CalVer::from_str_with("2024.11.0", YearFormat::Short).is_err());
+    /// ```
+    pub fn from_str_with(s: &str, format: YearFormat) -> Result<Self, InvalidCalVer> {
+        Self::parse_impl(s, Some(format))
+    }
+
+    fn parse_impl(s: &str, format: Option<YearFormat>) -> Result<Self, InvalidCalVer> {
+        let Some((_, year, month, micro, modifier)) =
+            regex_captures!(r"^(\d+)\.(\d{1,2})\.(\d+)(?:-(.+))?$", s)
+        else {
+            return InvalidFormatSnafu { version: s }.fail();
+        };
+
+        let year = match (year.len(), format) {
+            (4, None | Some(YearFormat::Full)) => year.parse::<u16>().expect("4 digits"),
+            (2, None | Some(YearFormat::Short)) => 2000 + year.parse::<u16>().expect("2 digits"),
+            (_, format) => {
+                return InvalidYearSnafu {
+                    year,
+                    expected: match format {
+                        None => "2 or 4",
+                        Some(YearFormat::Full) => "4",
+                        Some(YearFormat::Short) => "2",
+                    },
+                }
+                .fail();
+            }
+        };
+        let month = month.parse::<u8>().expect("At most 2 digits");
+        if !(1..=12).contains(&month) {
+            return InvalidMonthSnafu { month }.fail();
+        }
+        let micro = micro.parse().context(MicroTooBigSnafu)?;
+        let modifier = if modifier.is_empty() {
+            Cow::Borrowed(&[] as &[_])
+        } else {
+            Cow::Owned(
+                modifier
+                    .split('.')
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()
+                    .context(InvalidModifierSnafu)?,
+            )
+        };
+
+        Ok(Self {
+            year,
+            month,
+            micro,
+            modifier,
+        })
+    }
+
+    const MIN: Self = Self {
+        year: 0,
+        month: 1,
+        micro: 0,
+        modifier: Cow::Borrowed({
+            static V: [Prerelease; 1] = [Prerelease::MIN];
+            &V
+        }),
+    };
+}
+
+impl Display for CalVer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        padded(f, |f| {
+            display_impl((self.year, 4), self.month, self.micro, &self.modifier, f)
+        })
+    }
+}
+
+/// Display a calendar version, with the year zero padded to `year_width` digits
+fn display_impl(
+    (year, year_width): (u16, usize),
+    month: u8,
+    micro: u64,
+    modifier: &[Prerelease],
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result {
+    write!(f, "{year:0year_width$}.{month:02}.{micro}")?;
+    if let Some((first, rest)) = modifier.split_first() {
+        write!(f, "-{first}")?;
+        for modifier in rest {
+            write!(f, ".{modifier}")?;
+        }
+    }
+    Ok(())
+}
+
+impl FromStr for CalVer {
+    type Err = InvalidCalVer;
+
+    /// Parse a calendar version, with the year in either [`YearFormat`]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_impl(s, None)
+    }
+}
+
+impl PartialOrd for CalVer {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Calendar versions sort chronologically, and modifiers precede their release
+impl Ord for CalVer {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.year, self.month, self.micro)
+            .cmp(&(other.year, other.month, other.micro))
+            .then_with(|| match (self.has_modifier(), other.has_modifier()) {
+                (true, true) => self.modifier.cmp(&other.modifier),
+                (true, false) => std::cmp::Ordering::Less,
+                (false, true) => std::cmp::Ordering::Greater,
+                (false, false) => std::cmp::Ordering::Equal,
+            })
+    }
+}
+
+/// Calculate the smallest release date and micro greater than the given one
+fn next_release(year: u16, month: u8, micro: u64) -> Option<(u16, u8, u64)> {
+    if let Some(micro) = micro.checked_add(1) {
+        Some((year, month, micro))
+    } else if month < 12 {
+        Some((year, month + 1, 0))
+    } else {
+        Some((year.checked_add(1)?, 1, 0))
+    }
+}
+
+impl range::RangeExtreme for CalVer {
+    fn minimum() -> Option<Self> {
+        Some(Self::MIN)
+    }
+
+    fn next(mut self) -> Option<Self> {
+        if !self.has_modifier() {
+            (self.year, self.month, self.micro) = next_release(self.year, self.month, self.micro)?;
+        }
+        self.modifier.to_mut().push(Prerelease::MIN);
+        Some(self)
+    }
+
+    fn compare_next_to(&self, other: &Self) -> bool {
+        let Some((last, other_modifier)) = other.modifier.split_last() else {
+            return false;
+        };
+        if last != &Prerelease::MIN {
+            return false;
+        }
+        if self.has_modifier() {
+            (self.year, self.month, self.micro) == (other.year, other.month, other.micro)
+                && *self.modifier == *other_modifier
+        } else {
+            other_modifier.is_empty()
+                && next_release(self.year, self.month, self.micro)
+                    == Some((other.year, other.month, other.micro))
+        }
+    }
+}
+
+impl range::RangeExtremeCanonical for CalVer {}
+
+impl range::RangeExtremeDisplay for CalVer {
+    fn has_prev(&self) -> bool {
+        self.modifier.last() == Some(&Prerelease::MIN)
+            && (self.modifier.len() > 1 || self.micro != 0)
+    }
+
+    fn display_prev(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !range::RangeExtremeDisplay::has_prev(self) {
+            return Err(std::fmt::Error);
+        }
+        display_impl(
+            (self.year, 4),
+            self.month,
+            self.micro - if self.modifier.len() == 1 { 1 } else { 0 },
+            self.modifier.split_last().unwrap().1,
+            f,
+        )
+    }
+}
+
+impl range::RangeExtremeParseable for CalVer {
+    fn parser<'a>() -> impl Parser<'a, &'a str, Self, ParserExtra<'a>> + Clone {
+        digits(10)
+            .separated_by(just('.'))
+            .exactly(3)
+            .then(
+                just('-')
+                    .then(Prerelease::parser().separated_by(just('.')).at_least(1))
+                    .or_not(),
+            )
+            .to_slice()
+            .try_map(|s: &str, span| s.parse().map_err(|err| Rich::custom(span, err)))
+    }
+}
+
+#[derive(Debug, Clone, Snafu)]
+pub enum InvalidCalVer {
+    #[snafu(display("Calendar versions must be in the `YYYY.MM.MICRO` format: `{version}`"))]
+    InvalidFormat { version: String },
+    #[snafu(display("The year must have {expected} digits: `{year}`"))]
+    InvalidYear {
+        year: String,
+        expected: &'static str,
+    },
+    #[snafu(display("The month must be between 1 and 12, not {month}"))]
+    InvalidMonth { month: u8 },
+    #[snafu(display("The micro version is too big to fit inside a 64 bit unsigned int"))]
+    MicroTooBig { source: ParseIntError },
+    #[snafu(display("Invalid modifier"))]
+    InvalidModifier { source: InvalidPrerelease },
+}
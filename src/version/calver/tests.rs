@@ -0,0 +1,176 @@
+use super::{CalVer, InvalidCalVer, YearFormat};
+use crate::range::Ranges;
+
+fn c(s: &str) -> CalVer {
+    s.parse().unwrap()
+}
+
+#[test]
+fn parse_formats() {
+    assert_eq!(c("2024.06.2"), CalVer::new(2024, 6, 2));
+    assert_eq!(c("2024.6.2"), CalVer::new(2024, 6, 2));
+    assert_eq!(c("24.11.0"), CalVer::new(2024, 11, 0));
+    assert_eq!(c("06.01.3"), CalVer::new(2006, 1, 3));
+    assert_eq!(c("2024.06.2-rc.1").modifier.len(), 2);
+}
+
+#[test]
+fn display_is_canonical() {
+    assert_eq!(c("2024.6.2").to_string(), "2024.06.2");
+    assert_eq!(c("24.11.0").to_string(), "2024.11.0");
+    assert_eq!(c("2024.06.2-rc.1").to_string(), "2024.06.2-rc.1");
+    assert_eq!(
+        c("2024.6.2").display_with(YearFormat::Short).to_string(),
+        "24.06.2"
+    );
+    assert_eq!(
+        c("1999.12.0").display_with(YearFormat::Short).to_string(),
+        "1999.12.0"
+    );
+    assert_eq!(
+        CalVer::new(2100, 1, 0)
+            .display_with(YearFormat::Short)
+            .to_string(),
+        "2100.01.0"
+    );
+}
+
+#[test]
+fn early_years_roundtrip() {
+    for year in [0, 5, 99, 500, 999] {
+        let version = CalVer::new(year, 1, 0);
+        assert_eq!(c(&version.to_string()), version, "{version}");
+        let short = version.display_with(YearFormat::Short).to_string();
+        assert_eq!(c(&short), version, "{short}");
+    }
+    assert_eq!(CalVer::new(500, 1, 0).to_string(), "0500.01.0");
+    assert_eq!(
+        CalVer::new(2005, 1, 0)
+            .display_with(YearFormat::Short)
+            .to_string(),
+        "05.01.0"
+    );
+}
+
+#[test]
+fn invalid() {
+    assert!(matches!(
+        "2024.13.0".parse::<CalVer>(),
+        Err(InvalidCalVer::InvalidMonth { month: 13 })
+    ));
+    assert!(matches!(
+        "2024.0.0".parse::<CalVer>(),
+        Err(InvalidCalVer::InvalidMonth { month: 0 })
+    ));
+    assert!(matches!(
+        "2024.06".parse::<CalVer>(),
+        Err(InvalidCalVer::InvalidFormat { .. })
+    ));
+    assert!(matches!(
+        "20245.06.1".parse::<CalVer>(),
+        Err(InvalidCalVer::InvalidYear { .. })
+    ));
+}
+
+#[test]
+fn only_two_or_four_digit_years() {
+    for s in ["5.1.0", "999.1.0", "024.1.0"] {
+        assert!(
+            matches!(s.parse::<CalVer>(), Err(InvalidCalVer::InvalidYear { .. })),
+            "{s:?}"
+        );
+    }
+}
+
+#[test]
+fn from_str_with_format() {
+    assert_eq!(
+        CalVer::from_str_with("2024.06.2", YearFormat::Full).unwrap(),
+        CalVer::new(2024, 6, 2)
+    );
+    assert_eq!(
+        CalVer::from_str_with("24.06.2", YearFormat::Short).unwrap(),
+        CalVer::new(2024, 6, 2)
+    );
+    assert!(matches!(
+        CalVer::from_str_with("24.06.2", YearFormat::Full),
+        Err(InvalidCalVer::InvalidYear { expected: "4", .. })
+    ));
+    assert!(matches!(
+        CalVer::from_str_with("2024.06.2", YearFormat::Short),
+        Err(InvalidCalVer::InvalidYear { expected: "2", .. })
+    ));
+    for s in ["5.1.0", "999.1.0"] {
+        for format in [YearFormat::Full, YearFormat::Short] {
+            assert!(CalVer::from_str_with(s, format).is_err(), "{s:?}");
+        }
+    }
+}
+
+#[test]
+fn ordering_across_years() {
+    let mut versions = [
+        "2025.01.0",
+        "2024.12.3",
+        "24.12.10",
+        "2024.12.3-rc.1",
+        "2023.12.0",
+        "2025.1.0-dev",
+    ]
+    .map(c);
+    versions.sort();
+    assert_eq!(
+        versions.map(|v| v.to_string()),
+        [
+            "2023.12.0",
+            "2024.12.3-rc.1",
+            "2024.12.3",
+            "2024.12.10",
+            "2025.01.0-dev",
+            "2025.01.0",
+        ]
+    );
+}
+
+#[test]
+fn range_membership() {
+    let range = Ranges::<CalVer>::from_str(">=2024.01.0 && <2025.01.0").unwrap();
+    assert!(range.contains(&c("2024.01.0")));
+    assert!(range.contains(&c("24.06.2")));
+    assert!(range.contains(&c("2024.12.99")));
+    assert!(!range.contains(&c("2025.01.0")));
+    assert!(!range.contains(&c("2023.12.31")));
+
+    let range = Ranges::<CalVer>::from_str("==2024.06.2 || >2024.12.0").unwrap();
+    assert!(range.contains(&c("2024.06.2")));
+    assert!(!range.contains(&c("2024.06.3")));
+    assert!(!range.contains(&c("2024.12.0")));
+    assert!(range.contains(&c("2024.12.1")));
+}
+
+#[test]
+fn micro_overflow_carries_to_month_and_year() {
+    let last = CalVer::new(2024, 12, u64::MAX);
+    let range = Ranges::from_exclusive(last.clone());
+    assert!(!range.contains(&last));
+    assert!(range.contains(&CalVer::new(2025, 1, 0)));
+}
+
+#[test]
+fn display_roundtrip() {
+    for s in [
+        "==2024.06.2",
+        ">=2024.01.0 && <=2025.01.3",
+        "<2024.06.0-rc.1",
+    ] {
+        let range = Ranges::<CalVer>::from_str(s).unwrap();
+        assert_eq!(Ranges::from_str(&range.to_string()).unwrap(), range, "{s}");
+    }
+}
+
+#[test]
+fn display_padding() {
+    let version = c("2024.06.2");
+    assert_eq!(format!("{version:>12}"), "   2024.06.2");
+    assert_eq!(format!("{version:-<11}"), "2024.06.2--");
+}
@@ -0,0 +1,247 @@
+//! Parsing at compile time, for [`crate_version!`](crate::crate_version) and
+//! [`assert_version_req!`](crate::assert_version_req)
+//!
+//! Only releases and a small subset of the requirement syntax are supported:
+//! comparators with complete release versions, `*`, `&&`, `,` and `||`.
+//! Anything else is a compile error when evaluated in a const context.
+
+use std::cmp::Ordering;
+
+use super::UInt;
+
+#[cfg(test)]
+mod tests;
+
+/// Embed the version of the crate being compiled as a constant [`Version`](crate::version::Version)
+///
+/// The version is read from `CARGO_PKG_VERSION`. Prereleases and build metadata
+/// cannot be built at compile time, so they are a compile error.
+///
+/// ```
+/// use areq::version::Version;
+///
+/// const SELF: Version = areq::crate_version!();
+/// assert_eq!(SELF.to_string(), env!("CARGO_PKG_VERSION"));
+/// ```
+#[macro_export]
+macro_rules! crate_version {
+    () => {{
+        const VERSION: $crate::version::Version =
+            $crate::version::Version::parse_release_const(env!("CARGO_PKG_VERSION"));
+        VERSION
+    }};
+}
+
+/// Check at compile time if the version of the crate being compiled matches a requirement
+///
+/// The requirement is limited to comparators with complete release versions, `*`, `&&`,
+/// `,` and `||`. The result is a constant, so it can drive `compile_error!` or asserts.
+///
+/// ```
+/// const _: () = assert!(areq::crate_version_str_matches!(">=0.1.0 && <100.0.0"));
+/// const _: () = assert!(!areq::crate_version_str_matches!("<0.0.1 || ==99.0.0"));
+/// ```
+#[macro_export]
+macro_rules! crate_version_str_matches {
+    ($req:expr) => {{
+        const MATCHES: bool =
+            $crate::version::const_parse::release_matches(env!("CARGO_PKG_VERSION"), $req);
+        MATCHES
+    }};
+}
+
+/// Fail the compilation if a release version does not match a requirement
+///
+/// The version defaults to the one of the crate being compiled. Both arguments must be string
+/// literals, or macros expanding to them like `env!`, so they can be part of the error.
+/// The requirement syntax is the one supported by
+/// [`crate_version_str_matches!`](crate::crate_version_str_matches), and prereleases are
+/// a compile error.
+///
+/// ```
+/// areq::assert_version_req!("0.2.5", ">=0.2.0, <0.3.0");
+/// areq::assert_version_req!(env!("CARGO_PKG_VERSION"), "*");
+/// areq::assert_version_req!("<100.0.0");
+/// ```
+///
+/// A version outside the requirement does not compile:
+///
+/// ```compile_fail
+/// areq::assert_version_req!("1.0.0", ">=0.2.0, <0.3.0");
+/// ```
+///
+/// Neither do prereleases:
+///
+/// ```compile_fail
+/// areq::assert_version_req!("0.2.5-rc.1", ">=0.2.0");
+/// ```
+#[macro_export]
+macro_rules! assert_version_req {
+    ($req:expr $(,)?) => {
+        $crate::assert_version_req!(::core::env!("CARGO_PKG_VERSION"), $req);
+    };
+    ($version:expr, $req:expr $(,)?) => {
+        const _: () = ::core::assert!(
+            $crate::version::const_parse::release_matches($version, $req),
+            ::core::concat!("Version `", $version, "` does not satisfy `", $req, "`")
+        );
+    };
+}
+
+/// Parse a release version, panicking on anything else
+pub const fn parse_release(s: &str) -> (UInt, UInt, UInt) {
+    let bytes = s.as_bytes();
+    let (version, idx) = release_at(bytes, 0);
+    if idx != bytes.len() {
+        panic!("Only release versions, with no prerelease or build metadata, are supported");
+    }
+    version
+}
+
+/// Return whether the release `version` matches `req`
+pub const fn release_matches(version: &str, req: &str) -> bool {
+    let version = parse_release(version);
+    let bytes = req.as_bytes();
+    let (matches, idx) = or_at(bytes, 0, version);
+    if skip_whitespace(bytes, idx) != bytes.len() {
+        panic!("Unsupported requirement syntax");
+    }
+    matches
+}
+
+const fn or_at(bytes: &[u8], idx: usize, version: (UInt, UInt, UInt)) -> (bool, usize) {
+    let (mut matches, mut idx) = and_at(bytes, idx, version);
+    loop {
+        let next = skip_whitespace(bytes, idx);
+        if !starts_with(bytes, next, b"||") {
+            return (matches, idx);
+        }
+        let (branch, end) = and_at(bytes, next + 2, version);
+        matches |= branch;
+        idx = end;
+    }
+}
+
+const fn and_at(bytes: &[u8], idx: usize, version: (UInt, UInt, UInt)) -> (bool, usize) {
+    let (mut matches, mut idx) = comparator_at(bytes, idx, version);
+    loop {
+        let next = skip_whitespace(bytes, idx);
+        let next = if starts_with(bytes, next, b"&&") {
+            next + 2
+        } else if starts_with(bytes, next, b",") {
+            next + 1
+        } else {
+            return (matches, idx);
+        };
+        let (term, end) = comparator_at(bytes, next, version);
+        matches &= term;
+        idx = end;
+    }
+}
+
+const fn comparator_at(bytes: &[u8], idx: usize, version: (UInt, UInt, UInt)) -> (bool, usize) {
+    let idx = skip_whitespace(bytes, idx);
+    if starts_with(bytes, idx, b"*") {
+        return (true, idx + 1);
+    }
+    let (accepted, idx): (&[Ordering], usize) = if starts_with(bytes, idx, b">=") {
+        (&[Ordering::Greater, Ordering::Equal], idx + 2)
+    } else if starts_with(bytes, idx, b"<=") {
+        (&[Ordering::Less, Ordering::Equal], idx + 2)
+    } else if starts_with(bytes, idx, b"==") {
+        (&[Ordering::Equal], idx + 2)
+    } else if starts_with(bytes, idx, b"!=") {
+        (&[Ordering::Less, Ordering::Greater], idx + 2)
+    } else if starts_with(bytes, idx, b">") {
+        (&[Ordering::Greater], idx + 1)
+    } else if starts_with(bytes, idx, b"<") {
+        (&[Ordering::Less], idx + 1)
+    } else if starts_with(bytes, idx, b"=") {
+        (&[Ordering::Equal], idx + 1)
+    } else {
+        panic!("Expected a comparison operator");
+    };
+    let (target, idx) = release_at(bytes, skip_whitespace(bytes, idx));
+    let ordering = compare(version, target);
+    let mut matches = false;
+    let mut i = 0;
+    while i < accepted.len() {
+        matches |= accepted[i] as i8 == ordering as i8;
+        i += 1;
+    }
+    (matches, idx)
+}
+
+const fn compare(a: (UInt, UInt, UInt), b: (UInt, UInt, UInt)) -> Ordering {
+    let pairs = [(a.0, b.0), (a.1, b.1), (a.2, b.2)];
+    let mut i = 0;
+    while i < pairs.len() {
+        let (a, b) = pairs[i];
+        if a < b {
+            return Ordering::Less;
+        }
+        if a > b {
+            return Ordering::Greater;
+        }
+        i += 1;
+    }
+    Ordering::Equal
+}
+
+const fn release_at(bytes: &[u8], idx: usize) -> ((UInt, UInt, UInt), usize) {
+    let (major, idx) = number_at(bytes, idx);
+    let (minor, idx) = number_at(bytes, expect_dot(bytes, idx));
+    let (patch, idx) = number_at(bytes, expect_dot(bytes, idx));
+    ((major, minor, patch), idx)
+}
+
+const fn expect_dot(bytes: &[u8], idx: usize) -> usize {
+    if !starts_with(bytes, idx, b".") {
+        panic!("Expected a `.` between the components of the version");
+    }
+    idx + 1
+}
+
+const fn number_at(bytes: &[u8], mut idx: usize) -> (UInt, usize) {
+    let start = idx;
+    let mut value: UInt = 0;
+    while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+        value = match value.checked_mul(10) {
+            Some(value) => value,
+            None => panic!("Version component too big"),
+        };
+        value = match value.checked_add((bytes[idx] - b'0') as UInt) {
+            Some(value) => value,
+            None => panic!("Version component too big"),
+        };
+        idx += 1;
+    }
+    if idx == start {
+        panic!("Expected a version component");
+    }
+    if bytes[start] == b'0' && idx - start > 1 {
+        panic!("Version components must not have leading zeros");
+    }
+    (value, idx)
+}
+
+const fn skip_whitespace(bytes: &[u8], mut idx: usize) -> usize {
+    while idx < bytes.len() && bytes[idx].is_ascii_whitespace() {
+        idx += 1;
+    }
+    idx
+}
+
+const fn starts_with(bytes: &[u8], idx: usize, prefix: &[u8]) -> bool {
+    if idx + prefix.len() > bytes.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < prefix.len() {
+        if bytes[idx + i] != prefix[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
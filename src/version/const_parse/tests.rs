@@ -0,0 +1,72 @@
+use super::{parse_release, release_matches};
+use crate::{
+    range::Ranges,
+    version::{Version, pure::PureVersion},
+};
+
+#[test]
+fn parses_releases() {
+    assert_eq!(parse_release("1.22.333"), (1, 22, 333));
+    assert_eq!(parse_release("18446744073709551615.0.0"), (u64::MAX, 0, 0));
+    const VERSION: Version = Version::parse_release_const("3.2.1");
+    assert_eq!(VERSION, "3.2.1".parse().unwrap());
+}
+
+#[test]
+#[should_panic = "Only release versions"]
+fn rejects_prereleases() {
+    parse_release("1.0.0-beta.1");
+}
+
+#[test]
+#[should_panic = "Only release versions"]
+fn rejects_build_metadata() {
+    parse_release("1.0.0+build");
+}
+
+#[test]
+#[should_panic = "leading zeros"]
+fn rejects_leading_zeros() {
+    parse_release("1.01.0");
+}
+
+#[test]
+#[should_panic = "too big"]
+fn rejects_overflows() {
+    parse_release("18446744073709551616.0.0");
+}
+
+#[test]
+fn agrees_with_ranges() {
+    let reqs = [
+        ">=1.2.0 && <2.0.0",
+        ">1.2.3, <=1.4.0 || ==3.0.0",
+        "!=1.3.0 && *",
+        "=1.3.0 || <1.0.0",
+        "  >= 1.0.0&&<1.3.0  ",
+    ];
+    for req in reqs {
+        let ranges = Ranges::<PureVersion>::from_str(req).unwrap();
+        for version in [
+            "0.9.0", "1.2.0", "1.2.3", "1.2.4", "1.3.0", "1.4.0", "2.0.0", "3.0.0",
+        ] {
+            assert_eq!(
+                release_matches(version, req),
+                ranges.contains(&version.parse().unwrap()),
+                "{version} {req}"
+            );
+        }
+    }
+}
+
+#[test]
+#[should_panic = "Expected a comparison operator"]
+fn rejects_parentheses() {
+    release_matches("1.0.0", ">=1.0.0 && (<2.0.0)");
+}
+
+#[test]
+#[should_panic = "Unsupported requirement syntax"]
+fn rejects_trailing_input() {
+    release_matches("1.0.0", ">=1.0.0 ^2");
+}
@@ -0,0 +1,290 @@
+//! Debian package versions, compared like `dpkg --compare-versions`
+//!
+//! A Debian version is made of an optional epoch (`1:`), the upstream version, and an optional
+//! revision after the last hyphen (`-1ubuntu1`). The upstream version and the revision are
+//! compared by alternating runs of non-digits, compared character by character, and runs of
+//! digits, compared numerically. Letters sort before the other characters, and `~` sorts
+//! before everything, even the end of the string: `1.0~rc1` precedes `1.0`.
+
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    num::ParseIntError,
+    str::FromStr,
+};
+
+use chumsky::{Parser, error::Rich, prelude::*};
+use itertools::{EitherOrBoth, Itertools};
+use snafu::{ResultExt, Snafu};
+
+use super::padded;
+use crate::range::{self, ParserExtra};
+
+#[cfg(test)]
+mod tests;
+
+/// A Debian package version
+///
+/// Equality follows the dpkg ordering, so `1.0` and `1.00` are equal, as are `1.0` and `1.0-0`.
+///
+/// Ranges also use versions immediately after another, that are not valid Debian versions.
+/// They are written with a trailing `^` for each step, like `1.0^` right after `1.0`,
+/// and parsed back from that form.
+#[derive(Debug, Clone)]
+pub struct DebVersion {
+    pub epoch: u64,
+    pub upstream: String,
+    /// The revision, empty if there is none
+    pub revision: String,
+    /// How many steps this version is after the one described by the other fields
+    ///
+    /// Such versions are used by ranges to represent `>v`, and are written with a `^` per step.
+    after: u32,
+}
+
+/// Sorting weight of a character in a non-digit run, `None` being the end of the run
+fn order(c: Option<u8>) -> i32 {
+    match c {
+        None => 0,
+        Some(b'~') => -1,
+        Some(c) if c.is_ascii_alphabetic() => c as i32,
+        Some(c) => c as i32 + 256,
+    }
+}
+
+/// Split a string in runs of non-digits, each followed by the digits after it
+///
+/// Leading zeros are removed from the digits, as they are not significant.
+fn runs(s: &str) -> impl Iterator<Item = (&str, &str)> {
+    let mut rest = s;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let digits_start = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let (non_digits, tail) = rest.split_at(digits_start);
+        let digits_end = tail
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(tail.len());
+        let (digits, tail) = tail.split_at(digits_end);
+        rest = tail;
+        Some((non_digits, digits.trim_start_matches('0')))
+    })
+}
+
+/// Compare two strings with the dpkg algorithm
+fn compare_part(a: &str, b: &str) -> Ordering {
+    runs(a)
+        .zip_longest(runs(b))
+        .map(|pair| {
+            let ((a_non_digits, a_digits), (b_non_digits, b_digits)) = pair.or(("", ""), ("", ""));
+            a_non_digits
+                .bytes()
+                .zip_longest(b_non_digits.bytes())
+                .map(|pair| match pair {
+                    EitherOrBoth::Both(a, b) => order(Some(a)).cmp(&order(Some(b))),
+                    EitherOrBoth::Left(a) => order(Some(a)).cmp(&order(None)),
+                    EitherOrBoth::Right(b) => order(None).cmp(&order(Some(b))),
+                })
+                .find(|o| o.is_ne())
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| {
+                    a_digits
+                        .len()
+                        .cmp(&b_digits.len())
+                        .then_with(|| a_digits.cmp(b_digits))
+                })
+        })
+        .find(|o| o.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Hash a string consistently with [`compare_part`]
+fn hash_part<H: Hasher>(s: &str, state: &mut H) {
+    // Trailing empty runs compare equal to missing ones
+    let runs = runs(s).collect_vec();
+    let significant = runs
+        .iter()
+        .rposition(|&run| run != ("", ""))
+        .map_or(0, |idx| idx + 1);
+    runs[..significant].hash(state);
+}
+
+impl PartialEq for DebVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for DebVersion {}
+
+impl Hash for DebVersion {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.epoch.hash(state);
+        hash_part(&self.upstream, state);
+        hash_part(&self.revision, state);
+        self.after.hash(state);
+    }
+}
+
+impl PartialOrd for DebVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DebVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| compare_part(&self.upstream, &other.upstream))
+            .then_with(|| compare_part(&self.revision, &other.revision))
+            .then_with(|| self.after.cmp(&other.after))
+    }
+}
+
+impl Display for DebVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        padded(f, |f| display_impl(self, f))
+    }
+}
+
+fn display_impl(version: &DebVersion, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    // A colon in the upstream version needs an explicit epoch to be parsed back
+    if version.epoch != 0 || version.upstream.contains(':') {
+        write!(f, "{}:", version.epoch)?;
+    }
+    write!(f, "{}", version.upstream)?;
+    if !version.revision.is_empty() {
+        write!(f, "-{}", version.revision)?;
+    }
+    for _ in 0..version.after {
+        f.write_str("^")?;
+    }
+    Ok(())
+}
+
+impl FromStr for DebVersion {
+    type Err = InvalidDebVersion;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // `^` is not valid in Debian versions, so it can only mark successors
+        let unmarked = s.trim_end_matches('^');
+        let after = u32::try_from(s.len() - unmarked.len())
+            .map_err(|_| TooManySuccessorMarksSnafu.build())?;
+        let s = unmarked;
+
+        let (epoch, rest) = match s.split_once(':') {
+            Some((epoch, rest)) => {
+                if epoch.is_empty() || !epoch.bytes().all(|b| b.is_ascii_digit()) {
+                    return InvalidEpochSnafu { epoch }.fail();
+                }
+                (epoch.parse().context(EpochTooBigSnafu { epoch })?, rest)
+            }
+            None => (0, s),
+        };
+        let (upstream, revision) = match rest.rsplit_once('-') {
+            Some((_, "")) => return EmptyRevisionSnafu.fail(),
+            Some((upstream, revision)) => (upstream, revision),
+            None => (rest, ""),
+        };
+
+        if !upstream.starts_with(|c: char| c.is_ascii_digit()) {
+            return UpstreamNotDigitSnafu { upstream }.fail();
+        }
+        if let Some(c) = upstream
+            .chars()
+            .find(|&c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-' | '~' | ':')))
+        {
+            return InvalidCharacterSnafu {
+                c,
+                part: "upstream version",
+            }
+            .fail();
+        }
+        if let Some(c) = revision
+            .chars()
+            .find(|&c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '~')))
+        {
+            return InvalidCharacterSnafu {
+                c,
+                part: "revision",
+            }
+            .fail();
+        }
+
+        Ok(Self {
+            epoch,
+            upstream: upstream.to_owned(),
+            revision: revision.to_owned(),
+            after,
+        })
+    }
+}
+
+impl range::RangeExtreme for DebVersion {
+    fn next(self) -> Option<Self> {
+        // There is no string immediately after another, as `~` can always be appended to
+        // anything greater, so the successor is marked explicitly
+        Some(Self {
+            after: self.after.checked_add(1)?,
+            ..self
+        })
+    }
+}
+
+impl range::RangeExtremeCanonical for DebVersion {}
+
+impl range::RangeExtremeDisplay for DebVersion {
+    fn has_prev(&self) -> bool {
+        self.after == 1
+    }
+
+    fn display_prev(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.after != 1 {
+            return Err(std::fmt::Error);
+        }
+        display_impl(
+            &Self {
+                after: 0,
+                ..self.clone()
+            },
+            f,
+        )
+    }
+}
+
+impl range::RangeExtremeParseable for DebVersion {
+    fn parser<'a>() -> impl Parser<'a, &'a str, Self, ParserExtra<'a>> + Clone {
+        any()
+            .filter(|c: &char| {
+                c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-' | '~' | ':' | '^')
+            })
+            .repeated()
+            .at_least(1)
+            .to_slice()
+            .try_map(|s: &str, span| s.parse().map_err(|err| Rich::custom(span, err)))
+    }
+}
+
+#[derive(Debug, Clone, Snafu)]
+pub enum InvalidDebVersion {
+    #[snafu(display("Invalid epoch `{epoch}`"))]
+    InvalidEpoch { epoch: String },
+    #[snafu(display("The epoch `{epoch}` does not fit inside a 64 bit unsigned int"))]
+    EpochTooBig {
+        epoch: String,
+        source: ParseIntError,
+    },
+    #[snafu(display("The upstream version `{upstream}` does not start with a digit"))]
+    UpstreamNotDigit { upstream: String },
+    #[snafu(display("The revision is empty"))]
+    EmptyRevision,
+    #[snafu(display("Invalid character `{c}` in the {part}"))]
+    InvalidCharacter { c: char, part: &'static str },
+    #[snafu(display("Too many successor marks `^`"))]
+    TooManySuccessorMarks,
+}
@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+
+use super::{DebVersion, InvalidDebVersion};
+use crate::range::{RangeExtreme, Ranges};
+
+fn d(s: &str) -> DebVersion {
+    s.parse().unwrap()
+}
+
+/// Versions in ascending order, as sorted by `dpkg --compare-versions`
+static SORTED: &[&str] = &[
+    "1.0~~",
+    "1.0~~a",
+    "1.0~",
+    "1.0~rc1",
+    "1.0",
+    "1.0-1~bpo1",
+    "1.0-1",
+    "1.0-1ubuntu1",
+    "1.0-1+b1",
+    "1.0-2",
+    "1.0a",
+    "1.0+dfsg",
+    "1.0.1",
+    "1.2",
+    "1.9",
+    "1.10",
+    "2.30.1-1ubuntu1",
+    "4.18.0-305.10.2.el8",
+    "4.18.0-305.el8",
+    "5",
+    "1:0.1",
+    "1:2.30.1-1ubuntu1",
+    "2:0",
+];
+
+/// Pairs of versions that `dpkg --compare-versions` considers equal
+static EQUAL: &[(&str, &str)] = &[
+    ("1.0", "1.00"),
+    ("1.0", "1.0-0"),
+    ("1.001", "1.1"),
+    ("0:1.0", "1.0"),
+    ("1.0a", "1.0a0"),
+    ("1.0-1", "1.0-01"),
+];
+
+#[test]
+fn ordering() {
+    for (i, a) in SORTED.iter().enumerate() {
+        for (j, b) in SORTED.iter().enumerate() {
+            assert_eq!(d(a).cmp(&d(b)), i.cmp(&j), "comparing {a} and {b}");
+        }
+    }
+}
+
+#[test]
+fn equality() {
+    for (a, b) in EQUAL {
+        assert_eq!(d(a), d(b), "comparing {a} and {b}");
+    }
+    let set: HashSet<_> = ["1.0", "1.00", "1.0-0", "0:1.000-00"]
+        .into_iter()
+        .map(d)
+        .collect();
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn parts() {
+    let v = d("1:2.30.1-1-1ubuntu1");
+    assert_eq!(v.epoch, 1);
+    assert_eq!(v.upstream, "2.30.1-1");
+    assert_eq!(v.revision, "1ubuntu1");
+
+    let v = d("4.18.0");
+    assert_eq!(v.epoch, 0);
+    assert_eq!(v.revision, "");
+}
+
+#[test]
+fn display_round_trip() {
+    for s in SORTED
+        .iter()
+        .copied()
+        .chain(["0:1:2", "1.0-1-2", "3:1.0~rc1+dfsg-2"])
+    {
+        assert_eq!(d(s).to_string(), s);
+    }
+    assert_eq!(format!("{:>6}", d("1.0-1")), " 1.0-1");
+}
+
+#[test]
+fn invalid() {
+    assert!(matches!(
+        "a1.0".parse::<DebVersion>(),
+        Err(InvalidDebVersion::UpstreamNotDigit { .. })
+    ));
+    assert!(matches!(
+        "".parse::<DebVersion>(),
+        Err(InvalidDebVersion::UpstreamNotDigit { .. })
+    ));
+    assert!(matches!(
+        "1.0-".parse::<DebVersion>(),
+        Err(InvalidDebVersion::EmptyRevision)
+    ));
+    assert!(matches!(
+        "x:1.0".parse::<DebVersion>(),
+        Err(InvalidDebVersion::InvalidEpoch { .. })
+    ));
+    assert!(matches!(
+        "99999999999999999999:1.0".parse::<DebVersion>(),
+        Err(InvalidDebVersion::EpochTooBig { .. })
+    ));
+    assert!(matches!(
+        "1.0 beta".parse::<DebVersion>(),
+        Err(InvalidDebVersion::InvalidCharacter { c: ' ', .. })
+    ));
+    assert!(matches!(
+        "1.0^-1".parse::<DebVersion>(),
+        Err(InvalidDebVersion::InvalidCharacter { c: '^', .. })
+    ));
+    assert!(matches!(
+        "1.0-1_2".parse::<DebVersion>(),
+        Err(InvalidDebVersion::InvalidCharacter {
+            c: '_',
+            part: "revision"
+        })
+    ));
+}
+
+#[test]
+fn ranges() {
+    let range = Ranges::<DebVersion>::from_str(">=1:2.30.1-1 && <1:2.31").unwrap();
+    assert!(range.contains(&d("1:2.30.1-1ubuntu1")));
+    assert!(range.contains(&d("1:2.31~rc1")));
+    assert!(!range.contains(&d("1:2.31")));
+    assert!(!range.contains(&d("2.30.1-1")));
+
+    let range = Ranges::<DebVersion>::from_str(">1.0 && <=2.0").unwrap();
+    assert!(range.contains(&d("1.0-1")));
+    assert!(range.contains(&d("1.0a")));
+    assert!(range.contains(&d("2.00")));
+    assert!(!range.contains(&d("1.0")));
+    assert!(!range.contains(&d("1.00-0")));
+    assert!(!range.contains(&d("2.0-1")));
+    assert_eq!(range.to_string(), ">1.0 && <=2.0");
+
+    let range = Ranges::<DebVersion>::from_str("!=1.0").unwrap();
+    assert!(!range.contains(&d("1.0-0")));
+    assert!(range.contains(&d("1.0~")));
+    assert!(range.contains(&d("1.0-1")));
+}
+
+#[test]
+fn successors_read_back() {
+    for (version, successor) in [
+        ("1.0", "1.0^"),
+        ("1:2.30.1-1ubuntu1", "1:2.30.1-1ubuntu1^"),
+        ("1.0^", "1.0^^"),
+    ] {
+        let next = d(version).next().unwrap();
+        assert_eq!(next.to_string(), successor);
+        assert_eq!(d(successor), next);
+        assert!(d(version) < next);
+    }
+}
+
+#[test]
+fn canonical_round_trip() {
+    for (req, canonical) in [
+        (">1.0", ">=1.0^"),
+        ("<=1.0", "<1.0^"),
+        ("==1.0-1", ">=1.0-1,<1.0-1^"),
+        ("!=1:2.0", "<1:2.0|>=1:2.0^"),
+    ] {
+        let range = Ranges::<DebVersion>::from_str(req).unwrap();
+        assert_eq!(range.to_canonical_string(), canonical);
+        assert_eq!(Ranges::from_canonical(canonical).unwrap(), range, "{req}");
+    }
+    assert_eq!(
+        Ranges::<DebVersion>::from_str(">=1.0^").unwrap(),
+        Ranges::from_str(">1.0").unwrap()
+    );
+}
@@ -0,0 +1,354 @@
+//! Versions with an arbitrary number of numeric components
+//!
+//! Some vendors use versions like `10.0.19041.1`, that are not semantic versions.
+//! [`ExtendedVersion`] accepts them, while keeping the prerelease and build metadata
+//! identifiers of semantic versioning.
+
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    num::ParseIntError,
+    str::FromStr,
+};
+
+use chumsky::{Parser, error::Rich, prelude::*};
+use itertools::{EitherOrBoth, Itertools};
+use lazy_regex::regex_captures;
+use snafu::{ResultExt, Snafu};
+
+use super::{
+    Version,
+    build::{BuildMetadata, InvalidBuildMetadata},
+    padded,
+    pure::{
+        PureVersion,
+        prerelease::{InvalidPrerelease, Prerelease},
+    },
+};
+use crate::range::{self, ParserExtra};
+
+#[cfg(test)]
+mod tests;
+
+/// A version with any number of numeric components
+///
+/// Missing components are equivalent to zeros, so `1.2.3` and `1.2.3.0` are equal.
+/// Equality and ordering follow the precedence, so they ignore the build metadata.
+#[derive(Debug, Clone)]
+pub struct ExtendedVersion {
+    /// Numeric components, at least one and at most [`ExtendedVersion::MAX_COMPONENTS`]
+    components: Vec<u64>,
+    pub pre: Vec<Prerelease>,
+    pub build: Vec<BuildMetadata>,
+}
+
+impl ExtendedVersion {
+    /// Maximum number of numeric components
+    ///
+    /// Bounding the components is needed to have a next version: without it, there would be
+    /// infinite versions like `1.2.3.0.0.1` between `1.2.3` and any greater version.
+    pub const MAX_COMPONENTS: usize = 8;
+
+    /// Build a release version from its numeric components
+    ///
+    /// # Panics
+    /// If there are no components, or more than [`ExtendedVersion::MAX_COMPONENTS`]
+    pub fn new(components: impl Into<Vec<u64>>) -> Self {
+        let components = components.into();
+        assert!(
+            (1..=Self::MAX_COMPONENTS).contains(&components.len()),
+            "Extended versions must have between 1 and {} components",
+            Self::MAX_COMPONENTS
+        );
+        Self {
+            components,
+            pre: vec![],
+            build: vec![],
+        }
+    }
+
+    /// Numeric components, at least one and at most [`ExtendedVersion::MAX_COMPONENTS`]
+    pub fn components(&self) -> &[u64] {
+        &self.components
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    /// Components without the trailing zeros, that do not change the precedence
+    fn significant_components(&self) -> &[u64] {
+        let len = self
+            .components
+            .iter()
+            .rposition(|c| *c != 0)
+            .map_or(0, |idx| idx + 1);
+        &self.components[..len]
+    }
+}
+
+impl PartialEq for ExtendedVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ExtendedVersion {}
+
+impl Hash for ExtendedVersion {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.significant_components().hash(state);
+        self.pre.hash(state);
+    }
+}
+
+impl PartialOrd for ExtendedVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ExtendedVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.components
+            .iter()
+            .zip_longest(&other.components)
+            .map(|pair| match pair {
+                EitherOrBoth::Both(a, b) => a.cmp(b),
+                EitherOrBoth::Left(a) => a.cmp(&0),
+                EitherOrBoth::Right(b) => 0.cmp(b),
+            })
+            .find(|ord| ord.is_ne())
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| match (self.is_prerelease(), other.is_prerelease()) {
+                (true, true) => self.pre.cmp(&other.pre),
+                (true, false) => Ordering::Less,
+                (false, true) => Ordering::Greater,
+                (false, false) => Ordering::Equal,
+            })
+    }
+}
+
+impl Display for ExtendedVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        padded(f, |f| {
+            display_impl(&self.components, &self.pre, f)?;
+            if let Some((first, rest)) = self.build.split_first() {
+                write!(f, "+{first}")?;
+                for build in rest {
+                    write!(f, ".{build}")?;
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+fn display_impl(
+    components: &[u64],
+    pre: &[Prerelease],
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result {
+    write!(f, "{}", components.iter().format("."))?;
+    if let Some((first, rest)) = pre.split_first() {
+        write!(f, "-{first}")?;
+        for pre in rest {
+            write!(f, ".{pre}")?;
+        }
+    }
+    Ok(())
+}
+
+impl FromStr for ExtendedVersion {
+    type Err = InvalidExtendedVersion;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((_, components, pre, build)) =
+            regex_captures!(r"^(\d+(?:\.\d+)*)(?:-([^+]+))?(?:\+(.+))?$", s)
+        else {
+            return InvalidFormatSnafu { version: s }.fail();
+        };
+
+        let components = components
+            .split('.')
+            .map(|component| {
+                if component.len() > 1 && component.starts_with('0') {
+                    return LeadingZerosSnafu { component }.fail();
+                }
+                component
+                    .parse()
+                    .context(ComponentTooBigSnafu { component })
+            })
+            .collect::<Result<Vec<u64>, _>>()?;
+        if components.len() > Self::MAX_COMPONENTS {
+            return TooManyComponentsSnafu {
+                components: components.len(),
+            }
+            .fail();
+        }
+        let pre = if pre.is_empty() {
+            vec![]
+        } else {
+            pre.split('.')
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .context(InvalidPrereleaseSnafu)?
+        };
+        let build = if build.is_empty() {
+            vec![]
+        } else {
+            build
+                .split('.')
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .context(InvalidBuildMetadataSnafu)?
+        };
+
+        Ok(Self {
+            components,
+            pre,
+            build,
+        })
+    }
+}
+
+impl From<Version> for ExtendedVersion {
+    fn from(value: Version) -> Self {
+        let Version {
+            pure:
+                PureVersion {
+                    major,
+                    minor,
+                    patch,
+                    pre,
+                },
+            build,
+        } = value;
+        Self {
+            components: vec![major, minor, patch],
+            pre: pre.into_owned(),
+            build,
+        }
+    }
+}
+
+/// Convert into a semantic version
+///
+/// Fails if there are non-zero components after the third, as they cannot be represented.
+/// Missing components are filled with zeros.
+impl TryFrom<ExtendedVersion> for Version {
+    type Error = NotSemantic;
+
+    fn try_from(value: ExtendedVersion) -> Result<Self, Self::Error> {
+        let significant = value.significant_components().len();
+        if significant > 3 {
+            return Err(NotSemantic {
+                components: significant,
+            });
+        }
+        let component = |idx| value.components.get(idx).copied().unwrap_or(0);
+        Ok(Version {
+            pure: PureVersion {
+                major: component(0),
+                minor: component(1),
+                patch: component(2),
+                pre: value.pre.into(),
+            },
+            build: value.build,
+        })
+    }
+}
+
+/// The version has too many components to be a semantic version
+#[derive(Debug, Clone, Snafu)]
+#[snafu(display("Semantic versions have 3 numeric components, not {components}"))]
+pub struct NotSemantic {
+    pub components: usize,
+}
+
+impl range::RangeExtreme for ExtendedVersion {
+    fn minimum() -> Option<Self> {
+        Some(Self {
+            components: vec![0],
+            pre: vec![Prerelease::MIN],
+            build: vec![],
+        })
+    }
+
+    fn next(mut self) -> Option<Self> {
+        self.build.clear();
+        if !self.is_prerelease() {
+            // The smallest greater version increments the last possible component
+            self.components.resize(Self::MAX_COMPONENTS, 0);
+            let carry = self.components.iter().rposition(|c| *c != u64::MAX)?;
+            self.components[carry] += 1;
+            self.components[carry + 1..].fill(0);
+        }
+        self.pre.push(Prerelease::MIN);
+        Some(self)
+    }
+}
+
+impl range::RangeExtremeCanonical for ExtendedVersion {}
+
+impl range::RangeExtremeDisplay for ExtendedVersion {
+    fn has_prev(&self) -> bool {
+        self.pre.last() == Some(&Prerelease::MIN)
+            && (self.pre.len() > 1
+                || (self.components.len() == Self::MAX_COMPONENTS
+                    && self.components[Self::MAX_COMPONENTS - 1] != 0))
+    }
+
+    fn display_prev(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !range::RangeExtremeDisplay::has_prev(self) {
+            return Err(std::fmt::Error);
+        }
+        let pre = &self.pre[..self.pre.len() - 1];
+        if !pre.is_empty() {
+            return display_impl(&self.components, pre, f);
+        }
+        let mut components = self.components.clone();
+        *components.last_mut().unwrap() -= 1;
+        // Remove the padding added by `next`, keeping the look of a semantic version
+        let len = components
+            .iter()
+            .rposition(|c| *c != 0)
+            .map_or(0, |idx| idx + 1)
+            .max(3);
+        display_impl(&components[..len], pre, f)
+    }
+}
+
+impl range::RangeExtremeParseable for ExtendedVersion {
+    fn parser<'a>() -> impl Parser<'a, &'a str, Self, ParserExtra<'a>> + Clone {
+        any()
+            .filter(|c: &char| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'))
+            .repeated()
+            .at_least(1)
+            .to_slice()
+            .try_map(|s: &str, span| s.parse().map_err(|err| Rich::custom(span, err)))
+    }
+}
+
+#[derive(Debug, Clone, Snafu)]
+pub enum InvalidExtendedVersion {
+    #[snafu(display("Invalid version `{version}`"))]
+    InvalidFormat { version: String },
+    #[snafu(display("Numeric components must not start with zero: `{component}`"))]
+    LeadingZeros { component: String },
+    #[snafu(display("The component `{component}` does not fit inside a 64 bit unsigned int"))]
+    ComponentTooBig {
+        component: String,
+        source: ParseIntError,
+    },
+    #[snafu(display(
+        "Too many numeric components: {components}, the maximum is {}",
+        ExtendedVersion::MAX_COMPONENTS
+    ))]
+    TooManyComponents { components: usize },
+    #[snafu(display("Invalid prerelease"))]
+    InvalidPrerelease { source: InvalidPrerelease },
+    #[snafu(display("Invalid build metadata"))]
+    InvalidBuildMetadata { source: InvalidBuildMetadata },
+}
@@ -0,0 +1,101 @@
+use std::cmp::Ordering;
+
+use super::{ExtendedVersion, InvalidExtendedVersion};
+use crate::{range::Ranges, version::Version};
+
+fn e(s: &str) -> ExtendedVersion {
+    s.parse().unwrap()
+}
+
+#[test]
+fn parse_and_display() {
+    for s in ["10.0.19041.1", "1.2.3.4-rc.1+build.5", "7", "1.2"] {
+        assert_eq!(e(s).to_string(), s);
+    }
+    assert_eq!(e("1.2.3.4").components(), [1, 2, 3, 4]);
+}
+
+#[test]
+fn missing_components_are_zero() {
+    assert_eq!(e("1.2.3").cmp(&e("1.2.3.0")), Ordering::Equal);
+    assert_eq!(e("1.2.3"), e("1.2.3.0"));
+    assert_eq!(e("1.2.3.1").cmp(&e("1.2.3")), Ordering::Greater);
+    assert_eq!(e("1.2").cmp(&e("1.2.0.0.1")), Ordering::Less);
+    assert_eq!(e("1.2.3.0-rc").cmp(&e("1.2.3")), Ordering::Less);
+}
+
+#[test]
+fn equal_versions_hash_equally() {
+    use std::collections::HashSet;
+
+    let set: HashSet<_> = [e("1.2.3"), e("1.2.3.0"), e("1.2.3.0.0+build")].into();
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn invalid() {
+    assert!(matches!(
+        "1.02.3".parse::<ExtendedVersion>(),
+        Err(InvalidExtendedVersion::LeadingZeros { .. })
+    ));
+    assert!(matches!(
+        "1.2.3.4.5.6.7.8.9".parse::<ExtendedVersion>(),
+        Err(InvalidExtendedVersion::TooManyComponents { components: 9 })
+    ));
+    assert!(matches!(
+        "1..2".parse::<ExtendedVersion>(),
+        Err(InvalidExtendedVersion::InvalidFormat { .. })
+    ));
+}
+
+#[test]
+fn conversion_to_version() {
+    let version: Version = e("1.2.3.0-rc.1+b").try_into().unwrap();
+    assert_eq!(version, "1.2.3-rc.1+b".parse().unwrap());
+
+    let version: Version = e("1.2").try_into().unwrap();
+    assert_eq!(version, "1.2.0".parse().unwrap());
+
+    let err = Version::try_from(e("1.2.3.4")).unwrap_err();
+    assert_eq!(err.components, 4);
+
+    let back: ExtendedVersion = "1.2.3-rc.1".parse::<Version>().unwrap().into();
+    assert_eq!(back, e("1.2.3-rc.1"));
+}
+
+#[test]
+fn ranges() {
+    let range = Ranges::<ExtendedVersion>::from_str(">=10.0.19041 && <10.0.22000").unwrap();
+    assert!(range.contains(&e("10.0.19041.1")));
+    assert!(range.contains(&e("10.0.19041")));
+    assert!(!range.contains(&e("10.0.22000.0")));
+
+    let range = Ranges::<ExtendedVersion>::from_str("==1.2.3").unwrap();
+    assert!(range.contains(&e("1.2.3.0")));
+    assert!(!range.contains(&e("1.2.3.0.0.0.0.1")));
+    assert!(!range.contains(&e("1.2.3.0.0.0.0.1-0")));
+
+    let range = Ranges::<ExtendedVersion>::from_str(">1.2.3.4").unwrap();
+    assert!(!range.contains(&e("1.2.3.4")));
+    assert!(range.contains(&e("1.2.3.4.0.0.0.1")));
+}
+
+#[test]
+fn display_roundtrip() {
+    for s in ["==1.2.3", "<=1.2.3.4 || >=2", ">1.0.0.1 && !=1.5"] {
+        let range = Ranges::<ExtendedVersion>::from_str(s).unwrap();
+        let displayed = range.to_string();
+        assert_eq!(
+            Ranges::from_str(&displayed).unwrap(),
+            range,
+            "{s} -> {displayed}"
+        );
+    }
+}
+
+#[test]
+fn display_padding() {
+    let version = e("10.0.19041.1");
+    assert_eq!(format!("{version:>14}"), "  10.0.19041.1");
+    assert_eq!(format!("{version:^14}"), " 10.0.19041.1 ");
+}
@@ -0,0 +1,174 @@
+//! Versions described by `git describe --tags`
+//!
+//! The output of `git describe` is the nearest tag, optionally followed by the number of
+//! commits since the tag and the abbreviated commit hash, like `v1.4.2-7-g3a5b9c1`,
+//! and by `-dirty` if the working tree has local changes.
+
+use std::{num::ParseIntError, str::FromStr};
+
+use lazy_regex::regex_captures;
+use snafu::{ResultExt, Snafu};
+
+use super::{
+    InvalidVersion, Version,
+    pure::{PrereleasePolicy, PureVersion},
+};
+use crate::range::Ranges;
+
+#[cfg(test)]
+mod tests;
+
+/// A version described by `git describe`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GitDescribed {
+    /// The version of the tag, without the `v` prefix
+    pub version: Version,
+    /// Number of commits since the tag
+    pub distance: u64,
+    /// Abbreviated hash of the described commit, without the `g` prefix
+    ///
+    /// This is `None` if the commit is exactly at the tag and `--long` was not used.
+    pub hash: Option<String>,
+    /// If the working tree had local changes
+    pub dirty: bool,
+}
+
+impl GitDescribed {
+    /// Convert into a version, encoding the describe suffix as build metadata
+    ///
+    /// `v1.4.2-7-g3a5b9c1-dirty` becomes `1.4.2+7.g3a5b9c1.dirty`, so it has the same
+    /// precedence as its tag.
+    pub fn to_version(&self) -> Version {
+        let mut version = self.version.clone();
+        if let Some(hash) = &self.hash {
+            version.build.push(
+                self.distance
+                    .to_string()
+                    .parse()
+                    .expect("Numbers are valid build metadata"),
+            );
+            version.build.push(
+                format!("g{hash}")
+                    .parse()
+                    .expect("Hashes are valid build metadata"),
+            );
+        }
+        if self.dirty {
+            version
+                .build
+                .push("dirty".parse().expect("`dirty` is valid build metadata"));
+        }
+        version
+    }
+}
+
+impl FromStr for GitDescribed {
+    type Err = GitDescribeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix('v').unwrap_or(s);
+        let (s, dirty) = match s.strip_suffix("-dirty") {
+            Some(s) => (s, true),
+            None => (s, false),
+        };
+
+        let (tag, distance, hash) = match regex_captures!(r"^(.+)-(\d+)-g([0-9a-f]+)$", s) {
+            Some((_, tag, distance, hash)) => (
+                tag,
+                distance.parse().context(InvalidDistanceSnafu)?,
+                Some(hash.to_owned()),
+            ),
+            None => (s, 0, None),
+        };
+
+        Ok(Self {
+            version: tag.parse().context(InvalidVersionSnafu)?,
+            distance,
+            hash,
+            dirty,
+        })
+    }
+}
+
+impl From<GitDescribed> for Version {
+    fn from(value: GitDescribed) -> Self {
+        value.to_version()
+    }
+}
+
+/// How tags are turned into versions, see [`Ranges::latest_matching`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagOptions {
+    /// Strip a leading `v` or `V`, like in `v1.2.3`
+    pub strip_v_prefix: bool,
+    /// A prefix to strip before the `v`, like `mycrate-` for `mycrate-v1.2.3`
+    ///
+    /// Tags without the prefix are skipped.
+    pub prefix: Option<String>,
+    /// How prereleases are matched against the requirement
+    pub policy: PrereleasePolicy,
+}
+
+impl Default for TagOptions {
+    fn default() -> Self {
+        Self {
+            strip_v_prefix: true,
+            prefix: None,
+            policy: PrereleasePolicy::default(),
+        }
+    }
+}
+
+impl TagOptions {
+    /// Parse a tag, returning `None` if it is not a version
+    fn parse(&self, tag: &str) -> Option<Version> {
+        let mut tag = tag.trim();
+        if let Some(prefix) = &self.prefix {
+            tag = tag.strip_prefix(prefix.as_str())?;
+        }
+        if self.strip_v_prefix {
+            tag = tag.strip_prefix(['v', 'V']).unwrap_or(tag);
+        }
+        tag.parse().ok()
+    }
+}
+
+impl Ranges<PureVersion> {
+    /// Find the greatest tag matching the requirement, like in the output of `git tag`
+    ///
+    /// Returns the original tag together with its version. Tags that are not
+    /// versions are skipped. Among tags with the same precedence the first is returned.
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::git::TagOptions};
+    /// let tags = ["nightly", "v1.4.0", "v1.5.2", "docs-v2", "v2.0.0"];
+    /// let req = Ranges::from_str(">=1.4.0 && <2.0.0").unwrap();
+    /// let (tag, _) = req.latest_matching(tags, &TagOptions::default()).unwrap();
+    /// assert_eq!(tag, "v1.5.2");
+    /// ```
+    pub fn latest_matching<'a>(
+        &self,
+        tags: impl IntoIterator<Item = &'a str>,
+        opts: &TagOptions,
+    ) -> Option<(&'a str, Version)> {
+        tags.into_iter()
+            .filter_map(|tag| Some((tag, opts.parse(tag)?)))
+            .filter(|(_, version)| self.contains_with_policy(version, opts.policy))
+            .reduce(|best, candidate| {
+                if candidate.1.pure > best.1.pure {
+                    candidate
+                } else {
+                    best
+                }
+            })
+    }
+}
+
+#[derive(Debug, Clone, Snafu)]
+pub enum GitDescribeError {
+    #[snafu(display("The tag is not a valid semantic version"))]
+    InvalidVersion { source: InvalidVersion },
+    #[snafu(display("The distance from the tag is too big"))]
+    InvalidDistance { source: ParseIntError },
+}
@@ -0,0 +1,146 @@
+use super::{GitDescribeError, GitDescribed, TagOptions};
+use crate::version::Version;
+use crate::version::pure::PrereleasePolicy;
+
+fn v(s: &str) -> Version {
+    s.parse().unwrap()
+}
+
+#[test]
+fn exactly_tagged() {
+    let described = Version::from_git_describe("v1.4.2").unwrap();
+
+    assert_eq!(described.version, v("1.4.2"));
+    assert_eq!(described.distance, 0);
+    assert_eq!(described.hash, None);
+    assert!(!described.dirty);
+    assert_eq!(described.to_version(), v("1.4.2"));
+}
+
+#[test]
+fn long_format() {
+    let described = Version::from_git_describe("v1.4.2-0-g3a5b9c1").unwrap();
+
+    assert_eq!(described.distance, 0);
+    assert_eq!(described.hash.as_deref(), Some("3a5b9c1"));
+    assert_eq!(described.to_version(), v("1.4.2+0.g3a5b9c1"));
+}
+
+#[test]
+fn suffixed() {
+    let described = Version::from_git_describe("v1.4.2-7-g3a5b9c1").unwrap();
+
+    assert_eq!(described.version, v("1.4.2"));
+    assert_eq!(described.distance, 7);
+    assert_eq!(described.hash.as_deref(), Some("3a5b9c1"));
+    assert!(!described.dirty);
+    assert_eq!(described.to_version(), v("1.4.2+7.g3a5b9c1"));
+    assert_eq!(described.to_version().pure, described.version.pure);
+}
+
+#[test]
+fn prerelease_tag() {
+    let described = Version::from_git_describe("v1.4.2-rc.1-7-gabc1234").unwrap();
+
+    assert_eq!(described.version, v("1.4.2-rc.1"));
+    assert_eq!(described.distance, 7);
+    assert_eq!(described.to_version(), v("1.4.2-rc.1+7.gabc1234"));
+
+    let described = Version::from_git_describe("1.4.2-rc.1").unwrap();
+    assert_eq!(described.version, v("1.4.2-rc.1"));
+    assert_eq!(described.hash, None);
+}
+
+#[test]
+fn dirty() {
+    let described = Version::from_git_describe("v1.4.2-7-g3a5b9c1-dirty").unwrap();
+    assert!(described.dirty);
+    assert_eq!(described.to_version(), v("1.4.2+7.g3a5b9c1.dirty"));
+
+    let described = Version::from_git_describe("v1.4.2-dirty").unwrap();
+    assert_eq!(described.version, v("1.4.2"));
+    assert_eq!(described.hash, None);
+    assert_eq!(described.to_version(), v("1.4.2+dirty"));
+}
+
+#[test]
+fn invalid() {
+    assert!(matches!(
+        "release-7-g3a5b9c1".parse::<GitDescribed>(),
+        Err(GitDescribeError::InvalidVersion { .. })
+    ));
+    assert!(matches!(
+        "v1.0.0-99999999999999999999-gabc".parse::<GitDescribed>(),
+        Err(GitDescribeError::InvalidDistance { .. })
+    ));
+}
+
+const TAGS: [&str; 14] = [
+    "nightly",
+    "v0.9.0",
+    "v1.4.0",
+    "docs-v2",
+    "1.4.3",
+    "v1.5.0-rc.1",
+    "V1.4.9",
+    "mycrate-v1.6.0",
+    "v1.4.10-beta",
+    "release-2023",
+    "v2.0.0",
+    "v1.04.11",
+    "mycrate-v1.4.7",
+    "",
+];
+
+#[test]
+fn latest_matching_skips_non_versions() {
+    let req = crate::range::Ranges::from_str(">=1.4.0 && <1.5.0").unwrap();
+    let (tag, version) = req.latest_matching(TAGS, &TagOptions::default()).unwrap();
+    assert_eq!(tag, "V1.4.9");
+    assert_eq!(version, v("1.4.9"));
+}
+
+#[test]
+fn latest_matching_prereleases() {
+    let req = crate::range::Ranges::from_str(">=1.4.0 && <2.0.0").unwrap();
+    let (tag, _) = req.latest_matching(TAGS, &TagOptions::default()).unwrap();
+    assert_eq!(tag, "V1.4.9");
+
+    let opts = TagOptions {
+        policy: PrereleasePolicy::Include,
+        ..Default::default()
+    };
+    let (tag, _) = req.latest_matching(TAGS, &opts).unwrap();
+    assert_eq!(tag, "v1.5.0-rc.1");
+}
+
+#[test]
+fn latest_matching_with_prefix() {
+    let req = crate::range::Ranges::from_str(">=1.0.0").unwrap();
+    let opts = TagOptions {
+        prefix: Some("mycrate-".to_owned()),
+        ..Default::default()
+    };
+    let (tag, version) = req.latest_matching(TAGS, &opts).unwrap();
+    assert_eq!(tag, "mycrate-v1.6.0");
+    assert_eq!(version, v("1.6.0"));
+
+    let opts = TagOptions {
+        strip_v_prefix: false,
+        ..Default::default()
+    };
+    let (tag, _) = req.latest_matching(TAGS, &opts).unwrap();
+    assert_eq!(tag, "1.4.3");
+}
+
+#[test]
+fn latest_matching_keeps_the_first_of_equal_versions() {
+    let req = crate::range::Ranges::from_str(">=1.0.0").unwrap();
+    let tags = ["v1.2.0", "1.2.0", "v1.2.0+build"];
+    let (tag, _) = req.latest_matching(tags, &TagOptions::default()).unwrap();
+    assert_eq!(tag, "v1.2.0");
+    assert_eq!(
+        req.latest_matching(["nightly", "v0.1.0"], &TagOptions::default()),
+        None
+    );
+}
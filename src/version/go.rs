@@ -0,0 +1,102 @@
+//! Versions of Go modules
+//!
+//! Go modules use semantic versions prefixed by `v`, with a few twists:
+//! major versions above 1 without a `go.mod` are marked with the `+incompatible` build metadata,
+//! and untagged commits are referenced by pseudo-versions like `v0.0.0-20210101000000-abcdef123456`.
+
+use std::{fmt::Display, str::FromStr};
+
+use lazy_regex::regex_captures;
+use snafu::{ResultExt, Snafu};
+
+use super::{InvalidVersion, Version};
+
+#[cfg(test)]
+mod tests;
+
+/// A version of a Go module
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GoVersion {
+    /// The semantic version, without the `v` prefix and the `+incompatible` tag
+    pub version: Version,
+    /// If the version is marked as `+incompatible`
+    pub incompatible: bool,
+    /// The commit referenced by the version, if it is a pseudo-version
+    pub pseudo: Option<PseudoVersion>,
+}
+
+/// The commit referenced by a pseudo-version
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PseudoVersion {
+    /// UTC commit time, in the `yyyymmddhhmmss` format
+    pub timestamp: String,
+    /// Prefix of the commit hash
+    pub revision: String,
+}
+
+impl Display for GoVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "v{}", self.version)?;
+        if self.incompatible {
+            write!(f, "+incompatible")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GoVersion {
+    type Err = InvalidGoVersion;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(s) = s.strip_prefix('v') else {
+            return Err(InvalidGoVersion::MissingPrefix);
+        };
+        let (s, incompatible) = match s.strip_suffix("+incompatible") {
+            Some(s) => (s, true),
+            None => (s, false),
+        };
+
+        let version: Version = s.parse().context(InvalidVersionSnafu)?;
+        if !version.build.is_empty() {
+            return Err(InvalidGoVersion::BuildMetadata);
+        }
+        if incompatible && version.major < 2 {
+            return Err(InvalidGoVersion::IncompatibleMajor {
+                major: version.major,
+            });
+        }
+
+        let pseudo = version.pre.last().and_then(|last| {
+            let last = last.to_string();
+            let (_, timestamp, revision) = regex_captures!(r"^(\d{14})-([0-9a-f]{12})$", &last)?;
+            Some(PseudoVersion {
+                timestamp: timestamp.to_owned(),
+                revision: revision.to_owned(),
+            })
+        });
+
+        Ok(Self {
+            version,
+            incompatible,
+            pseudo,
+        })
+    }
+}
+
+impl From<GoVersion> for Version {
+    fn from(value: GoVersion) -> Self {
+        value.version
+    }
+}
+
+#[derive(Debug, Clone, Snafu)]
+pub enum InvalidGoVersion {
+    #[snafu(display("Go versions must start with `v`"))]
+    MissingPrefix,
+    #[snafu(display("Invalid semantic version"))]
+    InvalidVersion { source: InvalidVersion },
+    #[snafu(display("Go versions cannot have build metadata other than `+incompatible`"))]
+    BuildMetadata,
+    #[snafu(display("Only major versions 2 and above can be `+incompatible`, not {major}"))]
+    IncompatibleMajor { major: u64 },
+}
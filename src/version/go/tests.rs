@@ -0,0 +1,72 @@
+use super::{GoVersion, InvalidGoVersion, PseudoVersion};
+use crate::version::Version;
+
+#[test]
+fn tagged() {
+    let version: GoVersion = "v1.2.3".parse().unwrap();
+
+    assert_eq!(version.version, "1.2.3".parse::<Version>().unwrap());
+    assert!(!version.incompatible);
+    assert_eq!(version.pseudo, None);
+    assert_eq!(version.to_string(), "v1.2.3");
+}
+
+#[test]
+fn incompatible() {
+    let version: GoVersion = "v2.0.0+incompatible".parse().unwrap();
+
+    assert_eq!(version.version, "2.0.0".parse::<Version>().unwrap());
+    assert!(version.incompatible);
+    assert_eq!(version.to_string(), "v2.0.0+incompatible");
+
+    assert!(matches!(
+        "v1.0.0+incompatible".parse::<GoVersion>(),
+        Err(InvalidGoVersion::IncompatibleMajor { major: 1 })
+    ));
+}
+
+#[test]
+fn pseudo_version() {
+    for (s, base) in [
+        (
+            "v0.0.0-20210101000000-abcdef123456",
+            "0.0.0-20210101000000-abcdef123456",
+        ),
+        (
+            "v1.2.4-0.20210101000000-abcdef123456",
+            "1.2.4-0.20210101000000-abcdef123456",
+        ),
+        (
+            "v1.2.3-pre.0.20210101000000-abcdef123456",
+            "1.2.3-pre.0.20210101000000-abcdef123456",
+        ),
+    ] {
+        let version: GoVersion = s.parse().unwrap();
+
+        assert_eq!(version.version, base.parse::<Version>().unwrap());
+        assert_eq!(
+            version.pseudo,
+            Some(PseudoVersion {
+                timestamp: "20210101000000".to_owned(),
+                revision: "abcdef123456".to_owned(),
+            })
+        );
+        assert_eq!(version.to_string(), s);
+    }
+}
+
+#[test]
+fn invalid() {
+    assert!(matches!(
+        "1.2.3".parse::<GoVersion>(),
+        Err(InvalidGoVersion::MissingPrefix)
+    ));
+    assert!(matches!(
+        "v1.2.3+build".parse::<GoVersion>(),
+        Err(InvalidGoVersion::BuildMetadata)
+    ));
+    assert!(matches!(
+        "v1.2".parse::<GoVersion>(),
+        Err(InvalidGoVersion::InvalidVersion { .. })
+    ));
+}
@@ -0,0 +1,129 @@
+//! Ordering of versions following the Maven rules for qualifiers
+//!
+//! Semantic versions compare prerelease identifiers in ASCII order, so `1.0.0-SNAPSHOT`
+//! precedes `1.0.0-alpha`. Maven instead orders the well-known qualifiers by maturity,
+//! ignoring case: `alpha` (or `a`) < `beta` (or `b`) < `milestone` (or `m`) < `rc` (or `cr`)
+//! < `snapshot` < the release < `sp`. The qualifiers `final`, `ga` and `release` are
+//! equal to the release. Other qualifiers follow `sp` in case-insensitive lexical order,
+//! and numbers follow all the qualifiers.
+//!
+//! The qualifiers are compared one by one, and a version with fewer qualifiers is compared
+//! as if the missing ones were the release: `1.0-alpha.snapshot` < `1.0-alpha` < `1.0-alpha.sp`.
+//! So, unlike with semantic versioning, some "prereleases" like `1.0-sp` follow their release.
+//!
+//! Only the subset of Maven versions that are also semantic versions is supported,
+//! with the minor and patch versions allowed to be missing. Qualifiers are separated by dots,
+//! and numeric qualifiers are never equal to the release, not even `0`.
+
+use std::cmp::Ordering;
+
+use itertools::{EitherOrBoth, Itertools};
+
+use super::{
+    InvalidVersion, ParseOptions, Version,
+    pure::{PureVersion, prerelease::Prerelease},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Parse a Maven version, where the minor and patch versions can be omitted, like `1.0-alpha`
+pub fn parse(s: &str) -> Result<Version, InvalidVersion> {
+    Version::parse_with_options(
+        s,
+        &ParseOptions {
+            allow_missing_components: true,
+            ..Default::default()
+        },
+    )
+}
+
+/// Compare two versions following the Maven qualifier ordering
+///
+/// ```
+/// # use areq::version::maven;
+/// use std::cmp::Ordering;
+///
+/// let snapshot = maven::parse("1.0-SNAPSHOT").unwrap();
+/// let alpha = maven::parse("1.0-alpha").unwrap();
+/// assert_eq!(maven::compare(&alpha, &snapshot), Ordering::Less);
+/// // While semantic versioning puts uppercase identifiers first
+/// assert!(snapshot.pure < alpha.pure);
+/// ```
+pub fn compare(a: &PureVersion, b: &PureVersion) -> Ordering {
+    (a.major, a.minor, a.patch)
+        .cmp(&(b.major, b.minor, b.patch))
+        .then_with(|| {
+            a.pre
+                .iter()
+                .map(Qualifier::of)
+                .zip_longest(b.pre.iter().map(Qualifier::of))
+                .map(|pair| match pair {
+                    EitherOrBoth::Both(a, b) => a.cmp(&b),
+                    EitherOrBoth::Left(a) => a.cmp(&Qualifier::Release),
+                    EitherOrBoth::Right(b) => Qualifier::Release.cmp(&b),
+                })
+                .find(|ord| ord.is_ne())
+                .unwrap_or(Ordering::Equal)
+        })
+}
+
+/// A version ordered with [`compare`]
+///
+/// Versions differing only in the case of their qualifiers are equal, and so are
+/// versions differing only by qualifiers equal to the release, like `1.0-final` and `1.0`.
+#[derive(Debug, Clone)]
+pub struct MavenOrdered(pub PureVersion);
+
+impl PartialEq for MavenOrdered {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MavenOrdered {}
+
+impl PartialOrd for MavenOrdered {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MavenOrdered {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare(&self.0, &other.0)
+    }
+}
+
+/// A prerelease identifier, in Maven order
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Qualifier<'a> {
+    Alpha,
+    Beta,
+    Milestone,
+    ReleaseCandidate,
+    Snapshot,
+    /// The release itself, standing for missing qualifiers too
+    Release,
+    ServicePack,
+    Other(String),
+    Number(&'a Prerelease),
+}
+
+impl<'a> Qualifier<'a> {
+    fn of(id: &'a Prerelease) -> Self {
+        let Prerelease::Alpha(alpha) = id else {
+            return Qualifier::Number(id);
+        };
+        match alpha.as_str().to_ascii_lowercase().as_str() {
+            "alpha" | "a" => Qualifier::Alpha,
+            "beta" | "b" => Qualifier::Beta,
+            "milestone" | "m" => Qualifier::Milestone,
+            "rc" | "cr" => Qualifier::ReleaseCandidate,
+            "snapshot" => Qualifier::Snapshot,
+            "final" | "ga" | "release" => Qualifier::Release,
+            "sp" => Qualifier::ServicePack,
+            other => Qualifier::Other(other.to_owned()),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::cmp::Ordering;
+
+use super::{MavenOrdered, compare, parse};
+
+fn m(s: &str) -> MavenOrdered {
+    MavenOrdered(parse(s).unwrap().pure)
+}
+
+#[test]
+fn documented_ordering() {
+    let ordered = [
+        "1.0-alpha",
+        "1.0-alpha.2",
+        "1.0-beta",
+        "1.0-milestone",
+        "1.0-rc",
+        "1.0-SNAPSHOT",
+        "1.0",
+        "1.0.1-alpha",
+        "1.1",
+    ];
+    for (a, b) in ordered.iter().zip(&ordered[1..]) {
+        assert!(m(a) < m(b), "{a} < {b}");
+    }
+    let mut shuffled = ordered.map(m);
+    shuffled.reverse();
+    shuffled.sort();
+    assert_eq!(shuffled, ordered.map(m));
+}
+
+#[test]
+fn aliases_and_case() {
+    assert_eq!(m("1.0-a"), m("1.0-alpha"));
+    assert_eq!(m("1.0-B"), m("1.0-beta"));
+    assert_eq!(m("1.0-m"), m("1.0-Milestone"));
+    assert_eq!(m("1.0-cr"), m("1.0-RC"));
+    assert_eq!(m("1.0-snapshot"), m("1.0-SNAPSHOT"));
+}
+
+#[test]
+fn release_aliases() {
+    for alias in ["1.0-final", "1.0-GA", "1.0-release", "1.0.0-final.ga"] {
+        assert_eq!(m(alias), m("1.0"), "{alias}");
+    }
+    assert_eq!(m("1.0-alpha.final"), m("1.0-alpha"));
+    assert!(m("1.0-SNAPSHOT") < m("1.0-final"));
+    assert!(m("1.0-final") < m("1.0-sp"));
+}
+
+#[test]
+fn qualifiers_after_the_release() {
+    let ordered = [
+        "1.0-rc",
+        "1.0",
+        "1.0-sp",
+        "1.0-SP.1",
+        "1.0-preview",
+        "1.0-zeta",
+        "1.0-1",
+        "1.0.1-alpha",
+    ];
+    for (a, b) in ordered.iter().zip(&ordered[1..]) {
+        assert!(m(a) < m(b), "{a} < {b}");
+    }
+    assert!(m("1.0-alpha") < m("1.0-alpha.1"));
+    assert!(m("1.0-alpha") < m("1.0-alpha.sp"));
+    assert!(m("1.0-alpha.sp") < m("1.0-alpha.1"));
+    assert!(m("1.0-alpha.snapshot") < m("1.0-alpha"));
+}
+
+#[test]
+fn unknown_qualifiers_and_numbers() {
+    assert!(m("1.0-sp") < m("1.0-preview"));
+    assert!(m("1.0-Preview") < m("1.0-zeta"));
+    assert!(m("1.0-zeta") < m("1.0-1"));
+    assert!(m("1.0-2") < m("1.0-10"));
+    assert_eq!(
+        compare(&parse("1.0.0").unwrap(), &parse("1.0").unwrap()),
+        Ordering::Equal
+    );
+}
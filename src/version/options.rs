@@ -0,0 +1,134 @@
+use std::borrow::Cow;
+
+use itertools::Itertools;
+
+use super::pure::{InvalidPureVersion, NumericPart};
+
+/// Options for parsing versions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Maximum number of digits of the major, minor and patch versions
+    ///
+    /// The check happens before any other parsing, so it can be used to reject
+    /// absurdly long inputs cheaply. `None` means unbounded.
+    pub max_numeric_digits: Option<usize>,
+    /// Accept leading zeros in the major, minor and patch versions, like `01.02.03`
+    ///
+    /// The zeros are removed before parsing. Numeric prereleases are still strict.
+    pub allow_leading_zeros: bool,
+    /// Accept versions missing the patch, or both the minor and the patch, like `1.2` or `1`
+    ///
+    /// The missing components are taken as zero, so `1.2` is parsed as `1.2.0`.
+    pub allow_missing_components: bool,
+    /// Accept `_` and `~` as separators, as in `1.2.3_rc1`, for inputs from environment variables
+    ///
+    /// If the first character after the major, minor and patch versions is `_` or `~`, it
+    /// is replaced with `-`. Every other `_` or `~` is replaced with `.`, so `1.2.3_rc_1`
+    /// is parsed as `1.2.3-rc.1`. No other substitution happens.
+    pub allow_alternate_separators: bool,
+    /// Maximum number of digits of a numeric prerelease identifier
+    ///
+    /// Numeric prerelease identifiers are arbitrary precision integers, so without a limit
+    /// untrusted inputs could allocate, and make every comparison go through, huge numbers.
+    ///
+    /// Only [`Version::parse_with_options`](super::Version::parse_with_options) and
+    /// [`PureVersion::parse_with_options`](super::pure::PureVersion::parse_with_options) follow
+    /// this option. [`FromStr`](std::str::FromStr), the [`chumsky`] parsers used by
+    /// [`Ranges`](crate::range::Ranges) and the serde implementations always use the
+    /// default limit. So a version accepted with a raised limit round-trips through
+    /// [`Display`](std::fmt::Display) only if parsed again with the same options, and
+    /// cannot be deserialized.
+    pub max_prerelease_digits: usize,
+}
+
+impl ParseOptions {
+    /// The strict options, used by [`FromStr`](std::str::FromStr)
+    pub const DEFAULT: Self = Self {
+        max_numeric_digits: None,
+        allow_leading_zeros: false,
+        allow_missing_components: false,
+        allow_alternate_separators: false,
+        max_prerelease_digits: 64,
+    };
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl ParseOptions {
+    /// Check the raw input against the limits, and normalize it for the strict parser
+    pub(super) fn preprocess<'s>(&self, s: &'s str) -> Result<Cow<'s, str>, InvalidPureVersion> {
+        if self.allow_alternate_separators && s.contains(['_', '~']) {
+            let replaced = replace_separators(s);
+            let options = Self {
+                allow_alternate_separators: false,
+                ..self.clone()
+            };
+            return Ok(Cow::Owned(options.preprocess(&replaced)?.into_owned()));
+        }
+
+        let core_len = s.find(['-', '+']).unwrap_or(s.len());
+        let (core, rest) = s.split_at(core_len);
+
+        if let Some(limit) = self.max_numeric_digits {
+            let parts = [NumericPart::Major, NumericPart::Minor, NumericPart::Patch];
+            for (part, value) in parts.into_iter().zip(core.split('.')) {
+                if value.len() > limit {
+                    return Err(InvalidPureVersion::TooManyDigits {
+                        part,
+                        digits: value.len(),
+                        limit,
+                    });
+                }
+            }
+        }
+
+        let has_leading_zeros = |part: &str| {
+            part.len() > 1 && part.starts_with('0') && part.bytes().all(|b| b.is_ascii_digit())
+        };
+        let fix_zeros = self.allow_leading_zeros && core.split('.').any(has_leading_zeros);
+        let missing = if self.allow_missing_components && core.split('.').all(|p| !p.is_empty()) {
+            3usize.saturating_sub(core.split('.').count())
+        } else {
+            0
+        };
+        if fix_zeros || missing > 0 {
+            let core = core
+                .split('.')
+                .map(|part| {
+                    if fix_zeros && has_leading_zeros(part) {
+                        let trimmed = part.trim_start_matches('0');
+                        if trimmed.is_empty() { "0" } else { trimmed }
+                    } else {
+                        part
+                    }
+                })
+                .chain(std::iter::repeat_n("0", missing))
+                .join(".");
+            return Ok(Cow::Owned(core + rest));
+        }
+
+        Ok(Cow::Borrowed(s))
+    }
+}
+
+/// Replace the alternate separators, see [`ParseOptions::allow_alternate_separators`]
+fn replace_separators(s: &str) -> String {
+    let core_len = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (core, rest) = s.split_at(core_len);
+    let mut replaced = String::with_capacity(s.len());
+    replaced.push_str(core);
+    for (idx, c) in rest.char_indices() {
+        replaced.push(match c {
+            '_' | '~' if idx == 0 => '-',
+            '_' | '~' => '.',
+            c => c,
+        });
+    }
+    replaced
+}
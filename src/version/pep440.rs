@@ -0,0 +1,382 @@
+//! Python versions, as specified by PEP 440
+//!
+//! PEP 440 versions are made of an optional epoch (`1!`), a release with any number of
+//! components, and optional pre (`a1`, `b2`, `rc3`), post (`.post1`) and dev (`.dev4`)
+//! releases, followed by an optional local label (`+ubuntu.1`).
+//! Parsing accepts the alternative spellings allowed by the specification, and the
+//! versions are displayed in their normalized form.
+
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    num::ParseIntError,
+    str::FromStr,
+};
+
+use chumsky::{Parser, error::Rich, prelude::*};
+use derive_more::Display;
+use itertools::{EitherOrBoth, Itertools};
+use lazy_regex::regex_captures;
+use snafu::{ResultExt, Snafu};
+
+use super::{Version, padded, pure::PureVersion};
+use crate::range::{self, ParserExtra};
+
+#[cfg(test)]
+mod tests;
+
+/// A PEP 440 version
+///
+/// Trailing zeros of the release are not significant, so `1.0` and `1.0.0` are equal.
+/// Equality and ordering follow the PEP 440 precedence: for the same release,
+/// dev releases come before pre releases, that come before the final release,
+/// that comes before post releases.
+#[derive(Debug, Clone)]
+pub struct Pep440Version {
+    pub epoch: u64,
+    /// Components of the release, at least one
+    pub release: Vec<u64>,
+    pub pre: Option<(PreKind, u64)>,
+    pub post: Option<u64>,
+    pub dev: Option<u64>,
+    /// Segments of the local label, empty if there is none
+    pub local: Vec<LocalSegment>,
+}
+
+/// Kind of a pre release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+pub enum PreKind {
+    /// `a`, also written `alpha`
+    #[display("a")]
+    Alpha,
+    /// `b`, also written `beta`
+    #[display("b")]
+    Beta,
+    /// `rc`, also written `c`, `pre` or `preview`
+    #[display("rc")]
+    Rc,
+}
+
+/// A segment of a local label
+///
+/// Numeric segments are greater than alphanumeric ones, that are compared as strings.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+pub enum LocalSegment {
+    /// A segment with at least a letter, in lowercase
+    ///
+    /// The empty segment precedes every other segment, and is used by ranges to represent
+    /// the version immediately after another. It is not valid PEP 440, but trailing empty
+    /// segments are still parsed so that these versions read back: `1.0+` is right after
+    /// `1.0`, and `1.0+ubuntu.` right after `1.0+ubuntu`.
+    Alpha(String),
+    Numeric(u64),
+}
+
+impl Pep440Version {
+    /// Build a final release from the components of the release
+    ///
+    /// # Panics
+    /// If there are no components
+    pub fn new(release: impl Into<Vec<u64>>) -> Self {
+        let release = release.into();
+        assert!(
+            !release.is_empty(),
+            "The release must have at least one component"
+        );
+        Self {
+            epoch: 0,
+            release,
+            pre: None,
+            post: None,
+            dev: None,
+            local: vec![],
+        }
+    }
+
+    /// Return whether this is a pre or dev release
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    /// Release components without the trailing zeros, that do not change the precedence
+    fn significant_release(&self) -> &[u64] {
+        let len = self
+            .release
+            .iter()
+            .rposition(|c| *c != 0)
+            .map_or(0, |idx| idx + 1);
+        &self.release[..len]
+    }
+
+    /// Convert into a semantic version, losing what cannot be represented
+    ///
+    /// - the epoch is dropped,
+    /// - the release is padded or truncated to three components,
+    /// - pre and dev releases become the prerelease identifiers, like `a.1.dev.2`,
+    /// - post releases and local labels become the build metadata, like `post.1.ubuntu.1`.
+    ///   Local segments that are not valid build metadata identifiers are dropped.
+    ///
+    /// The precedence is kept only among versions with the same epoch, no post releases
+    /// and releases with at most three components.
+    ///
+    /// ```
+    /// # use areq::version::pep440::Pep440Version;
+    /// let version: Pep440Version = "1!2.0rc1.post3.dev4+ubuntu.1".parse().unwrap();
+    /// assert_eq!(version.to_version_lossy().to_string(), "2.0.0-rc.1.dev.4+post.3.ubuntu.1");
+    /// ```
+    pub fn to_version_lossy(&self) -> Version {
+        let component = |idx| self.release.get(idx).copied().unwrap_or(0);
+
+        let mut pre = vec![];
+        if let Some((kind, n)) = self.pre {
+            pre.extend([kind.to_string(), n.to_string()]);
+        }
+        if let Some(n) = self.dev {
+            pre.extend(["dev".to_owned(), n.to_string()]);
+        }
+        let mut build = vec![];
+        if let Some(n) = self.post {
+            build.extend(["post".to_owned(), n.to_string()]);
+        }
+        build.extend(self.local.iter().map(ToString::to_string));
+
+        let pre: Vec<_> = pre
+            .iter()
+            .map(|pre| {
+                pre.parse()
+                    .expect("Only alphanumeric identifiers are built")
+            })
+            .collect();
+        // Segments built from the public fields might contain any character
+        let build = build
+            .iter()
+            .filter_map(|build| build.parse().ok())
+            .collect();
+        Version {
+            pure: PureVersion {
+                major: component(0),
+                minor: component(1),
+                patch: component(2),
+                pre: pre.into(),
+            },
+            build,
+        }
+    }
+
+    /// Key of the pre release: dev releases with no pre or post release come before
+    /// every pre release, and final releases after them
+    fn pre_key(&self) -> (u8, Option<(PreKind, u64)>) {
+        match (self.pre, self.post, self.dev) {
+            (None, None, Some(_)) => (0, None),
+            (Some(pre), _, _) => (1, Some(pre)),
+            (None, _, _) => (2, None),
+        }
+    }
+}
+
+impl PartialEq for Pep440Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Pep440Version {}
+
+impl Hash for Pep440Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.epoch.hash(state);
+        self.significant_release().hash(state);
+        self.pre.hash(state);
+        self.post.hash(state);
+        self.dev.hash(state);
+        self.local.hash(state);
+    }
+}
+
+impl PartialOrd for Pep440Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pep440Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| {
+                self.release
+                    .iter()
+                    .zip_longest(&other.release)
+                    .map(|pair| match pair {
+                        EitherOrBoth::Both(a, b) => a.cmp(b),
+                        EitherOrBoth::Left(a) => a.cmp(&0),
+                        EitherOrBoth::Right(b) => 0.cmp(b),
+                    })
+                    .find(|ord| ord.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .then_with(|| self.pre_key().cmp(&other.pre_key()))
+            .then_with(|| self.post.cmp(&other.post))
+            // Releases without a dev part come after their dev releases
+            .then_with(|| (self.dev.is_none(), self.dev).cmp(&(other.dev.is_none(), other.dev)))
+            .then_with(|| self.local.cmp(&other.local))
+    }
+}
+
+impl Display for Pep440Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        padded(f, |f| display_impl(self, &self.local, f))
+    }
+}
+
+fn display_impl(
+    version: &Pep440Version,
+    local: &[LocalSegment],
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result {
+    if version.epoch != 0 {
+        write!(f, "{}!", version.epoch)?;
+    }
+    write!(f, "{}", version.release.iter().format("."))?;
+    if let Some((kind, n)) = version.pre {
+        write!(f, "{kind}{n}")?;
+    }
+    if let Some(n) = version.post {
+        write!(f, ".post{n}")?;
+    }
+    if let Some(n) = version.dev {
+        write!(f, ".dev{n}")?;
+    }
+    if !local.is_empty() {
+        write!(f, "+{}", local.iter().format("."))?;
+    }
+    Ok(())
+}
+
+impl FromStr for Pep440Version {
+    type Err = InvalidPep440Version;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lowercase = s.trim().to_ascii_lowercase();
+        let Some((
+            _,
+            epoch,
+            release,
+            pre_kind,
+            pre_n,
+            implicit_post_n,
+            post_kind,
+            post_n,
+            dev_kind,
+            dev_n,
+            local,
+        )) = regex_captures!(
+            r"^v?(?:(\d+)!)?(\d+(?:\.\d+)*)(?:[-_.]?(alpha|a|beta|b|preview|pre|c|rc)[-_.]?(\d+)?)?(?:-(\d+)|[-_.]?(post|rev|r)[-_.]?(\d+)?)?(?:[-_.]?(dev)[-_.]?(\d+)?)?(\+(?:[a-z0-9]+(?:[-_.][a-z0-9]+)*)?[-_.]*)?$",
+            &lowercase
+        )
+        else {
+            return InvalidFormatSnafu { version: s }.fail();
+        };
+
+        let number =
+            |n: &str| -> Result<u64, _> { n.parse().context(NumberTooBigSnafu { number: n }) };
+        // Missing numbers of pre, post and dev releases are implicitly zero
+        let implicit = |n: &str| if n.is_empty() { Ok(0) } else { number(n) };
+
+        let pre = match pre_kind {
+            "" => None,
+            "a" | "alpha" => Some((PreKind::Alpha, implicit(pre_n)?)),
+            "b" | "beta" => Some((PreKind::Beta, implicit(pre_n)?)),
+            _ => Some((PreKind::Rc, implicit(pre_n)?)),
+        };
+        let post = match (implicit_post_n, post_kind) {
+            ("", "") => None,
+            ("", _) => Some(implicit(post_n)?),
+            (n, _) => Some(number(n)?),
+        };
+        let dev = match dev_kind {
+            "" => None,
+            _ => Some(implicit(dev_n)?),
+        };
+        let local = match local.strip_prefix('+') {
+            None => vec![],
+            Some(local) => local
+                .split(['-', '_', '.'])
+                .map(|segment| {
+                    Ok(
+                        if !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()) {
+                            LocalSegment::Numeric(number(segment)?)
+                        } else {
+                            LocalSegment::Alpha(segment.to_owned())
+                        },
+                    )
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        Ok(Self {
+            epoch: if epoch.is_empty() { 0 } else { number(epoch)? },
+            release: release.split('.').map(number).collect::<Result<_, _>>()?,
+            pre,
+            post,
+            dev,
+            local,
+        })
+    }
+}
+
+impl range::RangeExtreme for Pep440Version {
+    fn minimum() -> Option<Self> {
+        Some(Self {
+            dev: Some(0),
+            ..Self::new([0])
+        })
+    }
+
+    fn next(mut self) -> Option<Self> {
+        // A longer local label with the same beginning is greater,
+        // and the empty segment precedes all the others
+        self.local.push(LocalSegment::Alpha(String::new()));
+        Some(self)
+    }
+}
+
+impl range::RangeExtremeCanonical for Pep440Version {}
+
+impl range::RangeExtremeDisplay for Pep440Version {
+    fn has_prev(&self) -> bool {
+        self.local.last() == Some(&LocalSegment::Alpha(String::new()))
+    }
+
+    fn display_prev(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !range::RangeExtremeDisplay::has_prev(self) {
+            return Err(std::fmt::Error);
+        }
+        display_impl(self, &self.local[..self.local.len() - 1], f)
+    }
+}
+
+impl range::RangeExtremeParseable for Pep440Version {
+    fn parser<'a>() -> impl Parser<'a, &'a str, Self, ParserExtra<'a>> + Clone {
+        any()
+            .filter(|c: &char| {
+                c.is_ascii_alphanumeric() || matches!(c, '.' | '!' | '+' | '-' | '_')
+            })
+            .repeated()
+            .at_least(1)
+            .to_slice()
+            .try_map(|s: &str, span| s.parse().map_err(|err| Rich::custom(span, err)))
+    }
+}
+
+#[derive(Debug, Clone, Snafu)]
+pub enum InvalidPep440Version {
+    #[snafu(display("Invalid PEP 440 version `{version}`"))]
+    InvalidFormat { version: String },
+    #[snafu(display("The number `{number}` does not fit inside a 64 bit unsigned int"))]
+    NumberTooBig {
+        number: String,
+        source: ParseIntError,
+    },
+}
@@ -0,0 +1,214 @@
+use super::{InvalidPep440Version, LocalSegment, Pep440Version, PreKind};
+use crate::range::{RangeExtreme, Ranges};
+
+fn p(s: &str) -> Pep440Version {
+    s.parse().unwrap()
+}
+
+/// Ordering examples from PEP 440 and its reference implementation, in ascending order
+static SORTED: &[&str] = &[
+    "1.0.dev456",
+    "1.0a1",
+    "1.0a2.dev456",
+    "1.0a12.dev456",
+    "1.0a12",
+    "1.0b1.dev456",
+    "1.0b2",
+    "1.0b2.post345.dev456",
+    "1.0b2.post345",
+    "1.0b2-346",
+    "1.0c1.dev456",
+    "1.0c1",
+    "1.0rc2",
+    "1.0c3",
+    "1.0",
+    "1.0.post456.dev34",
+    "1.0.post456",
+    "1.1.dev1",
+    "1.2+123abc",
+    "1.2+123abc456",
+    "1.2+abc",
+    "1.2+abc123",
+    "1.2+abc123def",
+    "1.2+1234.abc",
+    "1.2+123456",
+    "1.2.r32+123456",
+    "1.2.rev33+123456",
+    "1!1.0b2.post345.dev456",
+    "1!1.0",
+    "1!1.0.post456",
+    "1!1.2.rev33+123456",
+];
+
+#[test]
+fn precedence() {
+    let versions: Vec<_> = SORTED.iter().map(|v| p(v)).collect();
+    for (idx, pair) in versions.windows(2).enumerate() {
+        assert!(pair[0] < pair[1], "{} < {}", SORTED[idx], SORTED[idx + 1]);
+    }
+}
+
+#[test]
+fn trailing_zeros_are_not_significant() {
+    assert_eq!(p("1.0"), p("1.0.0"));
+    assert_eq!(p("1"), p("1.0.0.0"));
+    assert!(p("1.0.1") > p("1.0"));
+}
+
+#[test]
+fn normalization() {
+    for (input, normalized) in [
+        ("1.0", "1.0"),
+        ("v1.0", "1.0"),
+        (" 1.0\n", "1.0"),
+        ("01.002", "1.2"),
+        ("0!1.0", "1.0"),
+        ("1.0ALPHA1", "1.0a1"),
+        ("1.0-alpha.1", "1.0a1"),
+        ("1.0b", "1.0b0"),
+        ("1.0c1", "1.0rc1"),
+        ("1.0preview2", "1.0rc2"),
+        ("1.0_pre_3", "1.0rc3"),
+        ("1.0-post1", "1.0.post1"),
+        ("1.0-1", "1.0.post1"),
+        ("1.0r", "1.0.post0"),
+        ("1.0.rev2", "1.0.post2"),
+        ("1.0.dev", "1.0.dev0"),
+        ("1.0-dev-3", "1.0.dev3"),
+        (
+            "2!1.0a1.post2.dev3+Ubuntu-1_x",
+            "2!1.0a1.post2.dev3+ubuntu.1.x",
+        ),
+    ] {
+        assert_eq!(p(input).to_string(), normalized, "{input:?}");
+    }
+}
+
+#[test]
+fn parts() {
+    let version = p("1!2.3rc4.post5.dev6+local.7");
+    assert_eq!(version.epoch, 1);
+    assert_eq!(version.release, [2, 3]);
+    assert_eq!(version.pre, Some((PreKind::Rc, 4)));
+    assert_eq!(version.post, Some(5));
+    assert_eq!(version.dev, Some(6));
+    assert_eq!(
+        version.local,
+        [
+            LocalSegment::Alpha("local".to_owned()),
+            LocalSegment::Numeric(7)
+        ]
+    );
+    assert!(version.is_prerelease());
+    assert!(!p("1.0.post1").is_prerelease());
+}
+
+#[test]
+fn invalid() {
+    for invalid in [
+        "",
+        "1.",
+        ".1",
+        "1.0+.abc",
+        "1.0+abc..1",
+        "1.0gamma",
+        "1!",
+        "1.0-",
+        "a1.0",
+    ] {
+        assert!(
+            matches!(
+                invalid.parse::<Pep440Version>(),
+                Err(InvalidPep440Version::InvalidFormat { .. })
+            ),
+            "{invalid:?}"
+        );
+    }
+    assert!(matches!(
+        "1.99999999999999999999".parse::<Pep440Version>(),
+        Err(InvalidPep440Version::NumberTooBig { .. })
+    ));
+}
+
+#[test]
+fn ranges() {
+    let range = Ranges::<Pep440Version>::from_str(">=1.0 && <2.0 || ==3.0").unwrap();
+    assert!(range.contains(&p("1.0.0")));
+    assert!(range.contains(&p("1.5.post1")));
+    assert!(range.contains(&p("1.9+local")));
+    assert!(!range.contains(&p("1.0rc1")));
+    assert!(!range.contains(&p("2.0")));
+    assert!(range.contains(&p("3.0")));
+    assert!(!range.contains(&p("3.0+local")));
+    assert!(!range.contains(&p("3.0.post1")));
+    assert_eq!(range.to_string(), ">=1.0 && <2.0 || ==3.0");
+
+    let range = Ranges::<Pep440Version>::from_str("<=1!1.0a1 , !=0.5").unwrap();
+    assert!(range.contains(&p("1!1.0a1")));
+    assert!(range.contains(&p("1!1.0a1.dev1")));
+    assert!(!range.contains(&p("1!1.0a1+local")));
+    assert!(!range.contains(&p("0.5.0")));
+    assert_eq!(range.to_string(), "<0.5 || >0.5 && <=1!1.0a1");
+}
+
+#[test]
+fn successors_read_back() {
+    for (version, successor) in [
+        ("1.0", "1.0+"),
+        ("1.0+ubuntu.1", "1.0+ubuntu.1."),
+        ("1.0+", "1.0+."),
+    ] {
+        let next = p(version).next().unwrap();
+        assert_eq!(next.to_string(), successor);
+        assert_eq!(p(successor), next);
+        assert!(p(version) < p(successor));
+    }
+    assert_eq!(p("1.0+ubuntu-"), p("1.0+ubuntu."));
+
+    for req in [">1.0", "==1.0", "<=1.0+ubuntu", "!=1.0"] {
+        let range = Ranges::<Pep440Version>::from_str(req).unwrap();
+        let canonical = range.to_canonical_string();
+        assert_eq!(Ranges::from_canonical(&canonical).unwrap(), range, "{req}");
+    }
+    assert_eq!(
+        Ranges::<Pep440Version>::from_str(">1.0")
+            .unwrap()
+            .to_canonical_string(),
+        ">=1.0+"
+    );
+}
+
+#[test]
+fn to_version_lossy() {
+    for (input, semantic) in [
+        ("1.2.3", "1.2.3"),
+        ("1.2", "1.2.0"),
+        ("1.2.3.4", "1.2.3"),
+        ("1!1.0", "1.0.0"),
+        ("1.0b2", "1.0.0-b.2"),
+        ("1.0.dev3", "1.0.0-dev.3"),
+        ("1.0.post1+abc.5", "1.0.0+post.1.abc.5"),
+    ] {
+        assert_eq!(p(input).to_version_lossy().to_string(), semantic, "{input}");
+    }
+}
+
+#[test]
+fn to_version_lossy_accepts_any_value() {
+    let version = Pep440Version {
+        release: vec![],
+        pre: Some((PreKind::Rc, u64::MAX)),
+        local: vec![
+            LocalSegment::Alpha("not valid".to_owned()),
+            LocalSegment::Alpha(String::new()),
+            LocalSegment::Alpha("a.b".to_owned()),
+            LocalSegment::Alpha("ok".to_owned()),
+            LocalSegment::Numeric(7),
+        ],
+        ..Pep440Version::new([1])
+    };
+    assert_eq!(
+        version.to_version_lossy().to_string(),
+        "0.0.0-rc.18446744073709551615+ok.7"
+    );
+}
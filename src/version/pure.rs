@@ -4,13 +4,20 @@ use std::{borrow::Cow, fmt::Display, num::ParseIntError, str::FromStr};
 
 use chumsky::{Parser, prelude::*, text::digits};
 use derive_more::Display;
-use lazy_regex::regex_captures;
+use lazy_regex::{regex_captures, regex_is_match};
 use snafu::{ResultExt, Snafu};
 
-use crate::range::{self, ParserExtra};
+use super::{InvalidVersion, build::BuildMetadata, padded};
+use crate::range::{self, ParserExtra, Ranges, traced};
 
+pub mod comparator;
+pub mod explain;
+mod like;
+mod majors;
+pub mod policy;
 pub mod prerelease;
 
+pub use like::VersionLike;
 use prerelease::{InvalidPrerelease, Prerelease};
 
 #[cfg(test)]
@@ -28,6 +35,7 @@ pub struct PureVersion {
 }
 
 impl PureVersion {
+    /// The minimum representable version
     const MIN: Self = Self {
         major: 0,
         minor: 0,
@@ -37,11 +45,17 @@ impl PureVersion {
             &V
         }),
     };
-    /// The maximum representable version
-    const MAX: Self = Self {
-        major: UInt::MAX,
-        minor: UInt::MAX,
-        patch: UInt::MAX,
+
+    /// The version `0.0.0`
+    ///
+    /// ```
+    /// # use areq::version::pure::PureVersion;
+    /// assert_eq!(PureVersion::ZERO, "0.0.0".parse().unwrap());
+    /// ```
+    pub const ZERO: Self = Self {
+        major: 0,
+        minor: 0,
+        patch: 0,
         pre: Cow::Borrowed(&[]),
     };
 
@@ -62,31 +76,132 @@ impl PureVersion {
         !self.pre.is_empty()
     }
 
+    /// Classify the stability of the version
+    ///
+    /// ```
+    /// # use areq::version::pure::{PureVersion, Stability};
+    /// assert_eq!(PureVersion::new(0, 3, 1).stability(), Stability::Unstable0x);
+    /// ```
+    pub fn stability(&self) -> Stability {
+        match (self.major, self.minor) {
+            _ if self.is_prerelease() => Stability::Prerelease,
+            (0, 0) => Stability::Unstable00x,
+            (0, _) => Stability::Unstable0x,
+            _ => Stability::Stable,
+        }
+    }
+
+    /// Replace the prerelease with already validated identifiers
+    ///
+    /// An empty iterator makes this version a release.
+    pub fn set_prerelease(&mut self, pre: impl IntoIterator<Item = Prerelease>) {
+        self.pre = Cow::Owned(pre.into_iter().collect());
+    }
+
+    /// The most significant component that differs between the two versions
+    ///
+    /// The result is symmetric, so it does not tell if `other` is greater or smaller.
+    ///
+    /// ```
+    /// # use areq::version::pure::{PureVersion, VersionChange};
+    /// let v = |s: &str| s.parse::<PureVersion>().unwrap();
+    /// assert_eq!(v("1.2.3").diff(&v("1.3.0")), VersionChange::Minor);
+    /// assert_eq!(v("1.2.3-rc.1").diff(&v("1.2.3")), VersionChange::Prerelease);
+    /// ```
+    pub fn diff(&self, other: &PureVersion) -> VersionChange {
+        if self.major != other.major {
+            VersionChange::Major
+        } else if self.minor != other.minor {
+            VersionChange::Minor
+        } else if self.patch != other.patch {
+            VersionChange::Patch
+        } else if self.pre != other.pre {
+            VersionChange::Prerelease
+        } else {
+            VersionChange::None
+        }
+    }
+
+    /// Apply a single bump of the given kind
+    ///
+    /// Numeric bumps increment the component and reset the lower ones, dropping the
+    /// prerelease identifiers, saturating at [`UInt::MAX`].
+    /// [`BumpKind::PrereleaseOnly`] moves a prerelease to the next one of its series
+    /// (see [`Version::prerelease_series`](super::Version::prerelease_series)) and leaves
+    /// releases unchanged. The other kinds return the version unchanged.
+    ///
+    /// ```
+    /// # use areq::version::pure::{BumpKind, PureVersion};
+    /// let v = |s: &str| s.parse::<PureVersion>().unwrap();
+    /// assert_eq!(v("1.2.3-rc.1").apply_bump(BumpKind::Minor), v("1.3.0"));
+    /// assert_eq!(v("1.2.3-rc.1").apply_bump(BumpKind::PrereleaseOnly), v("1.2.3-rc.2"));
+    /// ```
+    pub fn apply_bump(&self, kind: BumpKind) -> PureVersion {
+        let (major, minor, patch) = (self.major, self.minor, self.patch);
+        match kind {
+            BumpKind::Major => Self::new(major.saturating_add(1), 0, 0),
+            BumpKind::Minor => Self::new(major, minor.saturating_add(1), 0),
+            BumpKind::Patch => Self::new(major, minor, patch.saturating_add(1)),
+            BumpKind::PrereleaseOnly => self.next_prerelease().unwrap_or_else(|| self.clone()),
+            BumpKind::BuildOnly | BumpKind::None | BumpKind::Downgrade => self.clone(),
+        }
+    }
+
+    /// The next prerelease in the series of this one, or `None` for releases
+    ///
+    /// The last identifier is incremented if numeric, otherwise `.1` is appended.
+    pub(super) fn next_prerelease(&self) -> Option<PureVersion> {
+        if !self.is_prerelease() {
+            return None;
+        }
+        let mut pre = self.pre.to_vec();
+        match pre.last_mut() {
+            Some(Prerelease::Numeric(n)) => *n = n.incremented(),
+            _ => pre.push("1".parse().expect("`1` is a valid identifier")),
+        }
+        Some(PureVersion {
+            pre: pre.into(),
+            ..self.clone()
+        })
+    }
+
     /// Calculate the immediate successive version, such there are no version between this and that
     ///
     /// Note that this is not a "version bump", and normally generates nonsensical versions like `1.2.3-0.0.0.0`.
     /// The objective is simply to represent an exact version as a range [v, v.next()).
-    fn next(mut self) -> Self {
+    ///
+    /// Returns `None` only for the greatest release, `u64::MAX.u64::MAX.u64::MAX`.
+    fn next(mut self) -> Option<Self> {
         if !self.is_prerelease() {
-            self.patch += 1;
+            (self.major, self.minor, self.patch) =
+                next_release_core(self.major, self.minor, self.patch)?;
         }
         self.pre.to_mut().push(Prerelease::MIN);
-        self
+        Some(self)
     }
 
     /// Return if next is the next version
     fn compare_next_to(&self, other: &PureVersion) -> bool {
-        other.has_prev()
-            && self.major == other.major
-            && self.minor == other.minor
-            && if self.is_prerelease() {
-                self.patch == other.patch && self.pre == other.pre.split_last().unwrap().1
-            } else {
-                self.patch + 1 == other.patch && other.pre.len() == 1
-            }
+        let Some((last, other_pre)) = other.pre.split_last() else {
+            return false;
+        };
+        if last != &Prerelease::MIN {
+            return false;
+        }
+        if self.is_prerelease() {
+            (self.major, self.minor, self.patch) == (other.major, other.minor, other.patch)
+                && *self.pre == *other_pre
+        } else {
+            other_pre.is_empty()
+                && next_release_core(self.major, self.minor, self.patch)
+                    == Some((other.major, other.minor, other.patch))
+        }
     }
 
-    /// Check if this version has a previous version, such there are no version between that and this
+    /// Check if this version should be displayed through its previous version
+    ///
+    /// This is true if this version has a previous version, such there are no version between that and this,
+    /// with the exception of `x.y.0-0` and `x.0.0-0`, that are preceded by releases with a `u64::MAX` component.
     fn has_prev(&self) -> bool {
         self.pre.last() == Some(&Prerelease::MIN)
             && if self.pre.len() == 1 {
@@ -112,6 +227,131 @@ impl PureVersion {
         }
     */
 
+    /// Parser for a version, to embed it in other [`chumsky`] grammars
+    ///
+    /// The type of the parser is opaque and might change, only the accepted grammar is stable.
+    /// It does not consume any surrounding whitespace.
+    ///
+    /// ```
+    /// # use areq::{range::ParserExtra, version::pure::PureVersion};
+    /// use chumsky::prelude::*;
+    ///
+    /// fn spec<'a>() -> impl Parser<'a, &'a str, (&'a str, PureVersion), ParserExtra<'a>> {
+    ///     any()
+    ///         .filter(|c: &char| c.is_ascii_alphanumeric() || *c == '-')
+    ///         .repeated()
+    ///         .at_least(1)
+    ///         .to_slice()
+    ///         .then_ignore(just('@'))
+    ///         .then(PureVersion::parser())
+    /// }
+    ///
+    /// let (name, version) = spec().parse("serde@1.0.219").into_result().unwrap();
+    /// assert_eq!(name, "serde");
+    /// assert_eq!(version, PureVersion::new(1, 0, 219));
+    /// assert!(spec().parse("serde@1.0").has_errors());
+    /// ```
+    pub fn parser<'a>() -> impl chumsky::Parser<'a, &'a str, Self, ParserExtra<'a>> + Clone {
+        let numeric = digits(10).to_slice();
+
+        let version = numeric
+            .labelled("major")
+            .then_ignore(just('.'))
+            .then(numeric.labelled("minor"))
+            .then_ignore(just('.'))
+            .then(numeric.labelled("patch"))
+            .then(
+                just('-')
+                    .ignore_then(
+                        Prerelease::parser()
+                            .separated_by(just('.'))
+                            .at_least(1)
+                            .collect::<Vec<_>>(),
+                    )
+                    .or_not(),
+            )
+            .try_map(|(((major, minor), patch), pre), span| {
+                PureVersion::from_checked_parts_splitted(
+                    major,
+                    minor,
+                    patch,
+                    pre.map(Cow::Owned).unwrap_or(Cow::Borrowed(&[])),
+                )
+                .map_err(|err| Rich::custom(span, err))
+            });
+        traced(version, "version")
+    }
+
+    /// Parse a full version string, discarding the build metadata
+    ///
+    /// The build metadata is validated, but no identifier is allocated.
+    ///
+    /// ```
+    /// # use areq::version::pure::PureVersion;
+    /// let version = PureVersion::parse_ignoring_build("1.2.3-rc.1+build.5").unwrap();
+    /// assert_eq!(version.to_string(), "1.2.3-rc.1");
+    /// ```
+    pub fn parse_ignoring_build(s: &str) -> Result<Self, InvalidVersion> {
+        let (pure, build) = s.split_once('+').unwrap_or((s, ""));
+        if s.len() > pure.len() && !regex_is_match!(r"^[0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*$", build) {
+            let source = build
+                .split('.')
+                .find_map(|build| build.parse::<BuildMetadata>().err())
+                .expect("At least one identifier is invalid if the regex failed");
+            return Err(InvalidVersion::InvalidBuildMetadata { source });
+        }
+        Ok(pure.parse()?)
+    }
+
+    /// Parse the longest version at the start of `s`, returning the rest of the input
+    ///
+    /// ```
+    /// # use areq::version::pure::PureVersion;
+    /// let (version, rest) = PureVersion::parse_prefix("1.2.3-alpha and more").unwrap();
+    /// assert_eq!(version.to_string(), "1.2.3-alpha");
+    /// assert_eq!(rest, " and more");
+    /// ```
+    pub fn parse_prefix(s: &str) -> Result<(Self, &str), InvalidPureVersion> {
+        // Alphanumeric identifiers come first, so the longest one is taken
+        let Some((matched, major, minor, patch, pre)) = regex_captures!(
+            r"^(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)(?:-((?:\d*[a-zA-Z-][0-9a-zA-Z-]*|0|[1-9]\d*)(?:\.(?:\d*[a-zA-Z-][0-9a-zA-Z-]*|0|[1-9]\d*))*))?",
+            s
+        ) else {
+            return Err(debug_invalid_pure_version(s));
+        };
+
+        let version = Self::from_checked_parts(
+            major,
+            minor,
+            patch,
+            pre,
+            super::ParseOptions::DEFAULT.max_prerelease_digits,
+        )?;
+        Ok((version, &s[matched.len()..]))
+    }
+
+    /// Parse a strict version, with the given limit on the digits of numeric prereleases
+    fn parse_limited(s: &str, max_prerelease_digits: usize) -> Result<Self, InvalidPureVersion> {
+        let Some((_, major, minor, patch, pre)) = regex_captures!(
+            r"^(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)(?:-((?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*)(?:\.(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*))*))?$",
+            s
+        ) else {
+            return Err(debug_invalid_pure_version(s));
+        };
+
+        Self::from_checked_parts(major, minor, patch, pre, max_prerelease_digits)
+    }
+
+    /// Parse a version, with additional options
+    ///
+    /// With the default options this is the same as [`PureVersion::from_str`].
+    pub fn parse_with_options(
+        s: &str,
+        options: &super::ParseOptions,
+    ) -> Result<Self, InvalidPureVersion> {
+        Self::parse_limited(&options.preprocess(s)?, options.max_prerelease_digits)
+    }
+
     /// Display the previous version without cloning
     ///
     /// Fails if [`has_pre`] is false
@@ -133,15 +373,15 @@ impl PureVersion {
         minor: &str,
         patch: &str,
         pre: &str,
+        max_prerelease_digits: usize,
     ) -> Result<PureVersion, InvalidPureVersion> {
         let pre = if !pre.is_empty() {
+            // The regex only matches valid identifiers, but they can still exceed the limits
             Cow::Owned(
                 pre.split('.')
-                    .map(|p| {
-                        p.parse()
-                            .expect("The regex only matches valid prerelase identifiers")
-                    })
-                    .collect(),
+                    .map(|pre| Prerelease::parse_limited(pre, max_prerelease_digits))
+                    .collect::<Result<_, _>>()
+                    .map_err(|source| InvalidPureVersion::InvalidPrerelease { source })?,
             )
         } else {
             Cow::Borrowed(&[] as &[_])
@@ -167,10 +407,6 @@ impl PureVersion {
             part: NumericPart::Patch,
         })?;
 
-        if patch == UInt::MAX && pre.is_empty() {
-            return Err(InvalidPureVersion::PatchCannotBeUIntMax);
-        }
-
         Ok(Self {
             major,
             minor,
@@ -178,13 +414,80 @@ impl PureVersion {
             pre,
         })
     }
+
+    /// Append to `out` a key that sorts bytewise like the version precedence
+    ///
+    /// See [`Version::to_sortable_bytes`](super::Version::to_sortable_bytes) for the encoding.
+    pub(crate) fn write_sortable_bytes(&self, out: &mut Vec<u8>) {
+        for part in [self.major, self.minor, self.patch] {
+            out.extend_from_slice(&part.to_be_bytes());
+        }
+        if !self.is_prerelease() {
+            // A release follows all its prereleases
+            out.push(2);
+            return;
+        }
+        out.push(1);
+        for identifier in self.pre.iter() {
+            match identifier {
+                Prerelease::Numeric(n) => {
+                    // Longer numbers are bigger, as there are no leading zeros
+                    let bytes = n.to_bytes_be();
+                    out.push(1);
+                    out.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+                    out.extend_from_slice(&bytes);
+                }
+                Prerelease::Alpha(a) => {
+                    // Identifiers never contain a zero byte, so it can terminate them
+                    out.push(2);
+                    out.extend_from_slice(a.as_str().as_bytes());
+                    out.push(0);
+                }
+            }
+        }
+        // Fewer identifiers sort first
+        out.push(0);
+    }
 }
 
 impl Display for PureVersion {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        display_impl(self.major, self.minor, self.patch, &*self.pre, f)
+        padded(f, |f| {
+            display_impl(self.major, self.minor, self.patch, &self.pre, f)
+        })
     }
 }
+
+/// Calculate the smallest release core greater than the given one
+fn next_release_core(major: UInt, minor: UInt, patch: UInt) -> Option<(UInt, UInt, UInt)> {
+    if let Some(patch) = patch.checked_add(1) {
+        Some((major, minor, patch))
+    } else if let Some(minor) = minor.checked_add(1) {
+        Some((major, minor, 0))
+    } else {
+        Some((major.checked_add(1)?, 0, 0))
+    }
+}
+
+/// Convert a version into its string representation
+///
+/// ```
+/// # use areq::version::pure::PureVersion;
+/// let s: String = PureVersion::new(1, 2, 3).into();
+/// assert_eq!(s, "1.2.3");
+/// ```
+impl From<PureVersion> for String {
+    fn from(value: PureVersion) -> Self {
+        value.to_string()
+    }
+}
+
+impl From<&PureVersion> for String {
+    fn from(value: &PureVersion) -> Self {
+        value.to_string()
+    }
+}
+
 fn display_impl(
     major: UInt,
     minor: UInt,
@@ -208,19 +511,56 @@ impl FromStr for PureVersion {
     type Err = InvalidPureVersion;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let Some((_, major, minor, patch, pre)) = regex_captures!(
-            r"^(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)(?:-((?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*)(?:\.(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*))*))?$",
-            s
-        ) else {
-            return Err(debug_invalid_pure_version(s));
-        };
+        Self::parse_limited(s, super::ParseOptions::DEFAULT.max_prerelease_digits)
+    }
+}
+
+impl TryFrom<&str> for PureVersion {
+    type Error = InvalidPureVersion;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
 
-        Self::from_checked_parts(major, minor, patch, pre)
+impl From<(UInt, UInt, UInt)> for PureVersion {
+    /// The release with the given major, minor and patch versions
+    fn from((major, minor, patch): (UInt, UInt, UInt)) -> Self {
+        Self::new(major, minor, patch)
     }
 }
 
+/// Find whitespace inside a version, next to one of its separators
+///
+/// Whitespace between two words is left alone, as it is more likely to separate
+/// the version from what follows than to be a typo inside it.
+pub(super) fn find_stray_whitespace(s: &str) -> Option<usize> {
+    let is_separator = |c: char| matches!(c, '.' | '-' | '+');
+    s.char_indices()
+        .filter(|(_, c)| c.is_whitespace())
+        .find(|&(position, _)| {
+            let before = s[..position].trim_end().chars().next_back();
+            let after = s[position..].trim_start().chars().next();
+            match (before, after) {
+                (Some(before), Some(after)) => is_separator(before) || is_separator(after),
+                _ => false,
+            }
+        })
+        .map(|(position, _)| position)
+}
+
 fn debug_invalid_pure_version(s: &str) -> InvalidPureVersion {
-    let (version, pre) = s.split_once('-').unwrap_or((s, ""));
+    if s.trim().is_empty() {
+        return InvalidPureVersion::Empty;
+    }
+    if let Some(position) = find_stray_whitespace(s) {
+        return InvalidPureVersion::UnexpectedWhitespace { position };
+    }
+
+    let (version, pre) = match s.split_once('-') {
+        Some((version, pre)) => (version, Some(pre)),
+        None => (s, None),
+    };
 
     let mut version = version.splitn(4, '.');
     let Some(major) = version.next() else {
@@ -266,7 +606,27 @@ fn debug_invalid_pure_version(s: &str) -> InvalidPureVersion {
         };
     };
 
-    if !pre.is_empty() {
+    for (part, value) in [
+        (NumericPart::Major, major),
+        (NumericPart::Minor, minor),
+        (NumericPart::Patch, patch),
+    ] {
+        // `UInt::from_str` accepts a leading `+`
+        if !value.bytes().all(|b| b.is_ascii_digit()) {
+            return InvalidPureVersion::NonDigitNumericPart {
+                part,
+                value: value.to_string(),
+            };
+        }
+        if value.len() > 1 && value.starts_with('0') {
+            return InvalidPureVersion::LeadingZeros {
+                part,
+                value: value.to_string(),
+            };
+        }
+    }
+
+    if let Some(pre) = pre {
         for pre in pre.split('.') {
             if let Err(source) = pre.parse::<Prerelease>() {
                 return InvalidPureVersion::InvalidPrerelease { source };
@@ -274,9 +634,83 @@ fn debug_invalid_pure_version(s: &str) -> InvalidPureVersion {
         }
     }
 
-    unreachable!(
-        "At least one of the preceding conditions should fail if the regex failed. The passing version is {s}"
-    );
+    InvalidPureVersion::Malformed {
+        version: s.to_string(),
+    }
+}
+
+/// The most significant difference between two versions, see [`PureVersion::diff`]
+///
+/// Changes are ordered by significance, from [`VersionChange::None`] to [`VersionChange::Major`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+pub enum VersionChange {
+    /// The versions are equal
+    #[display("none")]
+    None,
+    /// Only the prerelease identifiers differ
+    #[display("prerelease")]
+    Prerelease,
+    #[display("patch")]
+    Patch,
+    #[display("minor")]
+    Minor,
+    #[display("major")]
+    Major,
+}
+
+/// The kind of an update between two versions, see [`Version::bump_kind`](super::Version::bump_kind)
+///
+/// An update that only leaves the prerelease, like `1.2.3-rc.1` to `1.2.3`,
+/// is [`BumpKind::PrereleaseOnly`], as the major, minor and patch versions do not change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+pub enum BumpKind {
+    #[display("major")]
+    Major,
+    #[display("minor")]
+    Minor,
+    #[display("patch")]
+    Patch,
+    /// Only the prerelease identifiers changed
+    #[display("prerelease")]
+    PrereleaseOnly,
+    /// Only the build metadata changed
+    #[display("build")]
+    BuildOnly,
+    /// The versions are identical
+    #[display("none")]
+    None,
+    /// The newer version precedes the older one
+    #[display("downgrade")]
+    Downgrade,
+}
+
+/// How stable a version is, see [`PureVersion::stability`]
+///
+/// Classes are ordered by increasing stability, from [`Stability::Prerelease`] to [`Stability::Stable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+pub enum Stability {
+    /// A prerelease, whatever its major version
+    #[display("pre-release")]
+    Prerelease,
+    /// A `0.0.x` release, where every change can break
+    #[display("experimental (0.0.x)")]
+    Unstable00x,
+    /// A `0.x` release
+    #[display("experimental (0.x)")]
+    Unstable0x,
+    #[display("stable")]
+    Stable,
+}
+
+/// The step between releases, see [`Version::releases_between`](super::Version::releases_between)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+pub enum Granularity {
+    #[display("major")]
+    Major,
+    #[display("minor")]
+    Minor,
+    #[display("patch")]
+    Patch,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
@@ -291,11 +725,19 @@ pub enum NumericPart {
 
 #[derive(Debug, Clone, Snafu)]
 pub enum InvalidPureVersion {
+    #[snafu(display("The version is empty"))]
+    Empty,
     #[snafu(display("The {part} version is too big to fit inside a 64 bit unsigned int"))]
     NumericPartTooLong {
         part: NumericPart,
         source: ParseIntError,
     },
+    #[snafu(display("The {part} version has {digits} digits, more than the limit of {limit}"))]
+    TooManyDigits {
+        part: NumericPart,
+        digits: usize,
+        limit: usize,
+    },
     #[snafu(display("The {part} version is missing"))]
     MissingNumericPart { part: NumericPart },
     #[snafu(display("Additional data between numeric parts and prerelase: `{extra}`"))]
@@ -306,12 +748,16 @@ pub enum InvalidPureVersion {
         value: String,
         source: ParseIntError,
     },
+    #[snafu(display("The {part} version must not start with zero: `{value}`"))]
+    LeadingZeros { part: NumericPart, value: String },
+    #[snafu(display("The {part} version must only contain digits: `{value}`"))]
+    NonDigitNumericPart { part: NumericPart, value: String },
     #[snafu(display("Invalid prerelease"))]
     InvalidPrerelease { source: InvalidPrerelease },
-    #[snafu(display(
-        "The patch version cannot be the maximum 64 bit unsigned int unless prerelease"
-    ))]
-    PatchCannotBeUIntMax,
+    #[snafu(display("Unexpected whitespace in version at position {position}"))]
+    UnexpectedWhitespace { position: usize },
+    #[snafu(display("Invalid version `{version}`"))]
+    Malformed { version: String },
 }
 
 impl PartialOrd for PureVersion {
@@ -342,59 +788,141 @@ impl Ord for PureVersion {
     }
 }
 
-impl range::RangeExtreme for PureVersion {
-    const MIN: Self = PureVersion::MIN;
+/// How prerelease versions are matched against ranges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PrereleasePolicy {
+    /// Prereleases match only when they are explicitly requested
+    ///
+    /// A prerelease is inside a range only if one of the extremes of its interval
+    /// is a prerelease with the same major, minor and patch version.
+    /// So `1.2.3-rc.2` matches `>=1.2.3-rc.1`, but neither `>=1.2.0` nor `<1.2.3`.
+    ///
+    /// Extremes like `1.2.4-0` are not considered explicit, as they are generated
+    /// by operators like `>1.2.3`.
+    #[default]
+    Explicit,
+    /// Prereleases are matched like any other version
+    Include,
+    /// Prereleases never match
+    Exclude,
+}
 
-    const MAX: Self = PureVersion::MAX;
+impl Ranges<PureVersion> {
+    /// Return whether `version` is inside the range, following the given prerelease policy
+    pub fn contains_with_policy(&self, version: &PureVersion, policy: PrereleasePolicy) -> bool {
+        if !version.is_prerelease() {
+            return self.contains(version);
+        }
+        match policy {
+            PrereleasePolicy::Include => self.contains(version),
+            PrereleasePolicy::Exclude => false,
+            PrereleasePolicy::Explicit => self.interval_of(version).is_some_and(|(start, end)| {
+                [start, end].into_iter().flatten().any(|extreme| {
+                    extreme.is_prerelease()
+                        && *extreme.pre != [Prerelease::MIN]
+                        && (extreme.major, extreme.minor, extreme.patch)
+                            == (version.major, version.minor, version.patch)
+                })
+            }),
+        }
+    }
+
+    /// Return the weakest stability of the versions matching the requirement,
+    /// or `None` if no version matches
+    ///
+    /// Matching follows the default [`PrereleasePolicy`], so prereleases count only
+    /// if they are explicitly allowed.
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::pure::Stability};
+    /// let req = Ranges::from_str(">=0.2.0 && <2.0.0").unwrap();
+    /// assert_eq!(req.min_stability(), Some(Stability::Unstable0x));
+    /// ```
+    pub fn min_stability(&self) -> Option<Stability> {
+        let policy = PrereleasePolicy::default();
+        let admits_prerelease = self.intervals().any(|(start, end)| {
+            // The lowest prerelease that an extreme could make explicit
+            let below_end = end.map(|end| {
+                let first = PureVersion {
+                    pre: PureVersion::MIN.pre,
+                    ..end.clone()
+                };
+                start.map_or(first.clone(), |start| start.clone().max(first))
+            });
+            start
+                .cloned()
+                .into_iter()
+                .chain(below_end)
+                .any(|candidate| {
+                    candidate.is_prerelease() && self.contains_with_policy(&candidate, policy)
+                })
+        });
+        if admits_prerelease {
+            return Some(Stability::Prerelease);
+        }
+
+        let has_release_in = |low: PureVersion, high: Option<PureVersion>| {
+            self.intervals().any(|(start, end)| {
+                let lowest = match start {
+                    Some(start) if start.is_prerelease() => PureVersion {
+                        pre: Cow::Borrowed(&[]),
+                        ..start.clone()
+                    },
+                    Some(start) => start.clone(),
+                    None => PureVersion::ZERO,
+                }
+                .max(low.clone());
+                end.is_none_or(|end| lowest < *end)
+                    && high.as_ref().is_none_or(|high| lowest < *high)
+            })
+        };
+        [
+            (
+                Stability::Unstable00x,
+                PureVersion::ZERO,
+                Some(PureVersion::new(0, 1, 0)),
+            ),
+            (
+                Stability::Unstable0x,
+                PureVersion::new(0, 1, 0),
+                Some(PureVersion::new(1, 0, 0)),
+            ),
+            (Stability::Stable, PureVersion::new(1, 0, 0), None),
+        ]
+        .into_iter()
+        .find(|(_, low, high)| has_release_in(low.clone(), high.clone()))
+        .map(|(stability, _, _)| stability)
+    }
+}
+
+impl range::RangeExtreme for PureVersion {
+    fn minimum() -> Option<Self> {
+        Some(PureVersion::MIN)
+    }
 
-    fn next(self) -> Self {
+    fn next(self) -> Option<Self> {
         PureVersion::next(self)
     }
 
     fn compare_next_to(&self, other: &Self) -> bool {
-        PureVersion::compare_next_to(&self, other)
+        PureVersion::compare_next_to(self, other)
     }
 }
 
+impl range::RangeExtremeCanonical for PureVersion {}
+
 impl range::RangeExtremeDisplay for PureVersion {
     fn has_prev(&self) -> bool {
-        PureVersion::has_prev(&self)
+        PureVersion::has_prev(self)
     }
 
     fn display_prev(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        PureVersion::display_prev(&self, f)
+        PureVersion::display_prev(self, f)
     }
 }
 
 impl range::RangeExtremeParseable for PureVersion {
     fn parser<'a>() -> impl chumsky::Parser<'a, &'a str, Self, ParserExtra<'a>> + Clone {
-        let numeric = digits(10).to_slice();
-
-        numeric
-            .clone()
-            .labelled("major")
-            .then_ignore(just('.'))
-            .then(numeric.clone().labelled("minor"))
-            .then_ignore(just('.'))
-            .then(numeric.labelled("patch"))
-            .then(
-                just('-')
-                    .ignore_then(
-                        Prerelease::parser()
-                            .separated_by(just('.'))
-                            .at_least(1)
-                            .collect::<Vec<_>>(),
-                    )
-                    .or_not(),
-            )
-            .try_map(|(((major, minor), patch), pre), span| {
-                PureVersion::from_checked_parts_splitted(
-                    major,
-                    minor,
-                    patch,
-                    pre.map(Cow::Owned).unwrap_or(Cow::Borrowed(&[])),
-                )
-                .map_err(|err| Rich::custom(span, err))
-            })
+        PureVersion::parser()
     }
 }
@@ -0,0 +1,296 @@
+//! Comparators on partial versions, shared by the dialects of other package managers
+
+use std::{borrow::Cow, num::ParseIntError};
+
+use snafu::{ResultExt, Snafu};
+
+use super::{
+    NumericPart, PureVersion, UInt, VersionLike,
+    prerelease::{InvalidPrerelease, Prerelease},
+};
+use crate::range::{RangeExtreme, Ranges};
+
+/// An operator applied to a version, as used in the requirements of package managers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeOp {
+    /// `=1.2.3`, only the version itself
+    Exact,
+    /// `!=1.2.3`, everything but the version
+    NotEq,
+    /// `>1.2.3`
+    Greater,
+    /// `>=1.2.3`
+    GreaterEq,
+    /// `<1.2.3`
+    Less,
+    /// `<=1.2.3`
+    LessEq,
+    /// `~1.2.3`, patch updates: `>=1.2.3 && <1.3.0-0`
+    Tilde,
+    /// `^1.2.3`, updates not changing the leftmost nonzero part: `>=1.2.3 && <2.0.0-0`
+    Caret,
+}
+
+impl RangeOp {
+    /// Split the leading operator from a comparator, if any
+    pub(crate) fn split(s: &str) -> (Option<RangeOp>, &str) {
+        [
+            (">=", RangeOp::GreaterEq),
+            ("<=", RangeOp::LessEq),
+            (">", RangeOp::Greater),
+            ("<", RangeOp::Less),
+            ("=", RangeOp::Exact),
+            ("~", RangeOp::Tilde),
+            ("^", RangeOp::Caret),
+        ]
+        .into_iter()
+        .find_map(|(prefix, op)| Some((Some(op), s.strip_prefix(prefix)?)))
+        .unwrap_or((None, s))
+    }
+}
+
+/// A version that might miss its trailing numeric parts
+///
+/// Missing parts and wildcards (`*`, `x`, `X`) are equivalent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Partial {
+    major: Option<UInt>,
+    minor: Option<UInt>,
+    patch: Option<UInt>,
+    pre: Vec<Prerelease>,
+    /// Whether a part was an explicit wildcard
+    pub(crate) wildcard: bool,
+}
+
+impl Partial {
+    pub(crate) fn parse(version: &str) -> Result<Self, InvalidComparator> {
+        if version.is_empty() {
+            return MissingVersionSnafu.fail();
+        }
+
+        // Build metadata never influences matching
+        let version = version
+            .split_once('+')
+            .map_or(version, |(version, _)| version);
+        let (core, pre) = match version.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (version, None),
+        };
+
+        let mut parts = core.split('.');
+        let mut wildcard = false;
+        let mut next_part = |part| -> Result<Option<UInt>, InvalidComparator> {
+            match parts.next() {
+                None => Ok(None),
+                Some("*" | "x" | "X") => {
+                    wildcard = true;
+                    Ok(None)
+                }
+                Some(value) => value
+                    .parse()
+                    .map(Some)
+                    .context(InvalidNumericPartSnafu { part, value }),
+            }
+        };
+        let major = next_part(NumericPart::Major)?;
+        let minor = major
+            .map(|_| next_part(NumericPart::Minor))
+            .transpose()?
+            .flatten();
+        let patch = minor
+            .map(|_| next_part(NumericPart::Patch))
+            .transpose()?
+            .flatten();
+        if parts.next().is_some() {
+            return TooManyPartsSnafu { version }.fail();
+        }
+
+        let pre = match pre {
+            Some(pre) if patch.is_some() => pre
+                .split('.')
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .context(InvalidPrereleaseSnafu)?,
+            Some(_) => return PartialPrereleaseSnafu { version }.fail(),
+            None => vec![],
+        };
+
+        Ok(Self {
+            major,
+            minor,
+            patch,
+            pre,
+            wildcard,
+        })
+    }
+
+    /// The versions matched by `op` applied to this partial version
+    pub(crate) fn ranges(self, op: RangeOp) -> Ranges<PureVersion> {
+        if op == RangeOp::NotEq {
+            return self.ranges(RangeOp::Exact).not();
+        }
+        let Some(major) = self.major else {
+            return match op {
+                RangeOp::Greater | RangeOp::Less => Ranges::EMPTY,
+                _ => Ranges::full(),
+            };
+        };
+        let (minor, patch) = (self.minor, self.patch);
+        let lower = PureVersion {
+            major,
+            minor: minor.unwrap_or(0),
+            patch: patch.unwrap_or(0),
+            pre: Cow::Owned(self.pre),
+        };
+        if patch.is_some() {
+            return Ranges::from_operator(op, lower);
+        }
+
+        let next_major = || Some(PureVersion::first_of(major.checked_add(1)?, 0, 0));
+        let next_minor = |minor: UInt| Some(PureVersion::first_of(major, minor.checked_add(1)?, 0));
+
+        match (op, minor) {
+            (RangeOp::Exact | RangeOp::Tilde, Some(minor)) => up_to(lower, next_minor(minor)),
+            (RangeOp::Exact | RangeOp::Tilde | RangeOp::Caret, None) => up_to(lower, next_major()),
+            (RangeOp::Greater, Some(minor)) => {
+                next_minor(minor).map_or(Ranges::EMPTY, Ranges::from)
+            }
+            (RangeOp::Greater, None) => next_major().map_or(Ranges::EMPTY, Ranges::from),
+            (RangeOp::GreaterEq, _) => Ranges::from(lower),
+            (RangeOp::Less, minor) => {
+                Ranges::to(PureVersion::first_of(major, minor.unwrap_or(0), 0))
+            }
+            (RangeOp::LessEq, Some(minor)) => next_minor(minor).map_or(Ranges::full(), Ranges::to),
+            (RangeOp::LessEq, None) => next_major().map_or(Ranges::full(), Ranges::to),
+            (RangeOp::Caret, Some(minor)) => {
+                let upper = match major {
+                    0 => next_minor(minor),
+                    _ => next_major(),
+                };
+                up_to(lower, upper)
+            }
+            (RangeOp::NotEq, _) => unreachable!("Handled above"),
+        }
+    }
+}
+
+impl<T> Ranges<T>
+where
+    T: VersionLike + RangeExtreme,
+{
+    /// The versions matched by `op` applied to `version`
+    ///
+    /// This is the same range the npm and Cargo dialects produce for a comparator
+    /// with a complete version.
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::pure::{PureVersion, comparator::RangeOp}};
+    /// let caret = Ranges::<PureVersion>::from_operator(RangeOp::Caret, "0.2.3".parse().unwrap());
+    /// assert_eq!(caret, Ranges::from_str(">=0.2.3 && <0.3.0-0").unwrap());
+    /// ```
+    pub fn from_operator(op: RangeOp, version: T) -> Self {
+        let (major, minor, patch) = (version.major(), version.minor(), version.patch());
+        let next_major = || Some(T::first_of(major.checked_add(1)?, 0, 0));
+        let next_minor = || Some(T::first_of(major, minor.checked_add(1)?, 0));
+        let next_patch = || Some(T::first_of(major, minor, patch.checked_add(1)?));
+
+        match op {
+            RangeOp::Exact => Ranges::single(version),
+            RangeOp::NotEq => Ranges::except(version),
+            RangeOp::Greater => Ranges::from_exclusive(version),
+            RangeOp::GreaterEq => Ranges::from(version),
+            RangeOp::Less => Ranges::to(version),
+            RangeOp::LessEq => Ranges::to_inclusive(version),
+            RangeOp::Tilde => up_to(version, next_minor()),
+            RangeOp::Caret => {
+                let upper = match (major, minor) {
+                    (0, 0) => next_patch(),
+                    (0, _) => next_minor(),
+                    _ => next_major(),
+                };
+                up_to(version, upper)
+            }
+        }
+    }
+
+    /// The versions compatible with `version`, as `^version`
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::pure::PureVersion};
+    /// let caret = Ranges::caret(PureVersion::new(1, 2, 3));
+    /// assert_eq!(caret, Ranges::from_str(">=1.2.3 && <2.0.0-0").unwrap());
+    /// ```
+    pub fn caret(version: T) -> Self {
+        Self::from_operator(RangeOp::Caret, version)
+    }
+
+    /// The patch updates of `version`, as `~version`
+    pub fn tilde(version: T) -> Self {
+        Self::from_operator(RangeOp::Tilde, version)
+    }
+}
+
+impl Ranges<PureVersion> {
+    /// The smallest caret requirement matching all the versions this set matches
+    ///
+    /// Returns the base of the caret and its range, or `None` if the set is empty
+    /// or spans more than one compatibility line.
+    /// Versions are matched following the default [`PrereleasePolicy`](super::PrereleasePolicy),
+    /// so `>=1.2.0 && <2.0.0` widens to `^1.2.0` even if the latter
+    /// excludes the prereleases of `2.0.0`.
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::pure::PureVersion};
+    /// let req = Ranges::<PureVersion>::from_str(">=1.4.2 && <1.7.0").unwrap();
+    /// let (base, _) = req.widen_to_caret().unwrap();
+    /// assert_eq!(base.to_string(), "1.4.2");
+    /// ```
+    pub fn widen_to_caret(&self) -> Option<(PureVersion, Self)> {
+        self.widen_to(RangeOp::Caret)
+    }
+
+    /// The smallest tilde requirement matching all the versions this set matches
+    ///
+    /// Same as [`Ranges::widen_to_caret`], for tilde requirements.
+    pub fn widen_to_tilde(&self) -> Option<(PureVersion, Self)> {
+        self.widen_to(RangeOp::Tilde)
+    }
+
+    fn widen_to(&self, op: RangeOp) -> Option<(PureVersion, Self)> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut base = self.lowest().unwrap_or(&PureVersion::MIN).clone();
+        if *base.pre == [Prerelease::MIN] {
+            base.pre = Cow::Borrowed(&[]);
+        }
+        let widened = Self::from_operator(op, base.clone());
+        let excess = self.clone().and(&widened.clone().not());
+        excess.min_stability().is_none().then_some((base, widened))
+    }
+}
+
+fn up_to<T: RangeExtreme>(lower: T, upper: Option<T>) -> Ranges<T> {
+    match upper {
+        Some(upper) => Ranges::between(lower, upper),
+        None => Ranges::from(lower),
+    }
+}
+
+#[derive(Debug, Clone, Snafu)]
+pub enum InvalidComparator {
+    #[snafu(display("The version is missing"))]
+    MissingVersion,
+    #[snafu(display("Invalid {part} version: `{value}`"))]
+    InvalidNumericPart {
+        part: NumericPart,
+        value: String,
+        source: ParseIntError,
+    },
+    #[snafu(display("Too many numeric parts in `{version}`"))]
+    TooManyParts { version: String },
+    #[snafu(display("A prerelease needs a full version: `{version}`"))]
+    PartialPrerelease { version: String },
+    #[snafu(display("Invalid prerelease"))]
+    InvalidPrerelease { source: InvalidPrerelease },
+}
@@ -0,0 +1,147 @@
+//! Explanations of why a version is inside a range set or not
+
+use std::{fmt::Display, ops::Bound};
+
+use super::{PureVersion, VersionChange};
+use crate::range::{Range, Ranges};
+
+#[cfg(test)]
+mod tests;
+
+/// Why a version is or is not inside a range set, see [`Ranges::explain`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)]
+pub enum MatchExplanation {
+    /// The version is inside `interval`
+    Matches {
+        version: PureVersion,
+        interval: Range<PureVersion>,
+    },
+    /// The version is outside the set
+    Mismatch {
+        version: PureVersion,
+        /// The nearest interval below the version, if any
+        below: Option<Nearest>,
+        /// The nearest interval above the version, if any
+        above: Option<Nearest>,
+    },
+}
+
+/// An interval of the set that does not contain the version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nearest {
+    pub interval: Range<PureVersion>,
+    /// The extreme of the interval facing the version
+    ///
+    /// For intervals below the version this is the excluded end, for intervals above it is the start.
+    pub bound: PureVersion,
+    /// How far is the version from `bound`
+    pub distance: VersionChange,
+}
+
+impl MatchExplanation {
+    pub fn is_match(&self) -> bool {
+        matches!(self, Self::Matches { .. })
+    }
+}
+
+impl Ranges<PureVersion> {
+    /// Explain why `version` is or is not inside the set
+    ///
+    /// Prereleases are treated like any other version, as in [`Ranges::contains`].
+    /// The set does not remember the constraints it was built from, so the explanation
+    /// is given in terms of its intervals.
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::pure::PureVersion};
+    /// let req = Ranges::from_str("<1.0.0 || >=2.0.0").unwrap();
+    /// let explanation = req.explain(&"1.9.2".parse::<PureVersion>().unwrap());
+    /// assert_eq!(
+    ///     explanation.to_string(),
+    ///     "1.9.2 does not match: the nearest intervals are `<1.0.0` below it (minor change) \
+    ///      and `>=2.0.0` above it (major change)"
+    /// );
+    /// ```
+    pub fn explain(&self, version: &PureVersion) -> MatchExplanation {
+        let interval = |start: Option<&PureVersion>, end: Option<&PureVersion>| {
+            Range::from_bounds(
+                start.map_or(Bound::Unbounded, |start| Bound::Included(start.clone())),
+                end.map_or(Bound::Unbounded, |end| Bound::Excluded(end.clone())),
+            )
+        };
+
+        let mut below = None;
+        for (start, end) in self.intervals() {
+            if start.is_some_and(|start| version < start) {
+                let start = start.expect("Checked above");
+                return MatchExplanation::Mismatch {
+                    version: version.clone(),
+                    below,
+                    above: Some(Nearest {
+                        interval: interval(Some(start), end),
+                        bound: start.clone(),
+                        distance: version.diff(start),
+                    }),
+                };
+            }
+            match end {
+                Some(end) if end <= version => {
+                    below = Some(Nearest {
+                        interval: interval(start, Some(end)),
+                        bound: end.clone(),
+                        distance: version.diff(end),
+                    })
+                }
+                _ => {
+                    return MatchExplanation::Matches {
+                        version: version.clone(),
+                        interval: interval(start, end),
+                    };
+                }
+            }
+        }
+        MatchExplanation::Mismatch {
+            version: version.clone(),
+            below,
+            above: None,
+        }
+    }
+}
+
+impl Display for MatchExplanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchExplanation::Matches { version, interval } => {
+                write!(f, "{version} matches, as it is inside `{interval}`")
+            }
+            MatchExplanation::Mismatch {
+                version,
+                below,
+                above,
+            } => {
+                write!(f, "{version} does not match: ")?;
+                let side = |nearest: &Nearest, side| match nearest.distance {
+                    VersionChange::None => {
+                        format!("`{}` {side} it (at its excluded end)", nearest.interval)
+                    }
+                    distance => format!("`{}` {side} it ({distance} change)", nearest.interval),
+                };
+                match (below, above) {
+                    (None, None) => write!(f, "no version is allowed"),
+                    (Some(below), None) => {
+                        write!(f, "the nearest interval is {}", side(below, "below"))
+                    }
+                    (None, Some(above)) => {
+                        write!(f, "the nearest interval is {}", side(above, "above"))
+                    }
+                    (Some(below), Some(above)) => write!(
+                        f,
+                        "the nearest intervals are {} and {}",
+                        side(below, "below"),
+                        side(above, "above")
+                    ),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use super::{MatchExplanation, Nearest};
+use crate::{
+    range::{Range, Ranges},
+    version::pure::{PureVersion, VersionChange},
+};
+
+fn v(s: &str) -> PureVersion {
+    s.parse().unwrap()
+}
+
+fn disjoint() -> Ranges<PureVersion> {
+    Ranges::from_str(">=1.0.0 && <1.2.0 || >=1.4.0 && <2.0.0 || >=3.0.0 && <3.1.0").unwrap()
+}
+
+#[test]
+fn inside() {
+    let explanation = disjoint().explain(&v("1.5.0"));
+    assert!(explanation.is_match());
+    assert_eq!(
+        explanation,
+        MatchExplanation::Matches {
+            version: v("1.5.0"),
+            interval: Range::between(v("1.4.0"), v("2.0.0")),
+        }
+    );
+    assert_eq!(
+        explanation.to_string(),
+        "1.5.0 matches, as it is inside `>=1.4.0 && <2.0.0`"
+    );
+}
+
+#[test]
+fn below_all() {
+    let explanation = disjoint().explain(&v("0.9.0"));
+    assert_eq!(
+        explanation,
+        MatchExplanation::Mismatch {
+            version: v("0.9.0"),
+            below: None,
+            above: Some(Nearest {
+                interval: Range::between(v("1.0.0"), v("1.2.0")),
+                bound: v("1.0.0"),
+                distance: VersionChange::Major,
+            }),
+        }
+    );
+    assert_eq!(
+        explanation.to_string(),
+        "0.9.0 does not match: the nearest interval is `>=1.0.0 && <1.2.0` above it (major change)"
+    );
+}
+
+#[test]
+fn between() {
+    let explanation = disjoint().explain(&v("1.2.0"));
+    assert_eq!(
+        explanation,
+        MatchExplanation::Mismatch {
+            version: v("1.2.0"),
+            below: Some(Nearest {
+                interval: Range::between(v("1.0.0"), v("1.2.0")),
+                bound: v("1.2.0"),
+                distance: VersionChange::None,
+            }),
+            above: Some(Nearest {
+                interval: Range::between(v("1.4.0"), v("2.0.0")),
+                bound: v("1.4.0"),
+                distance: VersionChange::Minor,
+            }),
+        }
+    );
+
+    let explanation = disjoint().explain(&v("2.5.0"));
+    assert_eq!(
+        explanation.to_string(),
+        "2.5.0 does not match: the nearest intervals are `>=1.4.0 && <2.0.0` below it (minor change) \
+         and `>=3.0.0 && <3.1.0` above it (major change)"
+    );
+}
+
+#[test]
+fn above_all() {
+    let explanation = disjoint().explain(&v("3.1.0"));
+    assert_eq!(
+        explanation.to_string(),
+        "3.1.0 does not match: the nearest interval is `>=3.0.0 && <3.1.0` below it (at its excluded end)"
+    );
+}
+
+#[test]
+fn empty_set() {
+    assert_eq!(
+        Ranges::EMPTY.explain(&v("1.0.0")).to_string(),
+        "1.0.0 does not match: no version is allowed"
+    );
+}
@@ -0,0 +1,94 @@
+//! Abstraction over types carrying a semantic version
+
+use std::borrow::Cow;
+
+use super::{PureVersion, UInt, prerelease::Prerelease};
+use crate::version::Version;
+
+#[cfg(test)]
+mod tests;
+
+/// A type made of a semantic version core and prerelease identifiers
+///
+/// Higher level helpers, like [`Ranges::from_operator`](crate::range::Ranges::from_operator)
+/// and [`Ranges::split_by_major`](crate::range::Ranges::split_by_major), are generic over
+/// this trait, so they work on wrappers of [`PureVersion`] without converting back and forth.
+///
+/// Implementors must uphold these laws:
+///  - the accessors of `Self::from_semver(major, minor, patch, pre)` return the given parts;
+///  - if `Self` is ordered, `a.cmp(b)` is the semantic versioning precedence of the parts,
+///    that is the ordering of the corresponding [`PureVersion`]s, and equal parts mean equal values.
+///
+/// The helpers build bounds like `2.0.0-0` through [`VersionLike::from_semver`],
+/// so every combination of parts must be representable.
+pub trait VersionLike {
+    fn major(&self) -> UInt;
+    fn minor(&self) -> UInt;
+    fn patch(&self) -> UInt;
+    /// Prerelease identifiers, empty for a release
+    fn pre(&self) -> &[Prerelease];
+
+    /// Build a value from its parts
+    fn from_semver(major: UInt, minor: UInt, patch: UInt, pre: Cow<'static, [Prerelease]>) -> Self;
+
+    /// The lowest version with the given core, preceding all its prereleases
+    fn first_of(major: UInt, minor: UInt, patch: UInt) -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_semver(major, minor, patch, Cow::Owned(vec![Prerelease::MIN]))
+    }
+}
+
+impl VersionLike for PureVersion {
+    fn major(&self) -> UInt {
+        self.major
+    }
+
+    fn minor(&self) -> UInt {
+        self.minor
+    }
+
+    fn patch(&self) -> UInt {
+        self.patch
+    }
+
+    fn pre(&self) -> &[Prerelease] {
+        &self.pre
+    }
+
+    fn from_semver(major: UInt, minor: UInt, patch: UInt, pre: Cow<'static, [Prerelease]>) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre,
+        }
+    }
+}
+
+/// Build metadata is not a part: it is dropped by the accessors, and empty in built values
+impl VersionLike for Version {
+    fn major(&self) -> UInt {
+        self.pure.major
+    }
+
+    fn minor(&self) -> UInt {
+        self.pure.minor
+    }
+
+    fn patch(&self) -> UInt {
+        self.pure.patch
+    }
+
+    fn pre(&self) -> &[Prerelease] {
+        &self.pure.pre
+    }
+
+    fn from_semver(major: UInt, minor: UInt, patch: UInt, pre: Cow<'static, [Prerelease]>) -> Self {
+        Self {
+            pure: PureVersion::from_semver(major, minor, patch, pre),
+            build: vec![],
+        }
+    }
+}
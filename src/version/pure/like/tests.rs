@@ -0,0 +1,112 @@
+use std::borrow::Cow;
+
+use super::VersionLike;
+use crate::{
+    range::{RangeExtreme, Ranges},
+    version::{
+        Version,
+        pure::{PureVersion, UInt, comparator::RangeOp, prerelease::Prerelease},
+    },
+};
+
+/// A version of a package that was published, wrapping a `PureVersion`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Published(PureVersion);
+
+impl VersionLike for Published {
+    fn major(&self) -> UInt {
+        self.0.major
+    }
+
+    fn minor(&self) -> UInt {
+        self.0.minor
+    }
+
+    fn patch(&self) -> UInt {
+        self.0.patch
+    }
+
+    fn pre(&self) -> &[Prerelease] {
+        &self.0.pre
+    }
+
+    fn from_semver(major: UInt, minor: UInt, patch: UInt, pre: Cow<'static, [Prerelease]>) -> Self {
+        Self(PureVersion::from_semver(major, minor, patch, pre))
+    }
+}
+
+impl RangeExtreme for Published {
+    fn minimum() -> Option<Self> {
+        PureVersion::minimum().map(Self)
+    }
+
+    fn next(self) -> Option<Self> {
+        self.0.next().map(Self)
+    }
+}
+
+fn published(s: &str) -> Published {
+    Published(s.parse().unwrap())
+}
+
+fn wrap(r: Ranges<PureVersion>) -> Ranges<Published> {
+    r.map(Published)
+}
+
+#[test]
+fn caret_on_a_newtype() {
+    let caret = Ranges::caret(published("1.2.3"));
+    assert_eq!(
+        caret,
+        wrap(Ranges::from_str(">=1.2.3 && <2.0.0-0").unwrap())
+    );
+    assert!(caret.contains(&published("1.9.0")));
+    assert!(!caret.contains(&published("2.0.0")));
+
+    assert_eq!(
+        Ranges::caret(published("0.2.3")),
+        wrap(Ranges::from_str(">=0.2.3 && <0.3.0-0").unwrap())
+    );
+    assert_eq!(
+        Ranges::tilde(published("1.2.3")),
+        wrap(Ranges::from_operator(
+            RangeOp::Tilde,
+            PureVersion::new(1, 2, 3)
+        ))
+    );
+}
+
+#[test]
+fn split_by_major_on_a_newtype() {
+    let range = wrap(Ranges::from_str(">=1.2.0 && <2.5.0").unwrap());
+    assert!(range.majors_touched().eq([1, 2]));
+    let split = range.split_by_major();
+    assert_eq!(
+        split[&1],
+        wrap(Ranges::from_str(">=1.2.0 && <2.0.0-0").unwrap())
+    );
+    assert_eq!(
+        split[&2],
+        wrap(Ranges::from_str(">=2.0.0-0 && <2.5.0").unwrap())
+    );
+}
+
+#[test]
+fn parts_round_trip() {
+    let pure: PureVersion = "1.2.3-rc.1".parse().unwrap();
+    let rebuilt = PureVersion::from_semver(
+        pure.major(),
+        pure.minor(),
+        pure.patch(),
+        pure.pre().to_vec().into(),
+    );
+    assert_eq!(rebuilt, pure);
+
+    let version: Version = "1.2.3-rc.1+build".parse().unwrap();
+    assert_eq!(version.pre(), &*pure.pre);
+    let rebuilt = <Version as VersionLike>::from_semver(1, 2, 3, version.pre().to_vec().into());
+    assert_eq!(rebuilt.pure, pure);
+    assert!(rebuilt.build.is_empty());
+
+    assert_eq!(PureVersion::first_of(2, 0, 0).to_string(), "2.0.0-0");
+}
@@ -0,0 +1,104 @@
+//! Partition of range sets by major version
+
+use std::collections::BTreeMap;
+
+use super::{UInt, VersionLike};
+use crate::range::{RangeExtreme, Ranges};
+
+#[cfg(test)]
+mod tests;
+
+/// The lowest version of the major line `major`, `major.0.0-0`
+fn major_floor<T: VersionLike>(major: UInt) -> T {
+    T::first_of(major, 0, 0)
+}
+
+impl<T> Ranges<T>
+where
+    T: VersionLike + RangeExtreme,
+{
+    /// Iterate over the major versions with at least a version inside the set, in ascending order
+    ///
+    /// Prereleases count: `<2.0.0` touches the major 2, as it contains `2.0.0-alpha`.
+    /// If the set is unbounded above, the iteration stops at the major of the last finite
+    /// extreme: every major above it is touched too, as reported by
+    /// [`Ranges::every_major_from`].
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// # use areq::version::pure::PureVersion;
+    /// let range = Ranges::<PureVersion>::from_str("<1.0.0-0 || >=3.2.0 && <5.0.0").unwrap();
+    /// assert!(range.majors_touched().eq([0, 3, 4, 5]));
+    /// ```
+    pub fn majors_touched(&self) -> impl Iterator<Item = UInt> + '_ {
+        let mut last: Option<UInt> = None;
+        self.intervals()
+            .filter_map(move |(start, end)| {
+                let first = start.map_or(0, |start| start.major());
+                let end = match end {
+                    // `end` is excluded, so its major is touched only if something is below it
+                    Some(end) if *end == major_floor(end.major()) => end.major().checked_sub(1)?,
+                    Some(end) => end.major(),
+                    None => first,
+                };
+                // Intervals can share a major with the previous one
+                let first = match last {
+                    Some(last) if last >= first => last.checked_add(1)?,
+                    _ => first,
+                };
+                last = Some(last.map_or(end, |last| last.max(end)));
+                Some(first..=end)
+            })
+            .flatten()
+    }
+
+    /// The major from which every major version is touched, if the set is unbounded above
+    ///
+    /// It is the last major returned by [`Ranges::majors_touched`], that stops there.
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// # use areq::version::pure::PureVersion;
+    /// let range = Ranges::<PureVersion>::from_str("<1.0.0 || >=3.2.0").unwrap();
+    /// assert_eq!(range.every_major_from(), Some(3));
+    /// let range = Ranges::<PureVersion>::from_str(">=3.2.0 && <5.0.0").unwrap();
+    /// assert_eq!(range.every_major_from(), None);
+    /// ```
+    pub fn every_major_from(&self) -> Option<UInt> {
+        match self.intervals().last()? {
+            (start, None) => Some(start.map_or(0, |start| start.major())),
+            (_, Some(_)) => None,
+        }
+    }
+
+    /// Split the set into the parts belonging to each major line `[m.0.0-0, (m+1).0.0-0)`
+    ///
+    /// Majors with no version inside the set are omitted. If the set is unbounded above,
+    /// the entry with the highest major also contains every version above it: the map
+    /// is finite, and is keyed as [`Ranges::majors_touched`].
+    ///
+    /// ```
+    /// # use areq::range::Ranges;
+    /// # use areq::version::pure::PureVersion;
+    /// let range = Ranges::<PureVersion>::from_str(">=1.2.0 && <2.5.0 || >=4.0.0").unwrap();
+    /// let split = range.split_by_major();
+    /// assert_eq!(split[&1], Ranges::from_str(">=1.2.0 && <2.0.0-0").unwrap());
+    /// assert_eq!(split[&2], Ranges::from_str(">=2.0.0-0 && <2.5.0").unwrap());
+    /// assert_eq!(split[&4], Ranges::from_str(">=4.0.0").unwrap());
+    /// assert_eq!(split.len(), 3);
+    /// ```
+    pub fn split_by_major(&self) -> BTreeMap<UInt, Self> {
+        let mut majors = self.majors_touched().peekable();
+        let mut split = BTreeMap::new();
+        while let Some(major) = majors.next() {
+            let line = match major.checked_add(1) {
+                Some(next) if majors.peek().is_some() || self.highest().is_some() => {
+                    Ranges::between(major_floor(major), major_floor(next))
+                }
+                _ => Ranges::from(major_floor(major)),
+            };
+            split.insert(major, self.clone().and(&line));
+        }
+        split
+    }
+}
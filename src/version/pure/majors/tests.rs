@@ -0,0 +1,75 @@
+use crate::{range::Ranges, version::pure::PureVersion};
+
+fn r(s: &str) -> Ranges<PureVersion> {
+    Ranges::from_str(s).unwrap()
+}
+
+#[test]
+fn majors_with_holes() {
+    let range = r(">=0.3.0 && <1.0.0-0 || >=1.5.0 && <1.6.0 \
+        || >=1.8.0 && <3.1.0 || >=5.0.0 && <6.0.0-0");
+    assert!(range.majors_touched().eq([0, 1, 2, 3, 5]));
+
+    let split = range.split_by_major();
+    assert!(split.keys().copied().eq([0, 1, 2, 3, 5]));
+    assert_eq!(split[&0], r(">=0.3.0 && <1.0.0-0"));
+    assert_eq!(split[&1], r(">=1.5.0 && <1.6.0 || >=1.8.0 && <2.0.0-0"));
+    assert_eq!(split[&2], r(">=2.0.0-0 && <3.0.0-0"));
+    assert_eq!(split[&3], r(">=3.0.0-0 && <3.1.0"));
+    assert_eq!(split[&5], r(">=5.0.0 && <6.0.0-0"));
+
+    let union = split
+        .values()
+        .fold(Ranges::EMPTY, |union, part| union.or(part));
+    assert_eq!(union, range);
+}
+
+#[test]
+fn prereleases_touch_the_next_major() {
+    let range = r(">=1.2.0 && <2.0.0");
+    assert!(range.majors_touched().eq([1, 2]));
+    assert_eq!(range.split_by_major()[&2], r(">=2.0.0-0 && <2.0.0"));
+}
+
+#[test]
+fn unbounded() {
+    let range = r("<1.2.0 || >=3.4.0");
+    assert!(range.majors_touched().eq([0, 1, 3]));
+    let split = range.split_by_major();
+    assert_eq!(split[&0], r("<1.0.0-0"));
+    assert_eq!(split[&1], r(">=1.0.0-0 && <1.2.0"));
+    assert_eq!(split[&3], r(">=3.4.0"));
+    assert_eq!(range.every_major_from(), Some(3));
+
+    let full = Ranges::<PureVersion>::full().split_by_major();
+    assert_eq!(full.len(), 1);
+    assert!(full[&0].is_full());
+    assert_eq!(Ranges::<PureVersion>::full().every_major_from(), Some(0));
+}
+
+#[test]
+fn bounded_sets_do_not_touch_every_major() {
+    assert_eq!(r(">=1.2.0 && <3.0.0").every_major_from(), None);
+    assert_eq!(Ranges::<PureVersion>::EMPTY.every_major_from(), None);
+}
+
+#[test]
+fn highest_major() {
+    let range = r(">=18446744073709551615.1.0 && <18446744073709551615.2.0 \
+        || >=18446744073709551615.3.0");
+    assert!(range.majors_touched().eq([u64::MAX]));
+    assert_eq!(range.every_major_from(), Some(u64::MAX));
+    let split = range.split_by_major();
+    assert_eq!(split.len(), 1);
+    assert_eq!(split[&u64::MAX], range);
+
+    let range = r(">=18446744073709551614.1.0 && <18446744073709551615.2.0 \
+        || >=18446744073709551615.3.0 && <18446744073709551615.4.0");
+    assert!(range.majors_touched().eq([u64::MAX - 1, u64::MAX]));
+}
+
+#[test]
+fn empty() {
+    assert_eq!(Ranges::<PureVersion>::EMPTY.majors_touched().count(), 0);
+    assert!(Ranges::<PureVersion>::EMPTY.split_by_major().is_empty());
+}
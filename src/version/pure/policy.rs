@@ -0,0 +1,103 @@
+//! Range sets carrying the prerelease policy used to match them
+
+use super::{PrereleasePolicy, PureVersion};
+use crate::range::Ranges;
+
+#[cfg(test)]
+mod tests;
+
+/// A set of requirements, each matched with its own prerelease policy
+///
+/// Combining two sets keeps every requirement with its policy, so a version is checked
+/// against each of them:
+///  - [`PolicyRanges::and`] matches a version only if both sides would accept it,
+///  - [`PolicyRanges::or`] matches a version if either side would accept it.
+///
+/// Merging the requirements into a single range set with a single policy would not work,
+/// as [`PrereleasePolicy::Explicit`] depends on the extremes of each requirement.
+///
+/// Equality is structural: two sets built from different requirements are different,
+/// even if they match the same versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyRanges {
+    /// Alternatives, each matching a version if all its requirements match it
+    alternatives: Vec<Vec<(Ranges<PureVersion>, PrereleasePolicy)>>,
+}
+
+impl PolicyRanges {
+    pub fn new(ranges: Ranges<PureVersion>, policy: PrereleasePolicy) -> Self {
+        Self {
+            alternatives: vec![vec![(ranges, policy)]],
+        }
+    }
+
+    /// Return whether `version` is inside the set, following the policy of each requirement
+    pub fn contains(&self, version: &PureVersion) -> bool {
+        self.alternatives.iter().any(|requirements| {
+            requirements
+                .iter()
+                .all(|(ranges, policy)| ranges.contains_with_policy(version, *policy))
+        })
+    }
+
+    /// The versions inside the set if every prerelease was matched
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::pure::{PrereleasePolicy, policy::PolicyRanges}};
+    /// let nightly = PolicyRanges::new(Ranges::from_str(">=1.0.0").unwrap(), PrereleasePolicy::Include);
+    /// let stable = PolicyRanges::new(Ranges::from_str("<2.0.0").unwrap(), PrereleasePolicy::Exclude);
+    /// assert_eq!(nightly.and(&stable).ranges(), Ranges::from_str(">=1.0.0 && <2.0.0").unwrap());
+    /// ```
+    pub fn ranges(&self) -> Ranges<PureVersion> {
+        self.alternatives
+            .iter()
+            .map(|requirements| {
+                requirements
+                    .iter()
+                    .fold(Ranges::full(), |all, (ranges, _)| all.and(ranges))
+            })
+            .fold(Ranges::EMPTY, |any, ranges| any.or(&ranges))
+    }
+
+    /// Intersection of the two sets, matching a version only if both sides match it
+    ///
+    /// Each alternative of one side is paired with each alternative of the other, so the
+    /// number of alternatives is the product of the two: intersecting many unions grows
+    /// the set quickly.
+    ///
+    /// ```
+    /// # use areq::{range::Ranges, version::pure::{PrereleasePolicy, policy::PolicyRanges}};
+    /// let nightly = PolicyRanges::new(Ranges::from_str(">=1.0.0").unwrap(), PrereleasePolicy::Include);
+    /// let stable = PolicyRanges::new(Ranges::from_str("<2.0.0").unwrap(), PrereleasePolicy::Explicit);
+    /// let both = nightly.and(&stable);
+    /// assert!(!both.contains(&"1.5.0-beta".parse().unwrap()));
+    /// assert!(both.contains(&"1.5.0".parse().unwrap()));
+    /// ```
+    pub fn and(self, other: &Self) -> Self {
+        Self {
+            alternatives: self
+                .alternatives
+                .iter()
+                .flat_map(|a| {
+                    other
+                        .alternatives
+                        .iter()
+                        .map(move |b| a.iter().chain(b).cloned().collect())
+                })
+                .collect(),
+        }
+    }
+
+    /// Union of the two sets, matching a version if either side matches it
+    pub fn or(mut self, other: &Self) -> Self {
+        self.alternatives.extend(other.alternatives.iter().cloned());
+        self
+    }
+}
+
+impl From<Ranges<PureVersion>> for PolicyRanges {
+    /// Use the default policy
+    fn from(ranges: Ranges<PureVersion>) -> Self {
+        Self::new(ranges, PrereleasePolicy::default())
+    }
+}
@@ -0,0 +1,89 @@
+use super::PolicyRanges;
+use crate::{
+    range::Ranges,
+    version::pure::{PrereleasePolicy, PureVersion},
+};
+
+fn p(s: &str, policy: PrereleasePolicy) -> PolicyRanges {
+    PolicyRanges::new(Ranges::from_str(s).unwrap(), policy)
+}
+
+fn v(s: &str) -> PureVersion {
+    s.parse().unwrap()
+}
+
+#[test]
+fn and_requires_both_policies() {
+    let candidate = v("1.5.0-beta.2");
+    let nightly = p(">=1.0.0", PrereleasePolicy::Include);
+    let stable = p("<2.0.0", PrereleasePolicy::Explicit);
+    assert!(nightly.contains(&candidate));
+    assert!(!stable.contains(&candidate));
+
+    let both = nightly.clone().and(&stable);
+    assert!(!both.contains(&candidate));
+    assert!(both.contains(&v("1.5.0")));
+    assert_eq!(
+        both.ranges(),
+        Ranges::from_str(">=1.0.0 && <2.0.0").unwrap()
+    );
+
+    // Explicitly requesting the prerelease is still honored
+    let beta = p(">=1.5.0-beta.1", PrereleasePolicy::Explicit);
+    assert!(nightly.and(&beta).contains(&candidate));
+}
+
+#[test]
+fn and_checks_explicit_prereleases_per_side() {
+    let candidate = v("1.5.0-beta");
+    let alpha = p(">=1.5.0-alpha", PrereleasePolicy::Include);
+    let stable = p(">=1.0.0", PrereleasePolicy::Explicit);
+    assert!(alpha.contains(&candidate));
+    assert!(!stable.contains(&candidate));
+
+    assert!(!alpha.clone().and(&stable).contains(&candidate));
+    assert!(!stable.and(&alpha).contains(&candidate));
+}
+
+#[test]
+fn or_accepts_either_policy() {
+    let candidate = v("3.1.0-rc.1");
+    let nightly = p(">=3.0.0", PrereleasePolicy::Include);
+    let stable = p("<2.0.0", PrereleasePolicy::Exclude);
+
+    let either = stable.or(&nightly);
+    assert!(either.contains(&candidate));
+    assert!(either.contains(&v("1.0.0")));
+    assert!(!either.contains(&v("2.5.0")));
+}
+
+#[test]
+fn or_does_not_extend_policies_to_the_other_side() {
+    let stable = p("<2.0.0", PrereleasePolicy::Exclude);
+    let nightly = p(">=3.0.0", PrereleasePolicy::Include);
+
+    let either = stable.or(&nightly);
+    assert!(!either.contains(&v("1.5.0-beta")));
+    assert!(either.contains(&v("3.5.0-beta")));
+}
+
+#[test]
+fn and_distributes_over_or() {
+    let either =
+        p("<2.0.0", PrereleasePolicy::Exclude).or(&p(">=3.0.0", PrereleasePolicy::Include));
+    let both = either.and(&p(">=1.0.0 && <4.0.0", PrereleasePolicy::Include));
+
+    assert!(both.contains(&v("1.5.0")));
+    assert!(!both.contains(&v("1.5.0-beta")));
+    assert!(both.contains(&v("3.5.0-beta")));
+    assert!(!both.contains(&v("0.5.0")));
+    assert!(!both.contains(&v("4.5.0")));
+}
+
+#[test]
+fn default_policy() {
+    let from: PolicyRanges = Ranges::from_str(">=1.0.0").unwrap().into();
+    assert!(!from.contains(&v("1.5.0-beta")));
+    let from: PolicyRanges = Ranges::from_str(">=1.5.0-alpha").unwrap().into();
+    assert!(from.contains(&v("1.5.0-beta")));
+}
@@ -12,7 +12,10 @@ use lazy_regex::regex_switch;
 use num_bigint::BigUint;
 use snafu::Snafu;
 
-use crate::range::ParserExtra;
+use crate::{range::ParserExtra, version::ParseOptions};
+
+#[cfg(test)]
+mod tests;
 
 /// An identifier for a pre-release
 #[derive(DebugDerive, Clone, PartialEq, Eq, Hash, IsVariant, PartialOrd, Ord, DisplayDerive)]
@@ -24,10 +27,31 @@ pub enum Prerelease {
 impl Prerelease {
     pub const MIN: Self = Self::Numeric(NumericPrerelease::MIN);
 
-    pub(crate) fn parser<'a>() -> impl chumsky::Parser<'a, &'a str, Self, ParserExtra<'a>> + Clone {
+    /// Parse an identifier, with the given limit on the digits of numeric ones
+    pub(crate) fn parse_limited(s: &str, max_digits: usize) -> Result<Self, InvalidPrerelease> {
+        regex_switch!(
+         s,
+         r"^(?:0|[1-9]\d*)$" => NumericPrerelease::parse_limited(s, max_digits).map(Prerelease::Numeric),
+         r"^\d*[a-zA-Z-][0-9a-zA-Z-]*$" => Ok(Prerelease::Alpha(AlphaPrerelease::new(s)))
+        )
+        .unwrap_or_else(|| Err(debug_invalid_identifier(s)))
+    }
+
+    /// Parser for a single prerelease identifier
+    ///
+    /// See [`PureVersion::parser`](super::PureVersion::parser) for the stability guarantees.
+    pub fn parser<'a>() -> impl chumsky::Parser<'a, &'a str, Self, ParserExtra<'a>> + Clone {
         text::int(10)
             .to_slice()
-            .map(|s: &str| Prerelease::Numeric(NumericPrerelease(s.parse().unwrap())))
+            // Reporting the error without failing, so it is not lost by backtracking
+            .validate(|s: &str, e, emitter| {
+                NumericPrerelease::parse_limited(s, ParseOptions::DEFAULT.max_prerelease_digits)
+                    .unwrap_or_else(|err| {
+                        emitter.emit(Rich::custom(e.span(), err));
+                        NumericPrerelease::MIN
+                    })
+            })
+            .map(Prerelease::Numeric)
             .or(digits(10)
                 .or_not()
                 .ignored()
@@ -63,7 +87,7 @@ impl Prerelease {
                         .repeated(),
                 )
                 .to_slice()
-                .map(|s: &str| Prerelease::Alpha(AlphaPrerelease(s.to_owned()))))
+                .map(|s: &str| Prerelease::Alpha(AlphaPrerelease::new(s))))
     }
 }
 
@@ -73,25 +97,155 @@ pub struct NumericPrerelease(BigUint);
 
 impl NumericPrerelease {
     pub const MIN: Self = Self(BigUint::ZERO);
+
+    /// Parse a valid numeric identifier, checking it against the limit of digits
+    fn parse_limited(s: &str, limit: usize) -> Result<Self, InvalidPrerelease> {
+        if s.len() > limit {
+            return Err(InvalidPrerelease::NumericTooLong {
+                digits: s.len(),
+                limit,
+            });
+        }
+        Ok(Self(
+            s.parse()
+                .expect("The conversion to BigUint should be infallible"),
+        ))
+    }
+
+    /// The identifier incremented by one
+    pub(crate) fn incremented(&self) -> Self {
+        Self(&self.0 + 1u8)
+    }
+
+    /// Big-endian bytes of the value, without leading zeros
+    pub(crate) fn to_bytes_be(&self) -> Vec<u8> {
+        self.0.to_bytes_be()
+    }
+
+    /// Little-endian bytes of the value, as used by the compact serde encoding
+    #[cfg(feature = "serde")]
+    pub(crate) fn to_bytes_le(&self) -> Vec<u8> {
+        self.0.to_bytes_le()
+    }
+
+    /// Value from little-endian bytes, checking it against the limit of digits
+    ///
+    /// Callers should bound the length of `bytes` first, as counting the digits
+    /// takes quadratic time.
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_bytes_le_limited(
+        bytes: &[u8],
+        limit: usize,
+    ) -> Result<Self, InvalidPrerelease> {
+        let len = bytes.iter().rposition(|&b| b != 0).map_or(0, |idx| idx + 1);
+        let value = BigUint::from_bytes_le(&bytes[..len]);
+        let digits = value.to_str_radix(10).len();
+        if digits > limit {
+            return Err(InvalidPrerelease::NumericTooLong { digits, limit });
+        }
+        Ok(Self(value))
+    }
 }
 
-#[derive(DebugDerive, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, DisplayDerive)]
-#[repr(transparent)]
-pub struct AlphaPrerelease(String);
+/// An alphanumeric prerelease identifier
+///
+/// The most common identifiers are stored as tags, so comparing them does not
+/// need to compare strings. The ordering is still the ASCII ordering of the identifiers.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct AlphaPrerelease(AlphaRepr);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum AlphaRepr {
+    Tag(CommonTag),
+    Other(String),
+}
+
+/// Ubiquitous identifiers, declared in ASCII order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum CommonTag {
+    Alpha,
+    Beta,
+    Dev,
+    Nightly,
+    Pre,
+    Rc,
+}
+
+impl CommonTag {
+    const ALL: [Self; 6] = [
+        Self::Alpha,
+        Self::Beta,
+        Self::Dev,
+        Self::Nightly,
+        Self::Pre,
+        Self::Rc,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Alpha => "alpha",
+            Self::Beta => "beta",
+            Self::Dev => "dev",
+            Self::Nightly => "nightly",
+            Self::Pre => "pre",
+            Self::Rc => "rc",
+        }
+    }
+}
+
+impl AlphaPrerelease {
+    fn new(s: &str) -> Self {
+        Self(
+            match CommonTag::ALL.into_iter().find(|tag| tag.as_str() == s) {
+                Some(tag) => AlphaRepr::Tag(tag),
+                None => AlphaRepr::Other(s.to_owned()),
+            },
+        )
+    }
+
+    /// The identifier as a string
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            AlphaRepr::Tag(tag) => tag.as_str(),
+            AlphaRepr::Other(s) => s,
+        }
+    }
+}
+
+impl Ord for AlphaPrerelease {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (&self.0, &other.0) {
+            (AlphaRepr::Tag(a), AlphaRepr::Tag(b)) => a.cmp(b),
+            _ => self.as_str().cmp(other.as_str()),
+        }
+    }
+}
+
+impl PartialOrd for AlphaPrerelease {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Debug for AlphaPrerelease {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AlphaPrerelease")
+            .field(&self.as_str())
+            .finish()
+    }
+}
+
+impl std::fmt::Display for AlphaPrerelease {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 impl FromStr for Prerelease {
     type Err = InvalidPrerelease;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        regex_switch!(
-         s,
-         r"^0|[1-9]\d*$" => Prerelease::Numeric(NumericPrerelease(
-             s.parse()
-                 .expect("The conversion to BigUint should be infallible"),
-          ) ),
-         r"^\d*[a-zA-Z-][0-9a-zA-Z-]*$" => Prerelease::Alpha(AlphaPrerelease(s.to_owned()))
-        )
-        .ok_or_else(|| debug_invalid_identifier(s))
+        Self::parse_limited(s, ParseOptions::DEFAULT.max_prerelease_digits)
     }
 }
 
@@ -107,10 +261,8 @@ fn debug_invalid_identifier(s: &str) -> InvalidPrerelease {
         };
     }
 
-    if s.chars().all(|c| c.is_ascii_digit()) {
-        if s.starts_with('0') && s.len() > 1 {
-            return InvalidPrerelease::LeadingZeros { id: s.to_string() };
-        }
+    if s.chars().all(|c| c.is_ascii_digit()) && s.starts_with('0') && s.len() > 1 {
+        return InvalidPrerelease::LeadingZeros { id: s.to_string() };
     }
 
     unreachable!(
@@ -128,4 +280,6 @@ pub enum InvalidPrerelease {
         "Prerelease must be composed of alphanumeric characters or hyphens, not '{ch}': `{id}`"
     ))]
     InvalidCharacters { id: String, ch: char },
+    #[snafu(display("Numeric prerelease has {digits} digits, more than the limit of {limit}"))]
+    NumericTooLong { digits: usize, limit: usize },
 }
@@ -0,0 +1,169 @@
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use super::{AlphaPrerelease, AlphaRepr, CommonTag, InvalidPrerelease, Prerelease};
+use crate::{
+    range::Ranges,
+    version::{
+        InvalidVersion, ParseOptions, Version,
+        pure::{InvalidPureVersion, PureVersion},
+    },
+};
+
+#[test]
+fn tags_are_in_ascii_order() {
+    for (a, b) in CommonTag::ALL.into_iter().tuple_combinations() {
+        assert_eq!(a.cmp(&b), a.as_str().cmp(b.as_str()), "{a:?} {b:?}");
+    }
+}
+
+#[test]
+fn common_identifiers_are_tags() {
+    for tag in CommonTag::ALL {
+        assert_eq!(AlphaPrerelease::new(tag.as_str()).0, AlphaRepr::Tag(tag));
+    }
+    assert!(matches!(
+        AlphaPrerelease::new("Alpha").0,
+        AlphaRepr::Other(_)
+    ));
+    assert!(matches!(
+        AlphaPrerelease::new("alpha1").0,
+        AlphaRepr::Other(_)
+    ));
+}
+
+#[test]
+fn ordering_matches_string_comparison() {
+    let ids = [
+        "alpha",
+        "alpha1",
+        "alph",
+        "Alpha",
+        "beta",
+        "b",
+        "be",
+        "betaa",
+        "dev",
+        "dev-1",
+        "-",
+        "a",
+        "nightly",
+        "night",
+        "pre",
+        "prerelease",
+        "preview",
+        "rc",
+        "rc-1",
+        "RC",
+        "z",
+        "0a",
+    ];
+    for (a, b) in ids.into_iter().cartesian_product(ids) {
+        let (pa, pb) = (
+            Prerelease::from_str(a).unwrap(),
+            Prerelease::from_str(b).unwrap(),
+        );
+        assert_eq!(pa.cmp(&pb), a.cmp(b), "{a} {b}");
+        assert_eq!(pa == pb, a == b, "{a} {b}");
+        assert_eq!(pa.to_string(), a);
+    }
+}
+
+#[test]
+fn debug_shows_the_identifier() {
+    assert_eq!(
+        format!("{:?}", AlphaPrerelease::new("rc")),
+        r#"AlphaPrerelease("rc")"#
+    );
+}
+
+#[test]
+fn numeric_identifiers_are_limited() {
+    let limit = ParseOptions::DEFAULT.max_prerelease_digits;
+    let under = format!("1{}", "0".repeat(limit - 1));
+    let over = format!("1{}", "0".repeat(limit));
+
+    let too_long = |err: &InvalidPrerelease| {
+        matches!(
+            *err,
+            InvalidPrerelease::NumericTooLong { digits, limit: l } if digits == limit + 1 && l == limit
+        )
+    };
+    let too_long_version =
+        |err| matches!(err, InvalidPureVersion::InvalidPrerelease { source } if too_long(&source));
+
+    // Every parsing path
+    assert!(Prerelease::from_str(&under).is_ok());
+    assert!(too_long(&Prerelease::from_str(&over).unwrap_err()));
+    assert!(format!("1.0.0-{under}").parse::<PureVersion>().is_ok());
+    assert!(too_long_version(
+        format!("1.0.0-rc.{over}")
+            .parse::<PureVersion>()
+            .unwrap_err()
+    ));
+    assert!(format!("1.0.0-{over}+build").parse::<Version>().is_err());
+    assert!(too_long_version(
+        PureVersion::parse_with_options(
+            &format!("01.0-{over}"),
+            &ParseOptions {
+                allow_leading_zeros: true,
+                allow_missing_components: true,
+                ..Default::default()
+            }
+        )
+        .unwrap_err()
+    ));
+    assert!(Ranges::<PureVersion>::from_str(&format!(">=1.0.0-{under}")).is_ok());
+    let req = format!(">=1.0.0-{over}");
+    let errors = Ranges::<PureVersion>::from_str(&req).unwrap_err();
+    assert!(
+        errors[0].to_string().contains("more than the limit"),
+        "{errors:?}"
+    );
+
+    // Ordinary identifiers and alphanumeric ones are unaffected
+    assert!("1.0.0-rc.1.2.3".parse::<PureVersion>().is_ok());
+    assert!(Prerelease::from_str(&format!("{over}a")).is_ok());
+}
+
+#[test]
+fn numeric_limit_is_an_option() {
+    let limit = ParseOptions::DEFAULT.max_prerelease_digits;
+    let over = format!("1.0.0-{}", "1".repeat(limit + 1));
+    let raised = ParseOptions {
+        max_prerelease_digits: limit + 1,
+        ..Default::default()
+    };
+    assert!(PureVersion::parse_with_options(&over, &raised).is_ok());
+    assert!(Version::parse_with_options(&format!("{over}+build"), &raised).is_ok());
+    // The default is unaffected
+    assert!(over.parse::<PureVersion>().is_err());
+    assert!(Ranges::<PureVersion>::from_str(&format!(">={over}")).is_err());
+
+    // Displayed versions are parsed again only with the same options
+    let version = Version::parse_with_options(&format!("{over}+build"), &raised).unwrap();
+    let displayed = version.to_string();
+    assert_eq!(
+        Version::parse_with_options(&displayed, &raised).unwrap(),
+        version
+    );
+    assert!(displayed.parse::<Version>().is_err());
+
+    let lowered = ParseOptions {
+        max_prerelease_digits: 2,
+        ..Default::default()
+    };
+    assert!(PureVersion::parse_with_options("1.0.0-rc.12", &lowered).is_ok());
+    assert!(matches!(
+        Version::parse_with_options("1.0.0-rc.123", &lowered),
+        Err(InvalidVersion::InvalidPureVersion {
+            source: InvalidPureVersion::InvalidPrerelease {
+                source: InvalidPrerelease::NumericTooLong {
+                    digits: 3,
+                    limit: 2
+                }
+            }
+        })
+    ));
+}
@@ -1,8 +1,9 @@
 use std::str::FromStr;
 
-use super::PureVersion;
+use super::{InvalidPureVersion, PrereleasePolicy, PureVersion, Stability};
+use crate::range::Ranges;
 
-static SORTED: &[&'static str] = &[
+static SORTED: &[&str] = &[
     "1.0.0-alpha",
     "1.0.0-alpha.1",
     "1.0.0-alpha.beta",
@@ -25,6 +26,29 @@ fn prereleases_are_sorted() {
     assert!(SORTED.is_sorted_by_key(|v| PureVersion::from_str(v).unwrap()))
 }
 
+#[test]
+fn prerelease_precedence_edge_cases() {
+    let ordered = |versions: &[&str]| {
+        versions
+            .iter()
+            .map(|v| PureVersion::from_str(v).unwrap())
+            .is_sorted_by(|a, b| a < b)
+    };
+    // A larger set of identifiers wins when the preceding ones are equal
+    assert!(ordered(&[
+        "1.0.0-alpha",
+        "1.0.0-alpha.1",
+        "1.0.0-alpha.1.1"
+    ]));
+    assert!(ordered(&["1.0.0-alpha.beta", "1.0.0-alpha.beta.0"]));
+    // Numeric identifiers have lower precedence than alphanumeric ones
+    assert!(ordered(&["1.0.0-1", "1.0.0-alpha"]));
+    assert!(ordered(&["1.0.0-alpha.99", "1.0.0-alpha.a"]));
+    assert!(ordered(&["1.0.0-1.alpha", "1.0.0-1a"]));
+    // Numeric identifiers are compared by value, not by their length
+    assert!(ordered(&["1.0.0-2", "1.0.0-10", "1.0.0-10.0"]));
+}
+
 #[test]
 fn roundtrips() {
     for v in SORTED {
@@ -38,10 +62,436 @@ fn next_and_prev() {
     for version in SORTED {
         let version = PureVersion::from_str(version).unwrap();
 
-        let next = version.clone().next();
+        let next = version.clone().next().unwrap();
 
         assert!(version.compare_next_to(&next));
         assert!(next.has_prev());
         // assert_eq!(version, next.prev().unwrap());
     }
 }
+
+#[test]
+fn alphanumeric_prerelease_ending_in_digits() {
+    let version = PureVersion::from_str("1.0.0-abc-123").unwrap();
+    assert_eq!(version.to_string(), "1.0.0-abc-123");
+}
+
+const MAX: &str = "18446744073709551615";
+
+#[test]
+fn patch_can_be_max() {
+    let version = PureVersion::from_str(&format!("1.2.{MAX}")).unwrap();
+
+    assert_eq!(version.patch, u64::MAX);
+    assert_eq!(version.to_string(), format!("1.2.{MAX}"));
+}
+
+#[test]
+fn next_carries_over_max_components() {
+    let next = PureVersion::new(1, 2, u64::MAX).next().unwrap();
+    assert_eq!(next, PureVersion::from_str("1.3.0-0").unwrap());
+    assert!(PureVersion::new(1, 2, u64::MAX).compare_next_to(&next));
+
+    let next = PureVersion::new(1, u64::MAX, u64::MAX).next().unwrap();
+    assert_eq!(next, PureVersion::from_str("2.0.0-0").unwrap());
+
+    assert_eq!(PureVersion::new(u64::MAX, u64::MAX, u64::MAX).next(), None);
+}
+
+#[test]
+fn ranges_with_max_patch() {
+    let max_patch = PureVersion::new(1, 2, u64::MAX);
+    let after = PureVersion::from_str("1.3.0-0").unwrap();
+
+    let to = Ranges::<PureVersion>::from_str(&format!("<={max_patch}")).unwrap();
+    assert!(to.contains(&max_patch));
+    assert!(!to.contains(&after));
+
+    let from = Ranges::<PureVersion>::from_str(&format!(">{max_patch}")).unwrap();
+    assert!(!from.contains(&max_patch));
+    assert!(from.contains(&after));
+    assert_eq!(from.to_string(), ">=1.3.0-0");
+
+    let single = Ranges::single(max_patch.clone());
+    assert!(single.is_single());
+    assert_eq!(single.to_string(), format!("=={max_patch}"));
+    assert_eq!(Ranges::from_str(&single.to_string()).unwrap(), single);
+
+    let top = PureVersion::new(u64::MAX, u64::MAX, u64::MAX);
+    assert!(Ranges::from_exclusive(top.clone()).is_empty());
+    assert!(Ranges::single(top.clone()).contains(&top));
+    assert!(Ranges::to_inclusive(top).is_full());
+}
+
+#[test]
+fn inclusive_upper_bound() {
+    let pv = |s: &str| PureVersion::from_str(s).unwrap();
+
+    let to = Ranges::<PureVersion>::from_str("<=1.2.3").unwrap();
+    assert_eq!(to, Ranges::to_inclusive(pv("1.2.3")));
+    assert_eq!(to.to_string(), "<=1.2.3");
+    assert!(to.contains(&pv("1.2.3")));
+    assert!(to.contains(&pv("1.2.2")));
+    assert!(!to.contains(&pv("1.2.4")));
+    assert!(!to.contains(&pv("1.3.0")));
+    // Prereleases precede their release, so those of 1.2.3 are included by precedence,
+    // while the ones of the next release are not
+    assert!(to.contains(&pv("1.2.3-rc.1")));
+    assert!(to.contains(&pv("1.2.3-0")));
+    assert!(!to.contains(&pv("1.2.4-0")));
+    assert!(!to.contains(&pv("1.2.4-alpha")));
+    // Still, the default policy does not match them, as they are not mentioned
+    assert!(to.contains_with_policy(&pv("1.2.3"), PrereleasePolicy::default()));
+    assert!(!to.contains_with_policy(&pv("1.2.3-rc.1"), PrereleasePolicy::default()));
+
+    let to_pre = Ranges::<PureVersion>::from_str("<=1.2.3-rc.1").unwrap();
+    assert_eq!(to_pre.to_string(), "<=1.2.3-rc.1");
+    assert!(to_pre.contains(&pv("1.2.3-rc.1")));
+    assert!(to_pre.contains(&pv("1.2.3-beta")));
+    assert!(to_pre.contains(&pv("1.2.2")));
+    assert!(!to_pre.contains(&pv("1.2.3-rc.1.0")));
+    assert!(!to_pre.contains(&pv("1.2.3-rc.2")));
+    assert!(!to_pre.contains(&pv("1.2.3")));
+    assert!(to_pre.contains_with_policy(&pv("1.2.3-rc.1"), PrereleasePolicy::default()));
+
+    // Combined with a lower bound, both ends are inclusive
+    let between = Ranges::<PureVersion>::from_str(">=1.0.0 && <=1.2.3").unwrap();
+    assert_eq!(between.to_string(), ">=1.0.0 && <=1.2.3");
+    assert!(between.contains(&pv("1.0.0")));
+    assert!(between.contains(&pv("1.2.3")));
+    assert!(!between.contains(&pv("1.2.4")));
+}
+
+#[test]
+fn explicit_prerelease_policy() {
+    use super::PrereleasePolicy::Explicit;
+
+    let matches = |req: &str, v: &str| {
+        Ranges::<PureVersion>::from_str(req)
+            .unwrap()
+            .contains_with_policy(&PureVersion::from_str(v).unwrap(), Explicit)
+    };
+
+    assert!(matches(">=1.2.3-rc.1", "1.2.3-rc.2"));
+    assert!(matches("==1.2.3-rc.1", "1.2.3-rc.1"));
+    assert!(matches("<=1.2.3-rc.1", "1.2.3-beta"));
+    assert!(matches(">=1.2.3-rc.1", "1.2.3"));
+    assert!(!matches(">=1.2.3-rc.1", "1.2.4-rc.1"));
+    assert!(!matches(">=1.2.0", "1.2.3-rc.1"));
+    assert!(!matches("<1.2.3", "1.2.3-rc.1"));
+    assert!(!matches(">1.2.3", "1.2.4-rc.1"));
+}
+
+#[test]
+fn missing_components() {
+    let lenient = crate::version::ParseOptions {
+        allow_missing_components: true,
+        allow_leading_zeros: true,
+        ..Default::default()
+    };
+
+    assert!(PureVersion::from_str("1.2").is_err());
+    assert_eq!(
+        PureVersion::parse_with_options("1.2", &lenient).unwrap(),
+        PureVersion::new(1, 2, 0)
+    );
+    assert_eq!(
+        PureVersion::parse_with_options("01", &lenient).unwrap(),
+        PureVersion::new(1, 0, 0)
+    );
+}
+
+#[test]
+fn leading_zeros() {
+    let lenient = crate::version::ParseOptions {
+        allow_leading_zeros: true,
+        ..Default::default()
+    };
+
+    assert!(PureVersion::from_str("01.2.3").is_err());
+    assert_eq!(
+        PureVersion::parse_with_options("01.2.3", &lenient).unwrap(),
+        PureVersion::new(1, 2, 3)
+    );
+    assert_eq!(
+        PureVersion::parse_with_options("1.00.3", &lenient).unwrap(),
+        PureVersion::new(1, 0, 3)
+    );
+}
+
+#[test]
+fn strict_leading_zeros_error() {
+    assert!(matches!(
+        PureVersion::from_str("1.02.3"),
+        Err(super::InvalidPureVersion::LeadingZeros {
+            part: super::NumericPart::Minor,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn empty_input() {
+    for s in ["", "   ", "\t"] {
+        assert!(
+            matches!(
+                PureVersion::from_str(s),
+                Err(super::InvalidPureVersion::Empty)
+            ),
+            "{s:?}"
+        );
+    }
+}
+
+#[test]
+fn parse_prefix() {
+    let (version, rest) = PureVersion::parse_prefix("1.2.3-rc.1+build").unwrap();
+    assert_eq!(version, PureVersion::from_str("1.2.3-rc.1").unwrap());
+    assert_eq!(rest, "+build");
+
+    assert!(PureVersion::parse_prefix("latest").is_err());
+}
+
+#[test]
+fn set_prerelease() {
+    use super::prerelease::Prerelease;
+
+    let mut version = PureVersion::new(1, 0, 0);
+    version.set_prerelease([
+        Prerelease::from_str("alpha").unwrap(),
+        Prerelease::from_str("1").unwrap(),
+    ]);
+    assert_eq!(version.to_string(), "1.0.0-alpha.1");
+    assert_eq!(version, PureVersion::from_str("1.0.0-alpha.1").unwrap());
+    assert!(version < PureVersion::from_str("1.0.0-alpha.beta").unwrap());
+    assert!(version < PureVersion::new(1, 0, 0));
+
+    version.set_prerelease([]);
+    assert_eq!(version, PureVersion::new(1, 0, 0));
+}
+
+#[test]
+fn parse_ignoring_build() {
+    let build = (0..1000)
+        .map(|i| format!("id{i}"))
+        .collect::<Vec<_>>()
+        .join(".");
+    let version = PureVersion::parse_ignoring_build(&format!("1.2.3+huge.build.chain.{build}"));
+    assert_eq!(version.unwrap(), PureVersion::new(1, 2, 3));
+
+    assert_eq!(
+        PureVersion::parse_ignoring_build("1.2.3-rc").unwrap(),
+        PureVersion::from_str("1.2.3-rc").unwrap()
+    );
+    for invalid in ["1.2.3+", "1.2.3+a..b", "1.2.3+a_b", "1.2+build"] {
+        assert!(
+            PureVersion::parse_ignoring_build(invalid).is_err(),
+            "{invalid:?}"
+        );
+    }
+}
+
+#[test]
+fn diff() {
+    use super::VersionChange;
+
+    for (a, b, change) in [
+        ("1.2.3", "2.0.0", VersionChange::Major),
+        ("1.2.3", "0.2.3", VersionChange::Major),
+        ("1.2.3", "1.3.0", VersionChange::Minor),
+        ("1.2.3-rc.1", "1.3.0", VersionChange::Minor),
+        ("1.2.3", "1.2.4", VersionChange::Patch),
+        ("1.2.3", "1.2.4-alpha", VersionChange::Patch),
+        ("1.2.3-alpha", "1.2.3-beta", VersionChange::Prerelease),
+        ("1.2.3-rc.1", "1.2.3", VersionChange::Prerelease),
+        ("1.2.3", "1.2.3", VersionChange::None),
+        ("1.2.3-rc.1", "1.2.3-rc.1", VersionChange::None),
+    ] {
+        let (a, b) = (
+            PureVersion::from_str(a).unwrap(),
+            PureVersion::from_str(b).unwrap(),
+        );
+        assert_eq!(a.diff(&b), change, "{a} -> {b}");
+        assert_eq!(b.diff(&a), change, "{b} -> {a}");
+    }
+}
+
+#[test]
+fn version_change_is_ordered_by_significance() {
+    use super::VersionChange;
+
+    assert!(VersionChange::None < VersionChange::Prerelease);
+    assert!(VersionChange::Prerelease < VersionChange::Patch);
+    assert!(VersionChange::Patch < VersionChange::Minor);
+    assert!(VersionChange::Minor < VersionChange::Major);
+}
+
+#[test]
+fn from_operator_matches_dialects() {
+    use super::comparator::RangeOp;
+    use crate::npm;
+
+    for (op, prefix) in [
+        (RangeOp::Caret, "^"),
+        (RangeOp::Tilde, "~"),
+        (RangeOp::Exact, "="),
+        (RangeOp::Greater, ">"),
+        (RangeOp::GreaterEq, ">="),
+        (RangeOp::Less, "<"),
+        (RangeOp::LessEq, "<="),
+    ] {
+        for version in ["1.2.3", "0.2.3", "0.0.3", "1.2.3-rc.1"] {
+            assert_eq!(
+                Ranges::<PureVersion>::from_operator(op, version.parse().unwrap()),
+                npm::parse_range(&format!("{prefix}{version}")).unwrap(),
+                "{prefix}{version}"
+            );
+        }
+    }
+
+    assert_eq!(
+        Ranges::<PureVersion>::from_operator(RangeOp::Caret, "1.2.3".parse().unwrap()),
+        Ranges::from_str(">=1.2.3 && <2.0.0-0").unwrap()
+    );
+    assert_eq!(
+        Ranges::<PureVersion>::from_operator(RangeOp::NotEq, "1.2.3".parse().unwrap()),
+        Ranges::from_str("!=1.2.3").unwrap()
+    );
+}
+
+#[test]
+fn stability_of_versions() {
+    let stability = |v: &str| PureVersion::from_str(v).unwrap().stability();
+    assert_eq!(stability("0.0.9"), Stability::Unstable00x);
+    assert_eq!(stability("0.1.0"), Stability::Unstable0x);
+    assert_eq!(stability("0.99.3"), Stability::Unstable0x);
+    assert_eq!(stability("1.0.0-rc.1"), Stability::Prerelease);
+    assert_eq!(stability("0.0.1-alpha"), Stability::Prerelease);
+    assert_eq!(stability("1.0.0"), Stability::Stable);
+    assert!(Stability::Prerelease < Stability::Unstable00x);
+    assert!(Stability::Unstable00x < Stability::Unstable0x);
+    assert!(Stability::Unstable0x < Stability::Stable);
+}
+
+#[test]
+fn min_stability_of_ranges() {
+    let min_stability = |r: &str| Ranges::<PureVersion>::from_str(r).unwrap().min_stability();
+    assert_eq!(
+        min_stability(">=0.1.0 && <1.0.0"),
+        Some(Stability::Unstable0x)
+    );
+    assert_eq!(
+        min_stability(">=0.0.1 && <0.0.5"),
+        Some(Stability::Unstable00x)
+    );
+    assert_eq!(min_stability(">=0.0.9"), Some(Stability::Unstable00x));
+    assert_eq!(min_stability(">=1.0.0"), Some(Stability::Stable));
+    assert_eq!(min_stability("<1.0.0-rc.2"), Some(Stability::Prerelease));
+    assert_eq!(min_stability(">=1.0.0-rc.1"), Some(Stability::Prerelease));
+    // Implicit prerelease extremes do not count
+    assert_eq!(min_stability(">0.9.9 && <0.9.10"), None);
+    assert_eq!(Ranges::<PureVersion>::EMPTY.min_stability(), None);
+}
+
+#[test]
+fn widen_to_caret() {
+    let widen = |r: &str| {
+        Ranges::<PureVersion>::from_str(r)
+            .unwrap()
+            .widen_to_caret()
+            .map(|(base, range)| (base.to_string(), range))
+    };
+    let caret =
+        |v: &str| Ranges::from_operator(super::comparator::RangeOp::Caret, v.parse().unwrap());
+
+    // Already a caret
+    assert_eq!(
+        widen(">=1.2.3 && <2.0.0-0"),
+        Some(("1.2.3".to_owned(), caret("1.2.3")))
+    );
+    assert_eq!(
+        widen(">=0.2.3 && <0.3.0"),
+        Some(("0.2.3".to_owned(), caret("0.2.3")))
+    );
+    // Strictly inside one
+    assert_eq!(
+        widen(">1.2.3 && <1.5.0 || ==1.8.0"),
+        Some(("1.2.4".to_owned(), caret("1.2.4")))
+    );
+    assert_eq!(
+        widen(">=1.0.0-rc.1 && <2.0.0"),
+        Some(("1.0.0-rc.1".to_owned(), caret("1.0.0-rc.1")))
+    );
+    // Spanning two majors
+    assert_eq!(widen(">=1.2.0 && <2.0.1"), None);
+    assert_eq!(widen(">=1.2.0"), None);
+    assert_eq!(widen(">=1.2.0 && <2.0.0-beta"), None);
+    assert_eq!(widen(">=0.2.0 && <0.4.0"), None);
+    assert_eq!(Ranges::<PureVersion>::EMPTY.widen_to_caret(), None);
+}
+
+#[test]
+fn widen_to_tilde() {
+    let widen = |r: &str| {
+        Ranges::<PureVersion>::from_str(r)
+            .unwrap()
+            .widen_to_tilde()
+            .map(|(base, range)| (base.to_string(), range))
+    };
+    let tilde =
+        |v: &str| Ranges::from_operator(super::comparator::RangeOp::Tilde, v.parse().unwrap());
+
+    assert_eq!(
+        widen(">=1.2.3 && <1.3.0"),
+        Some(("1.2.3".to_owned(), tilde("1.2.3")))
+    );
+    assert_eq!(widen("==1.2.5"), Some(("1.2.5".to_owned(), tilde("1.2.5"))));
+    assert_eq!(widen(">=1.2.3 && <1.4.0"), None);
+    assert_eq!(widen("<1.2.0"), None);
+}
+
+#[test]
+fn from_triples() {
+    assert_eq!(PureVersion::from((1, 2, 3)), PureVersion::new(1, 2, 3));
+    let max = PureVersion::from((u64::MAX, u64::MAX, u64::MAX));
+    assert!(max > PureVersion::from((u64::MAX, u64::MAX, u64::MAX - 1)));
+    assert!(max > PureVersion::from((u64::MAX - 1, u64::MAX, u64::MAX)));
+    assert!(Ranges::from_exclusive(max.clone()).is_empty());
+    assert_eq!(
+        Ranges::between_include_end(PureVersion::ZERO, max),
+        Ranges::from(PureVersion::ZERO)
+    );
+}
+
+#[test]
+fn stray_whitespace() {
+    for (s, expected) in [("1 .2.3", 1), ("1.2.3 -rc.1", 5), ("1.2.3-rc. 1", 9)] {
+        match s.parse::<PureVersion>() {
+            Err(InvalidPureVersion::UnexpectedWhitespace { position }) => {
+                assert_eq!(position, expected, "{s:?}")
+            }
+            other => panic!("{s:?} parsed as {other:?}"),
+        }
+    }
+    // Whitespace between words is not mistaken for a typo inside the version
+    assert!(!matches!(
+        "1.2.3 foo".parse::<PureVersion>(),
+        Err(InvalidPureVersion::UnexpectedWhitespace { .. })
+    ));
+}
+
+#[test]
+fn parse_prefix_signed_components() {
+    for s in ["+1.2.3", "1.+2.3", "1.2.+3"] {
+        match PureVersion::parse_prefix(s) {
+            Err(InvalidPureVersion::NonDigitNumericPart { value, .. }) => {
+                assert!(value.starts_with('+'), "{s:?}: {value:?}")
+            }
+            other => panic!("{s:?} parsed as {other:?}"),
+        }
+    }
+    assert!(matches!(
+        PureVersion::parse_prefix("-1.2.3"),
+        Err(InvalidPureVersion::MissingNumericPart { .. })
+    ));
+}
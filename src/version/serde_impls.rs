@@ -0,0 +1,162 @@
+//! Integration with [`serde`]
+//!
+//! Human-readable formats get the display string of the values. Other formats get a
+//! compact structured form: the numeric components as integers, the prerelease as a list
+//! of tagged identifiers and the build metadata as a list of strings.
+//! Both forms are validated when deserializing.
+
+use std::{borrow::Cow, fmt::Display, marker::PhantomData, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use super::{
+    ParseOptions, Version,
+    build::BuildMetadata,
+    pure::{
+        PureVersion, UInt,
+        prerelease::{NumericPrerelease, Prerelease},
+    },
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Compact form of a prerelease identifier
+#[derive(Serialize, Deserialize)]
+enum CompactPrerelease<'a> {
+    /// Little-endian bytes of the value
+    Numeric(Cow<'a, [u8]>),
+    Alpha(Cow<'a, str>),
+}
+
+impl Serialize for Prerelease {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(self);
+        }
+        match self {
+            Prerelease::Numeric(n) => CompactPrerelease::Numeric(Cow::Owned(n.to_bytes_le())),
+            Prerelease::Alpha(a) => CompactPrerelease::Alpha(Cow::Borrowed(a.as_str())),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Prerelease {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_str(FromStrVisitor::new("a prerelease identifier"));
+        }
+        match CompactPrerelease::deserialize(deserializer)? {
+            CompactPrerelease::Numeric(bytes) => {
+                let limit = ParseOptions::DEFAULT.max_prerelease_digits;
+                // Every significant byte after the first adds more than two digits
+                let significant = bytes.iter().rposition(|&b| b != 0).map_or(0, |idx| idx + 1);
+                if significant > limit / 2 + 1 {
+                    return Err(de::Error::invalid_length(
+                        bytes.len(),
+                        &"a numeric prerelease identifier within the limit of digits",
+                    ));
+                }
+                NumericPrerelease::from_bytes_le_limited(&bytes, limit)
+                    .map(Prerelease::Numeric)
+                    .map_err(de::Error::custom)
+            }
+            CompactPrerelease::Alpha(id) => match id.parse() {
+                Ok(pre @ Prerelease::Alpha(_)) => Ok(pre),
+                Ok(Prerelease::Numeric(_)) => Err(de::Error::invalid_value(
+                    de::Unexpected::Str(&id),
+                    &"an alphanumeric prerelease identifier",
+                )),
+                Err(err) => Err(de::Error::custom(err)),
+            },
+        }
+    }
+}
+
+impl Serialize for BuildMetadata {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for BuildMetadata {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(FromStrVisitor::new("a build metadata identifier"))
+    }
+}
+
+impl Serialize for PureVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(self);
+        }
+        (self.major, self.minor, self.patch, &*self.pre).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PureVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_str(FromStrVisitor::new("a version"));
+        }
+        let (major, minor, patch, pre) =
+            <(UInt, UInt, UInt, Vec<Prerelease>)>::deserialize(deserializer)?;
+        Ok(PureVersion {
+            major,
+            minor,
+            patch,
+            pre: Cow::Owned(pre),
+        })
+    }
+}
+
+impl Serialize for Version {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(self);
+        }
+        (&self.pure, &self.build).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_str(FromStrVisitor::new("a version"));
+        }
+        let (pure, build) = <(PureVersion, Vec<BuildMetadata>)>::deserialize(deserializer)?;
+        Ok(Version { pure, build })
+    }
+}
+
+/// Visitor parsing a string with [`FromStr`]
+struct FromStrVisitor<T> {
+    expecting: &'static str,
+    _marker: PhantomData<T>,
+}
+
+impl<T> FromStrVisitor<T> {
+    fn new(expecting: &'static str) -> Self {
+        Self {
+            expecting,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> de::Visitor<'_> for FromStrVisitor<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse().map_err(E::custom)
+    }
+}
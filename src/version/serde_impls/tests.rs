@@ -0,0 +1,121 @@
+use num_bigint::BigUint;
+
+use crate::version::{ParseOptions, Version, pure::PureVersion};
+
+fn v(s: &str) -> Version {
+    s.parse().unwrap()
+}
+
+const VERSIONS: [&str; 5] = [
+    "0.0.0",
+    "1.2.3",
+    "1.2.3-alpha.1",
+    "18446744073709551615.0.1-rc.99999999999999999999999.x-y+build.007",
+    "2.0.0+sha.5114f85",
+];
+
+#[test]
+fn json_uses_strings() {
+    for s in VERSIONS {
+        let version = v(s);
+        let json = serde_json::to_string(&version).unwrap();
+        assert_eq!(json, format!("\"{s}\""));
+        assert_eq!(serde_json::from_str::<Version>(&json).unwrap(), version);
+
+        let json = serde_json::to_string(&version.pure).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PureVersion>(&json).unwrap(),
+            version.pure
+        );
+    }
+}
+
+#[test]
+fn postcard_round_trips() {
+    for s in VERSIONS {
+        let version = v(s);
+        let bytes = postcard::to_allocvec(&version).unwrap();
+        assert_eq!(postcard::from_bytes::<Version>(&bytes).unwrap(), version);
+
+        let bytes = postcard::to_allocvec(&version.pure).unwrap();
+        assert_eq!(
+            postcard::from_bytes::<PureVersion>(&bytes).unwrap(),
+            version.pure
+        );
+    }
+}
+
+#[test]
+fn postcard_is_compact() {
+    let version = v("2024.11.305-rc.1234567");
+    let bytes = postcard::to_allocvec(&version).unwrap();
+    assert!(bytes.len() < version.to_string().len(), "{bytes:?}");
+}
+
+#[test]
+fn invalid_values_are_rejected() {
+    assert!(serde_json::from_str::<Version>("\"1.2\"").is_err());
+    assert!(serde_json::from_str::<Version>("\"1.2.3-01\"").is_err());
+    assert!(serde_json::from_str::<Version>("\"1.2.3+b@d\"").is_err());
+
+    // A numeric identifier disguised as an alphanumeric one
+    let bytes = postcard::to_allocvec(&(1u64, 2u64, 3u64, [(1u32, "42")])).unwrap();
+    assert!(postcard::from_bytes::<PureVersion>(&bytes).is_err());
+    let bytes = postcard::to_allocvec(&(1u64, 2u64, 3u64, [(1u32, "b.d")])).unwrap();
+    assert!(postcard::from_bytes::<PureVersion>(&bytes).is_err());
+    let bytes = postcard::to_allocvec(&((1u64, 2u64, 3u64, [(); 0]), ["b@d"])).unwrap();
+    assert!(postcard::from_bytes::<Version>(&bytes).is_err());
+}
+
+#[test]
+fn compact_numeric_identifiers_are_limited() {
+    let limit = ParseOptions::DEFAULT.max_prerelease_digits;
+    let numeric = |value: &BigUint| {
+        postcard::to_allocvec(&(1u64, 2u64, 3u64, vec![(0u32, value.to_bytes_le())])).unwrap()
+    };
+
+    let at_limit = BigUint::from(10u8).pow(limit as u32) - 1u8;
+    let version = postcard::from_bytes::<PureVersion>(&numeric(&at_limit)).unwrap();
+    assert_eq!(version.pre[0].to_string(), "9".repeat(limit));
+
+    let over = BigUint::from(10u8).pow(limit as u32);
+    assert!(postcard::from_bytes::<PureVersion>(&numeric(&over)).is_err());
+
+    // Huge values are rejected from their length alone
+    let huge =
+        postcard::to_allocvec(&(1u64, 2u64, 3u64, vec![(0u32, vec![0xffu8; 100_000])])).unwrap();
+    assert!(postcard::from_bytes::<PureVersion>(&huge).is_err());
+    // Trailing zero bytes are not significant
+    let mut padded = at_limit.to_bytes_le();
+    padded.resize(1000, 0);
+    let padded = postcard::to_allocvec(&(1u64, 2u64, 3u64, vec![(0u32, padded)])).unwrap();
+    assert!(postcard::from_bytes::<PureVersion>(&padded).is_ok());
+}
+
+#[test]
+fn forms_are_not_interchangeable() {
+    for s in VERSIONS {
+        // The string form is not accepted by the structured path
+        let bytes = postcard::to_allocvec(s).unwrap();
+        assert!(postcard::from_bytes::<Version>(&bytes).is_err(), "{s}");
+    }
+    // The structured form is not accepted by the string path
+    assert!(serde_json::from_str::<Version>("[[1,2,3,[]],[]]").is_err());
+    assert!(serde_json::from_str::<PureVersion>("[1,2,3,[]]").is_err());
+}
+
+#[test]
+fn raised_prerelease_limit_is_not_deserialized() {
+    let limit = ParseOptions::DEFAULT.max_prerelease_digits;
+    let raised = ParseOptions {
+        max_prerelease_digits: limit + 1,
+        ..Default::default()
+    };
+    let version =
+        Version::parse_with_options(&format!("1.0.0-{}", "9".repeat(limit + 1)), &raised).unwrap();
+
+    let json = serde_json::to_string(&version).unwrap();
+    assert!(serde_json::from_str::<Version>(&json).is_err());
+    let bytes = postcard::to_allocvec(&version).unwrap();
+    assert!(postcard::from_bytes::<Version>(&bytes).is_err());
+}
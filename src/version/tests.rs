@@ -0,0 +1,797 @@
+use std::{
+    cmp::Ordering,
+    io::{BufReader, Cursor, Read},
+    str::FromStr,
+};
+
+use super::{
+    InvalidVersion, ParseOptions, Version, is_sorted,
+    pure::{Granularity, InvalidPureVersion, NumericPart, PureVersion, UInt},
+};
+use crate::range::Ranges;
+
+fn v(s: &str) -> Version {
+    Version::from_str(s).unwrap()
+}
+
+#[test]
+fn cmp_including_build() {
+    let (a, b) = (v("1.0.0+1"), v("1.0.0+2"));
+
+    assert_eq!(a.pure.cmp(&b.pure), Ordering::Equal);
+    assert_eq!(a.cmp_including_build(&b), Ordering::Less);
+    assert_eq!(b.cmp_including_build(&a), Ordering::Greater);
+    assert_eq!(a.cmp_including_build(&a), Ordering::Equal);
+}
+
+#[test]
+fn cmp_including_build_precedence_first() {
+    let (a, b) = (v("1.0.0+zzz"), v("1.0.1+aaa"));
+
+    assert_eq!(a.pure.cmp(&b.pure), Ordering::Less);
+    assert_eq!(a.cmp_including_build(&b), Ordering::Less);
+
+    let (pre, release) = (v("1.0.0-rc.1+2"), v("1.0.0+1"));
+    assert_eq!(pre.cmp_including_build(&release), Ordering::Less);
+}
+
+#[test]
+fn cmp_including_build_missing_metadata_first() {
+    let (a, b) = (v("1.0.0"), v("1.0.0+build"));
+
+    assert_eq!(a.pure.cmp(&b.pure), Ordering::Equal);
+    assert_eq!(a.cmp_including_build(&b), Ordering::Less);
+}
+
+#[test]
+fn parse_stream() {
+    let input = Cursor::new("1.0.0\n  \n\t2.0.0-rc.1  \r\n\nnot.a.version\n3.0.0+build\n");
+    let mut versions = Version::parse_stream(input);
+
+    assert_eq!(versions.next().unwrap().unwrap(), v("1.0.0"));
+    assert_eq!(versions.next().unwrap().unwrap(), v("2.0.0-rc.1"));
+    assert!(matches!(
+        versions.next().unwrap(),
+        Err(InvalidVersion::InvalidPureVersion { .. })
+    ));
+    assert_eq!(versions.next().unwrap().unwrap(), v("3.0.0+build"));
+    assert!(versions.next().is_none());
+}
+
+#[test]
+fn parse_stream_stops_at_read_error() {
+    struct Failing;
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("broken"))
+        }
+    }
+
+    let input = Cursor::new("1.0.0\n").chain(Failing);
+    let versions: Vec<_> = Version::parse_stream(BufReader::new(input)).collect();
+
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[0].as_ref().unwrap(), &v("1.0.0"));
+    assert!(matches!(versions[1], Err(InvalidVersion::Read { .. })));
+}
+
+#[test]
+fn digits_cap() {
+    let options = ParseOptions {
+        max_numeric_digits: Some(20),
+        ..Default::default()
+    };
+    let huge = format!("{}.0.0", "9".repeat(200));
+
+    assert!(matches!(
+        Version::parse_with_options(&huge, &options),
+        Err(InvalidVersion::InvalidPureVersion {
+            source: InvalidPureVersion::TooManyDigits {
+                part: NumericPart::Major,
+                digits: 200,
+                limit: 20
+            }
+        })
+    ));
+    assert!(matches!(
+        Version::parse_with_options(&huge, &ParseOptions::default()),
+        Err(InvalidVersion::InvalidPureVersion {
+            source: InvalidPureVersion::NumericPartTooLong { .. }
+        })
+    ));
+    assert!(Version::parse_with_options("1.2.123456-rc.1", &options).is_ok());
+    assert!(matches!(
+        Version::parse_with_options(
+            "1.2.123456",
+            &ParseOptions {
+                max_numeric_digits: Some(3),
+                ..Default::default()
+            }
+        ),
+        Err(InvalidVersion::InvalidPureVersion {
+            source: InvalidPureVersion::TooManyDigits {
+                part: NumericPart::Patch,
+                ..
+            }
+        })
+    ));
+}
+
+#[test]
+fn sorted() {
+    let versions = [
+        "1.0.0-alpha",
+        "1.0.0-alpha.1",
+        "1.0.0+b",
+        "1.0.0+a",
+        "2.0.0",
+    ]
+    .map(v);
+
+    assert_eq!(is_sorted(&versions), Ok(()));
+    assert_eq!(is_sorted(&[]), Ok(()));
+}
+
+#[test]
+fn not_sorted() {
+    let versions = ["1.0.0", "1.1.0", "1.0.5", "0.1.0"].map(v);
+
+    assert_eq!(is_sorted(&versions), Err((1, &versions[1], &versions[2])));
+}
+
+#[test]
+fn leading_zeros() {
+    let lenient = ParseOptions {
+        allow_leading_zeros: true,
+        ..Default::default()
+    };
+
+    assert!(Version::parse_with_options("01.2.3", &ParseOptions::default()).is_err());
+    assert_eq!(
+        Version::parse_with_options("01.2.3", &lenient).unwrap(),
+        v("1.2.3")
+    );
+    assert_eq!(
+        Version::parse_with_options("01.02.000-rc.1+007", &lenient).unwrap(),
+        v("1.2.0-rc.1+007")
+    );
+    assert!(Version::parse_with_options("1.2.3-01", &lenient).is_err());
+}
+
+#[test]
+fn empty_input() {
+    for s in ["", "   ", "\t"] {
+        assert!(
+            matches!(
+                s.parse::<Version>(),
+                Err(InvalidVersion::InvalidPureVersion {
+                    source: InvalidPureVersion::Empty
+                })
+            ),
+            "{s:?}"
+        );
+    }
+}
+
+#[test]
+fn parse_prefix() {
+    let (version, rest) = Version::parse_prefix("1.2.3-alpha+build rest").unwrap();
+    assert_eq!(version, v("1.2.3-alpha+build"));
+    assert_eq!(rest, " rest");
+
+    let (version, rest) = Version::parse_prefix("1.2.3.4").unwrap();
+    assert_eq!(version, v("1.2.3"));
+    assert_eq!(rest, ".4");
+
+    let (version, rest) = Version::parse_prefix("1.2.3-0abc.01").unwrap();
+    assert_eq!(version, v("1.2.3-0abc.0"));
+    assert_eq!(rest, "1");
+
+    let (version, rest) = Version::parse_prefix("1.2.3+").unwrap();
+    assert_eq!(version, v("1.2.3"));
+    assert_eq!(rest, "+");
+
+    for invalid in ["", "v1.2.3", "name@1.2.3", "1.2", "01.2.3"] {
+        assert!(Version::parse_prefix(invalid).is_err(), "{invalid:?}");
+    }
+}
+
+#[test]
+fn parse_prefix_len() {
+    let (version, len) = Version::parse_prefix_len("1.2.3rest").unwrap();
+    assert_eq!(version, v("1.2.3"));
+    assert_eq!(len, 5);
+
+    let (version, len) = Version::parse_prefix_len("1.2.3-rc.1+b more").unwrap();
+    assert_eq!(version, v("1.2.3-rc.1+b"));
+    assert_eq!(len, 12);
+
+    assert!(Version::parse_prefix_len("rest").is_err());
+}
+
+#[test]
+fn set_build() {
+    let mut version = v("1.0.0-alpha.1");
+    version.set_build(["exp".parse().unwrap(), "sha".parse().unwrap()]);
+    assert_eq!(version.to_string(), "1.0.0-alpha.1+exp.sha");
+    assert_eq!(version, v("1.0.0-alpha.1+exp.sha"));
+}
+
+#[test]
+fn trailing_content() {
+    for (s, expected) in [
+        ("1.2.3 foo", " foo"),
+        ("1.2.3+b c", " c"),
+        ("1.2.3-rc.1, 1.2.4", ", 1.2.4"),
+    ] {
+        match s.parse::<Version>() {
+            Err(InvalidVersion::TrailingContent { rest }) => assert_eq!(rest, expected),
+            other => panic!("{s:?} parsed as {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn trailing_content_does_not_hide_other_errors() {
+    // The rest could be part of the version, so the specific error is reported
+    assert!(matches!(
+        "1.2.3-rc..1".parse::<Version>(),
+        Err(InvalidVersion::InvalidPureVersion { .. })
+    ));
+    assert!(matches!(
+        "1.2.3+b..c".parse::<Version>(),
+        Err(InvalidVersion::InvalidBuildMetadata { .. })
+    ));
+}
+
+#[test]
+fn clean_versions_still_parse() {
+    for s in ["1.2.3", "1.2.3-rc.1", "1.2.3+b.c", "1.2.3-rc.1+b-1"] {
+        assert_eq!(v(s).to_string(), s);
+    }
+}
+
+#[test]
+fn display_padding() {
+    let version = v("1.2.3+b");
+    assert_eq!(format!("{version:>12}"), "     1.2.3+b");
+    assert_eq!(format!("{version:<12}|"), "1.2.3+b     |");
+    assert_eq!(format!("{version:^11}"), "  1.2.3+b  ");
+    assert_eq!(format!("{version:*>9}"), "**1.2.3+b");
+    // Widths smaller than the version do not truncate it
+    assert_eq!(format!("{version:3}"), "1.2.3+b");
+    assert_eq!(format!("{:>8}", version.pure), "   1.2.3");
+    assert_eq!(format!("{version}"), "1.2.3+b");
+}
+
+#[test]
+fn minimal_bump_from() {
+    let req = Ranges::from_str(">=1.2.0 && <1.4.0 || >=2.1.0 && <3.0.0").unwrap();
+    let bump = |current: &str| {
+        req.minimal_bump_from(&v(current))
+            .map(|version| version.to_string())
+    };
+
+    // Below the satisfying region
+    assert_eq!(bump("0.9.7").as_deref(), Some("1.2.0"));
+    // Inside, the version itself with its metadata
+    assert_eq!(bump("1.3.5+build").as_deref(), Some("1.3.5+build"));
+    // In the hole between the intervals
+    assert_eq!(bump("1.4.0").as_deref(), Some("2.1.0"));
+    // Above everything
+    assert_eq!(bump("3.0.0"), None);
+}
+
+#[test]
+fn minimal_bump_from_skips_implicit_prereleases() {
+    let req = Ranges::from_str(">1.2.3").unwrap();
+    assert_eq!(req.minimal_bump_from(&v("1.0.0")).unwrap(), v("1.2.4"));
+    // A prerelease inside the range is not matched, but its release is
+    assert_eq!(req.minimal_bump_from(&v("1.5.0-beta")).unwrap(), v("1.5.0"));
+
+    let req = Ranges::from_str(">=1.5.0-alpha && <2.0.0").unwrap();
+    assert_eq!(
+        req.minimal_bump_from(&v("1.0.0")).unwrap(),
+        v("1.5.0-alpha")
+    );
+}
+
+#[test]
+fn parts_roundtrip() {
+    for version in [
+        "0.0.0",
+        "1.2.3-rc.1+build.5",
+        "1.0.0+exp.sha.5114f85",
+        "2.0.0-alpha.0.beta",
+    ] {
+        let version = v(version);
+        let (major, minor, patch, pre, build) = version.clone().into_parts();
+        assert_eq!(
+            Version::from_parts(major, minor, patch, pre, build),
+            version
+        );
+    }
+
+    let max = Version::from_parts(1, 2, u64::MAX, vec![], vec!["b".parse().unwrap()]);
+    assert_eq!(max.to_string(), format!("1.2.{}+b", u64::MAX));
+    assert_eq!(max, v(&max.to_string()));
+    assert_eq!(
+        max.into_parts(),
+        (1, 2, u64::MAX, vec![], vec!["b".parse().unwrap()])
+    );
+}
+
+#[test]
+fn prerelease_series() {
+    let series = |start: &str, n| {
+        v(start)
+            .prerelease_series()
+            .take(n)
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        series("1.0.0-alpha.1+build", 4),
+        [
+            "1.0.0-alpha.1+build",
+            "1.0.0-alpha.2",
+            "1.0.0-alpha.3",
+            "1.0.0-alpha.4"
+        ]
+    );
+    assert_eq!(
+        series("2.1.0-rc", 3),
+        ["2.1.0-rc", "2.1.0-rc.1", "2.1.0-rc.2"]
+    );
+    assert_eq!(
+        series("1.0.0-beta.9", 3),
+        ["1.0.0-beta.9", "1.0.0-beta.10", "1.0.0-beta.11"]
+    );
+    assert_eq!(series("1.0.0", 3), ["1.0.0"]);
+
+    // Every version of the series precedes the release
+    let release = v("1.0.0");
+    assert!(
+        v("1.0.0-alpha.1")
+            .prerelease_series()
+            .take(100)
+            .is_sorted_by(|a, b| a.pure < b.pure && b.pure < release.pure)
+    );
+}
+
+#[test]
+fn missing_components() {
+    let lenient = ParseOptions {
+        allow_missing_components: true,
+        ..Default::default()
+    };
+
+    for s in ["1", "1.2", "1.2-rc.1", "1+build"] {
+        assert!(s.parse::<Version>().is_err(), "{s} should be strict");
+    }
+    assert_eq!(
+        Version::parse_with_options("1", &lenient).unwrap(),
+        v("1.0.0")
+    );
+    assert_eq!(
+        Version::parse_with_options("1.2", &lenient).unwrap(),
+        v("1.2.0")
+    );
+    assert_eq!(
+        Version::parse_with_options("1.2-rc.1+build", &lenient).unwrap(),
+        v("1.2.0-rc.1+build")
+    );
+    assert_eq!(
+        Version::parse_with_options("1.2.3", &lenient).unwrap(),
+        v("1.2.3")
+    );
+    for s in ["", "1.", ".2", "1..3", "1.2.3.4"] {
+        assert!(Version::parse_with_options(s, &lenient).is_err(), "{s}");
+    }
+}
+
+#[test]
+fn canonical_eq() {
+    let versions = ["1.0.0+a", "1.0.0+b", "1.0.0", "1.0.0+a"].map(v);
+    for a in &versions {
+        for b in &versions {
+            assert_eq!(a.pure, b.pure);
+            assert_eq!(a.canonical_eq(b), a.to_string() == b.to_string());
+            assert_eq!(a.canonical_eq(b), a == b);
+        }
+    }
+    assert!(versions[0].canonical_eq(&versions[3]));
+    assert!(!versions[0].canonical_eq(&versions[1]));
+    assert!(!versions[0].canonical_eq(&versions[2]));
+}
+
+#[test]
+fn satisfies_str() {
+    let version = v("1.4.1+build.3");
+    assert_eq!(version.satisfies_str(">=1.2.0 && <2.0.0"), Ok(true));
+    assert_eq!(version.satisfies_str("<1.4.1 || >=2.0.0"), Ok(false));
+    assert_eq!(v("1.5.0-beta").satisfies_str(">=1.2.0"), Ok(false));
+    assert_eq!(v("1.5.0-beta").satisfies_str(">=1.5.0-alpha"), Ok(true));
+    assert!(
+        !version
+            .satisfies_str(">=1.2.0 && && <2")
+            .unwrap_err()
+            .is_empty()
+    );
+}
+
+#[test]
+fn bump_kind() {
+    use crate::version::pure::BumpKind;
+
+    for (old, new, kind) in [
+        ("1.2.3", "2.0.0", BumpKind::Major),
+        ("1.2.3", "1.3.0", BumpKind::Minor),
+        ("1.2.3", "1.2.4", BumpKind::Patch),
+        ("1.2.3-rc.1", "1.2.3", BumpKind::PrereleaseOnly),
+        ("1.2.3-rc.1", "1.2.3-rc.2", BumpKind::PrereleaseOnly),
+        ("1.2.3-rc.1", "1.2.4", BumpKind::Patch),
+        ("1.2.3", "2.0.0-alpha", BumpKind::Major),
+        ("1.2.3+a", "1.2.3+b", BumpKind::BuildOnly),
+        ("1.2.3+a", "1.2.3+a", BumpKind::None),
+        ("1.2.3", "1.2.3-rc.1", BumpKind::Downgrade),
+        ("2.0.0", "1.9.9", BumpKind::Downgrade),
+    ] {
+        assert_eq!(v(old).bump_kind(&v(new)), kind, "{old} -> {new}");
+    }
+}
+
+#[test]
+fn apply_bump_round_trips() {
+    use crate::version::pure::BumpKind;
+
+    for (old, new) in [
+        ("1.2.3", "2.0.0"),
+        ("1.2.3", "1.3.0"),
+        ("1.2.3", "1.2.4"),
+        ("1.2.3-rc.1", "1.2.4"),
+        ("0.9.0-beta", "0.10.0"),
+        ("1.2.3-rc.1", "1.2.3-rc.2"),
+        ("1.2.3-alpha", "1.2.3"),
+    ] {
+        let (old, new) = (v(old), v(new));
+        let kind = old.bump_kind(&new);
+        let bumped = old.apply_bump(kind);
+        assert_eq!(
+            old.bump_kind(&Version {
+                pure: bumped.clone(),
+                build: vec![],
+            }),
+            kind
+        );
+        if kind != BumpKind::PrereleaseOnly {
+            assert_eq!(bumped, new.pure);
+        } else {
+            // Same release line
+            assert!(bumped.diff(&new) <= crate::version::pure::VersionChange::Prerelease);
+        }
+    }
+    assert_eq!(
+        v("1.2.3").apply_bump(BumpKind::PrereleaseOnly),
+        v("1.2.3").pure
+    );
+    assert_eq!(v("1.2.3").apply_bump(BumpKind::Downgrade), v("1.2.3").pure);
+}
+
+#[test]
+fn parse_from_bytes() {
+    assert_eq!(
+        Version::try_from(&b"1.2.3-rc.1+build.5"[..]).unwrap(),
+        v("1.2.3-rc.1+build.5")
+    );
+    assert!(matches!(
+        Version::try_from(&b"1.2.x"[..]),
+        Err(InvalidVersion::InvalidPureVersion { .. })
+    ));
+}
+
+#[test]
+fn parse_from_non_ascii_bytes() {
+    // Invalid UTF-8
+    assert!(matches!(
+        Version::try_from(&b"1.2.3-\xff"[..]),
+        Err(InvalidVersion::NonAscii {
+            position: 6,
+            byte: 0xff
+        })
+    ));
+    // Valid UTF-8, but not ASCII
+    assert!(matches!(
+        Version::try_from("1.2.3-é".as_bytes()),
+        Err(InvalidVersion::NonAscii { position: 6, .. })
+    ));
+}
+
+#[test]
+fn releases_between() {
+    let between = |from: &str, to: &str, granularity| {
+        v(from)
+            .releases_between(&v(to), granularity)
+            .take(10)
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        between("1.0.0", "4.0.0", Granularity::Major),
+        ["1.0.0", "2.0.0", "3.0.0"]
+    );
+    assert_eq!(
+        between("1.0.5", "3.2.0", Granularity::Major),
+        ["1.0.5", "2.0.0", "3.0.0"]
+    );
+    assert_eq!(
+        between("1.0.0", "1.3.0", Granularity::Minor),
+        ["1.0.0", "1.1.0", "1.2.0"]
+    );
+    assert_eq!(
+        between("1.2.0", "1.2.3", Granularity::Patch),
+        ["1.2.0", "1.2.1", "1.2.2"]
+    );
+    // Prereleases and build metadata are dropped, the end is excluded
+    assert_eq!(
+        between("1.2.0-rc.1+b", "1.2.2-rc.1", Granularity::Patch),
+        ["1.2.0", "1.2.1"]
+    );
+    // Nothing between a version and itself, or an earlier one
+    assert!(between("1.3.0", "1.3.0", Granularity::Minor).is_empty());
+    assert!(between("2.0.0", "1.0.0", Granularity::Patch).is_empty());
+    // Coarser components are incremented when the finer ones cannot reach the end
+    assert_eq!(
+        between("1.0.5", "3.1.2", Granularity::Patch),
+        ["1.0.5", "2.0.0", "3.0.0", "3.1.0", "3.1.1"]
+    );
+    assert_eq!(
+        between("1.2.0", "3.0.0-rc.1", Granularity::Minor),
+        ["1.2.0", "2.0.0"]
+    );
+    let far: Vec<_> = v("1.0.0")
+        .releases_between(&v(&format!("{}.0.0", UInt::MAX)), Granularity::Patch)
+        .take(3)
+        .map(|v| v.to_string())
+        .collect();
+    assert_eq!(far, ["1.0.0", "2.0.0", "3.0.0"]);
+    // Stops before overflowing
+    assert_eq!(
+        between(
+            &format!("1.{}.0", UInt::MAX - 1),
+            &format!("1.{}.1", UInt::MAX),
+            Granularity::Minor
+        ),
+        [
+            format!("1.{}.0", UInt::MAX - 1),
+            format!("1.{}.0", UInt::MAX)
+        ]
+    );
+}
+
+#[test]
+fn with_core_keeps_prerelease_and_build() {
+    let rebased = v("1.2.0-rc.1+build.5").with_core(1, 3, 0);
+    assert_eq!(rebased, v("1.3.0-rc.1+build.5"));
+    assert_eq!(rebased.pre, v("1.2.0-rc.1").pre);
+    assert_eq!(rebased.build, v("1.2.0-rc.1+build.5").build);
+
+    let max = v("1.2.3-alpha").with_core(1, 2, UInt::MAX);
+    assert_eq!(max.to_string(), format!("1.2.{}-alpha", UInt::MAX));
+    assert_eq!(max.to_string().parse::<Version>().unwrap(), max);
+
+    assert_eq!(v("1.2.3").with_core(0, 0, 1), v("0.0.1"));
+}
+
+#[test]
+fn is_prerelease_of() {
+    let release = |s: &str| s.parse::<PureVersion>().unwrap();
+
+    assert!(v("1.2.0-rc.1").is_prerelease_of(&release("1.2.0")));
+    assert!(v("1.2.0-0+build").is_prerelease_of(&release("1.2.0")));
+    // Mismatched core
+    assert!(!v("1.2.0-rc.1").is_prerelease_of(&release("1.2.1")));
+    assert!(!v("1.3.0-rc.1").is_prerelease_of(&release("1.2.0")));
+    // Not a prerelease
+    assert!(!v("1.2.0").is_prerelease_of(&release("1.2.0")));
+    assert!(!v("1.2.0+build").is_prerelease_of(&release("1.2.0")));
+    // The other side is not a release
+    assert!(!v("1.2.0-rc.1").is_prerelease_of(&release("1.2.0-rc.2")));
+}
+
+#[test]
+fn alternate_separators() {
+    let lenient = ParseOptions {
+        allow_alternate_separators: true,
+        ..Default::default()
+    };
+    let parse = |s| Version::parse_with_options(s, &lenient).unwrap();
+
+    assert!("1.2.3_rc1".parse::<Version>().is_err());
+    assert_eq!(parse("1.2.3_rc1"), v("1.2.3-rc1"));
+    assert_eq!(parse("1.2.3~rc1"), v("1.2.3-rc1"));
+    assert_eq!(parse("1.2.3_rc_1"), v("1.2.3-rc.1"));
+    assert_eq!(parse("1.2.3-rc~1+build_5"), v("1.2.3-rc.1+build.5"));
+    assert_eq!(parse("1.2.3"), v("1.2.3"));
+    // Only the separators are replaced
+    assert!(Version::parse_with_options("1_2_3", &lenient).is_err());
+    assert!(Version::parse_with_options("1.2.3__rc", &lenient).is_err());
+
+    let all = ParseOptions {
+        allow_alternate_separators: true,
+        allow_missing_components: true,
+        allow_leading_zeros: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        Version::parse_with_options("01.2_rc1", &all).unwrap(),
+        v("1.2.0-rc1")
+    );
+}
+
+#[test]
+fn filter_candidates() {
+    let candidates: Vec<Version> = [
+        "2.0.0",
+        "1.2.0+build.1",
+        "1.0.0",
+        "1.3.0-beta",
+        "1.3.0-rc.1",
+        "1.9.9",
+        "1.2.0",
+    ]
+    .into_iter()
+    .map(v)
+    .collect();
+
+    let req = Ranges::from_str(">=1.2.0 && <2.0.0").unwrap();
+    let matching: Vec<_> = req
+        .filter(&candidates)
+        .into_iter()
+        .map(|v| v.to_string())
+        .collect();
+    assert_eq!(matching, ["1.2.0+build.1", "1.9.9", "1.2.0"]);
+
+    // Prereleases are kept when explicitly allowed
+    let req = Ranges::from_str(">=1.3.0-rc.1 && <2.0.0").unwrap();
+    let matching: Vec<_> = req
+        .filter(&candidates)
+        .into_iter()
+        .map(|v| v.to_string())
+        .collect();
+    assert_eq!(matching, ["1.3.0-rc.1", "1.9.9"]);
+
+    assert!(Ranges::EMPTY.filter(&candidates).is_empty());
+    assert!(req.filter(&[]).is_empty());
+}
+
+#[test]
+fn sortable_bytes() {
+    let versions = [
+        "0.0.0",
+        "0.0.1",
+        "0.1.0",
+        "0.9.0",
+        "0.10.0",
+        "1.0.0-0",
+        "1.0.0-2",
+        "1.0.0-10",
+        "1.0.0-255",
+        "1.0.0-256",
+        "1.0.0-99999999999999999999999999",
+        "1.0.0-A",
+        "1.0.0-alpha",
+        "1.0.0-alpha.1",
+        "1.0.0-alpha.beta",
+        "1.0.0-alpha-1",
+        "1.0.0-alphabet",
+        "1.0.0-beta",
+        "1.0.0-beta.2",
+        "1.0.0-beta.11",
+        "1.0.0-rc.1",
+        "1.0.0",
+        "1.0.0+a",
+        "1.0.0+a.b",
+        "1.0.0+b",
+        "1.0.1-0",
+        "1.0.1",
+        "256.0.0",
+        "18446744073709551615.0.0",
+    ]
+    .map(v);
+
+    let mut shuffled = versions.to_vec();
+    shuffled.reverse();
+    shuffled.swap(3, 17);
+    shuffled.sort_by_cached_key(Version::to_sortable_bytes);
+    assert_eq!(shuffled, versions);
+
+    for pair in versions.windows(2) {
+        assert_eq!(
+            pair[0].cmp_including_build(&pair[1]),
+            Ordering::Less,
+            "{} should precede {}",
+            pair[0],
+            pair[1]
+        );
+        assert!(pair[0].to_sortable_bytes() < pair[1].to_sortable_bytes());
+    }
+}
+
+#[test]
+fn pinned_to() {
+    let pin = Ranges::pinned_to(&v("1.2.3+build.5"));
+    assert_eq!(pin, Ranges::exact(v("1.2.3").pure));
+    assert!(pin.is_exactly(&v("1.2.3").pure));
+    assert!(v("1.2.3+other").matches(&pin));
+    assert!(!v("1.2.4").matches(&pin));
+    assert!(!v("1.2.3-rc.1").matches(&pin));
+}
+
+#[test]
+fn stray_whitespace() {
+    for (s, expected) in [
+        ("1 .2.3", 1),
+        ("1. 2.3", 2),
+        ("1.2.3 - rc.1", 5),
+        ("1.2.3 -rc.1", 5),
+        ("1.2.3- rc.1", 6),
+        ("1.2.3-rc .1", 8),
+        ("1.2.3-rc. 1", 9),
+        ("1.2.3\t+build", 5),
+        ("1.2.3+build .5", 11),
+    ] {
+        match s.parse::<Version>() {
+            Err(InvalidVersion::InvalidPureVersion {
+                source: InvalidPureVersion::UnexpectedWhitespace { position },
+            }) => assert_eq!(position, expected, "{s:?}"),
+            other => panic!("{s:?} parsed as {other:?}"),
+        }
+    }
+    assert_eq!(
+        "1.2.3 - rc.1".parse::<Version>().unwrap_err().to_string(),
+        "Unexpected whitespace in version at position 5"
+    );
+}
+
+#[test]
+fn signed_components_are_errors() {
+    for (s, expected) in [("+1.2.3", "+1"), ("1.+2.3", "+2"), ("1.2.+3", "+3")] {
+        match s.parse::<Version>() {
+            Err(InvalidVersion::InvalidPureVersion {
+                source: InvalidPureVersion::NonDigitNumericPart { value, .. },
+            }) => assert_eq!(value, expected, "{s:?}"),
+            other => panic!("{s:?} parsed as {other:?}"),
+        }
+    }
+    assert!(matches!(
+        "1.2.3+".parse::<Version>(),
+        Err(InvalidVersion::InvalidBuildMetadata { .. })
+    ));
+    assert!(matches!(
+        "1.2.3-".parse::<Version>(),
+        Err(InvalidVersion::InvalidPureVersion { .. })
+    ));
+}
+
+#[test]
+fn error_span() {
+    for (input, expected) in [
+        ("1.02.3", "02"),
+        ("+1.2.3", "+1"),
+        ("1.2.+3-rc", "+3"),
+        ("1.2", ""),
+        ("1.2.3.4-rc", "4"),
+        ("1.2.3-rc.01", "rc.01"),
+        ("1.2.3+bü", "ü"),
+        ("1.2.3+", ""),
+        ("1.2.3 foo", " foo"),
+        ("1.2.3 - rc.1", " "),
+        ("1.2.3-", ""),
+    ] {
+        let err = input.parse::<Version>().unwrap_err();
+        assert_eq!(&input[err.span(input)], expected, "{input:?}: {err:?}");
+    }
+    assert_eq!("1.2".parse::<Version>().unwrap_err().span("1.2"), 3..3);
+}
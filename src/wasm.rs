@@ -0,0 +1,89 @@
+//! Bindings for JavaScript, through [`wasm_bindgen`]
+//!
+//! Errors are thrown as JavaScript `Error`s, with the `start` and `end` properties
+//! locating the invalid part of the input. They are indices of UTF-16 code units,
+//! so they can be used directly on JavaScript strings.
+
+use std::{error::Error, ops::Range};
+
+use js_sys::{Array, BigInt, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    range::Ranges,
+    version::{Version, pure::PureVersion},
+};
+
+/// Parse a version into an object with the `major`, `minor` and `patch` bigints,
+/// and the `pre` and `build` arrays of identifiers
+#[wasm_bindgen]
+pub fn parse_version(s: &str) -> Result<JsValue, JsValue> {
+    let version = version(s)?;
+    let object = Object::new();
+    set(&object, "major", &BigInt::from(version.pure.major).into());
+    set(&object, "minor", &BigInt::from(version.pure.minor).into());
+    set(&object, "patch", &BigInt::from(version.pure.patch).into());
+    set(&object, "pre", &identifiers(version.pure.pre.iter()).into());
+    set(&object, "build", &identifiers(version.build.iter()).into());
+    Ok(object.into())
+}
+
+/// Check if the version matches the requirement, with the default prerelease policy
+#[wasm_bindgen]
+pub fn version_matches(version: &str, req: &str) -> Result<bool, JsValue> {
+    Ok(self::version(version)?.matches(&requirement(req)?))
+}
+
+/// Parse a requirement and format it in the canonical form
+#[wasm_bindgen]
+pub fn normalize_req(req: &str) -> Result<String, JsValue> {
+    Ok(requirement(req)?.to_string())
+}
+
+/// Compare two versions by precedence, returning `-1`, `0` or `1`
+///
+/// Build metadata is ignored, as it does not contribute to the precedence.
+#[wasm_bindgen]
+pub fn compare(a: &str, b: &str) -> Result<i32, JsValue> {
+    Ok(version(a)?.pure.cmp(&version(b)?.pure) as i32)
+}
+
+fn version(s: &str) -> Result<Version, JsValue> {
+    s.parse().map_err(|err: crate::version::InvalidVersion| {
+        let mut message = err.to_string();
+        let mut source = err.source();
+        while let Some(cause) = source {
+            message.push_str(&format!(": {cause}"));
+            source = cause.source();
+        }
+        error(&message, s, err.span(s))
+    })
+}
+
+fn requirement(s: &str) -> Result<Ranges<PureVersion>, JsValue> {
+    Ranges::from_str(s).map_err(|errors| {
+        let first = errors
+            .first()
+            .expect("Failed parses have at least one error");
+        error(&first.to_string(), s, first.span().into_range())
+    })
+}
+
+/// Build a JavaScript error locating the bytes in `span` of `input`
+fn error(message: &str, input: &str, span: Range<usize>) -> JsValue {
+    let utf16_index = |idx: usize| input[..idx].encode_utf16().count() as u32;
+    let error = js_sys::Error::new(message);
+    set(&error, "start", &utf16_index(span.start).into());
+    set(&error, "end", &utf16_index(span.end).into());
+    error.into()
+}
+
+fn identifiers<T: ToString>(identifiers: impl Iterator<Item = T>) -> Array {
+    identifiers
+        .map(|identifier| JsValue::from(identifier.to_string()))
+        .collect()
+}
+
+fn set(target: &JsValue, key: &str, value: &JsValue) {
+    Reflect::set(target, &key.into(), value).expect("Setting a property of a plain object");
+}
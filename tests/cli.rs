@@ -0,0 +1,169 @@
+//! Drive the `areq` binary
+#![cfg(feature = "cli")]
+
+use assert_cmd::Command;
+
+fn areq() -> Command {
+    Command::cargo_bin("areq").unwrap()
+}
+
+const TAGS: &str = "\
+1.2.0
+2.0.0
+1.4.2
+1.5.0-beta.1
+0.9.3
+1.4.10
+";
+
+#[test]
+fn check() {
+    areq()
+        .args(["check", "1.4.2", ">=1.2.0, <2.0.0"])
+        .assert()
+        .success();
+    areq()
+        .args(["check", "2.0.0", ">=1.2.0, <2.0.0"])
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn check_prereleases() {
+    areq()
+        .args(["check", "1.5.0-beta.1", ">=1.2.0"])
+        .assert()
+        .code(1);
+    areq()
+        .args([
+            "check",
+            "--prereleases",
+            "include",
+            "1.5.0-beta.1",
+            ">=1.2.0",
+        ])
+        .assert()
+        .success();
+}
+
+#[test]
+fn check_invalid_input() {
+    areq().args(["check", "1.4", ">=1.2.0"]).assert().code(2);
+    areq().args(["check", "1.4.0", ">=1.2"]).assert().code(2);
+}
+
+#[test]
+fn check_lenient() {
+    areq()
+        .args(["check", "--lenient", "v1.4.02", "^1.2"])
+        .assert()
+        .success();
+    // A single prefix is stripped, like the sorting helpers do
+    areq()
+        .args(["check", "--lenient", "vV1.4.2", "^1.2"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn check_lenient_cargo_requirement() {
+    areq()
+        .args(["check", "--lenient", "1.4.2", ">=1.2,<2"])
+        .assert()
+        .success();
+    areq()
+        .args(["check", "--lenient", "2.0.0", ">=1.2,<2"])
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn check_signed_version_is_an_error() {
+    areq().args(["check", "+1.2.3", ">=1.2.0"]).assert().code(2);
+    areq()
+        .args(["check", "--lenient", "+1.2.3", ">=1.2.0"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn filter() {
+    areq()
+        .args(["filter", ">=1.2.0 && <2.0.0"])
+        .write_stdin(TAGS)
+        .assert()
+        .success()
+        .stdout("1.2.0\n1.4.2\n1.4.10\n");
+}
+
+#[test]
+fn filter_strict_rejects_invalid_lines() {
+    areq()
+        .args(["filter", ">=1.2.0"])
+        .write_stdin("1.2.0\nlatest\n")
+        .assert()
+        .code(2)
+        .stdout("");
+}
+
+#[test]
+fn filter_lenient_skips_invalid_lines() {
+    areq()
+        .args(["filter", "--lenient", "^1"])
+        .write_stdin("v1.2.0\nlatest\nv2.0.0\n1.3.0\n")
+        .assert()
+        .success()
+        .stdout("v1.2.0\n1.3.0\n");
+}
+
+#[test]
+fn filter_lenient_skips_signed_versions() {
+    areq()
+        .args(["filter", "--lenient", "^1"])
+        .write_stdin("+1.2.3\n1.3.0\n")
+        .assert()
+        .success()
+        .stdout("1.3.0\n");
+}
+
+#[test]
+fn sort() {
+    areq()
+        .arg("sort")
+        .write_stdin(TAGS)
+        .assert()
+        .success()
+        .stdout("0.9.3\n1.2.0\n1.4.2\n1.4.10\n1.5.0-beta.1\n2.0.0\n");
+    areq()
+        .args(["sort", "--reverse"])
+        .write_stdin(TAGS)
+        .assert()
+        .success()
+        .stdout("2.0.0\n1.5.0-beta.1\n1.4.10\n1.4.2\n1.2.0\n0.9.3\n");
+}
+
+#[test]
+fn max() {
+    areq()
+        .args(["max", "<2.0.0"])
+        .write_stdin(TAGS)
+        .assert()
+        .success()
+        .stdout("1.4.10\n");
+    areq()
+        .args(["max", "--prereleases", "include", "<2.0.0"])
+        .write_stdin(TAGS)
+        .assert()
+        .success()
+        .stdout("1.5.0-beta.1\n");
+}
+
+#[test]
+fn max_without_matches() {
+    areq()
+        .args(["max", ">=3.0.0"])
+        .write_stdin(TAGS)
+        .assert()
+        .code(1)
+        .stdout("");
+}
@@ -0,0 +1,102 @@
+//! Embed the version of this package at compile time
+
+use std::{fs, path::Path, process::Output};
+
+use areq::{range::Ranges, version::Version};
+
+const SELF: Version = areq::crate_version!();
+const IS_ZERO_MAJOR: bool = areq::crate_version_str_matches!("<1.0.0");
+
+#[test]
+fn embeds_the_package_version() {
+    assert_eq!(SELF, env!("CARGO_PKG_VERSION").parse::<Version>().unwrap());
+    assert_eq!(SELF, areq::crate_version!());
+}
+
+#[test]
+fn matches_at_compile_time() {
+    let ranges = Ranges::from_str("<1.0.0").unwrap();
+    assert_eq!(IS_ZERO_MAJOR, SELF.matches(&ranges));
+    const { assert!(areq::crate_version_str_matches!("*")) };
+}
+
+areq::assert_version_req!("<100.0.0");
+areq::assert_version_req!(
+    env!("CARGO_PKG_VERSION"),
+    ">=0.0.0 && <100.0.0 || ==999.0.0"
+);
+areq::assert_version_req!("0.2.5", ">=0.2.0, <0.3.0",);
+
+#[test]
+fn asserts_at_compile_time() {
+    // Also usable inside functions
+    areq::assert_version_req!("1.2.3", "!=1.2.4");
+}
+
+/// Build and run the fixture crate with the given package version
+fn run_fixture(version: &str) -> Output {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let project = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("crate_version")
+        .join(version);
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("Cargo.toml"),
+        format!(
+            r#"[package]
+name = "crate-version-fixture"
+version = "{version}"
+edition = "2024"
+
+[[bin]]
+name = "fixture"
+path = {main:?}
+
+[dependencies]
+areq = {{ path = {areq:?} }}
+
+[workspace]
+"#,
+            main = manifest_dir.join("tests/fixtures/crate_version/main.rs"),
+            areq = manifest_dir,
+        ),
+    )
+    .unwrap();
+    // Reuse the resolved dependencies, if any
+    if let Ok(lock) = fs::read(manifest_dir.join("Cargo.lock")) {
+        fs::write(project.join("Cargo.lock"), lock).unwrap();
+    }
+
+    std::process::Command::new(env!("CARGO"))
+        .args(["run", "--quiet", "--manifest-path"])
+        .arg(project.join("Cargo.toml"))
+        .env(
+            "CARGO_TARGET_DIR",
+            Path::new(env!("CARGO_TARGET_TMPDIR")).join("crate_version/target"),
+        )
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn fixture_with_release_version() {
+    let output = run_fixture("1.2.3");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1.2.3 false\n");
+}
+
+#[test]
+fn fixture_with_prerelease_version() {
+    let output = run_fixture("0.4.0-rc.1");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr
+            .contains("Only release versions, with no prerelease or build metadata, are supported"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,8 @@
+//! Print the version embedded by `crate_version!`, built by `tests/crate_version.rs`
+
+const SELF: areq::version::Version = areq::crate_version!();
+const IS_ZERO_MAJOR: bool = areq::crate_version_str_matches!("<1.0.0");
+
+fn main() {
+    println!("{SELF} {IS_ZERO_MAJOR}");
+}
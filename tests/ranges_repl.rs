@@ -0,0 +1,62 @@
+//! Drive the REPL of the `ranges` example
+
+#[path = "../examples/ranges/session.rs"]
+mod session;
+
+fn run(input: &str) -> String {
+    let mut output = vec![];
+    session::run(input.as_bytes(), &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn parse() {
+    assert_eq!(run("parse >=1.0.0 && <2.0.0\n"), "_ = >=1.0.0 && <2.0.0\n");
+    // Lines without a command are parsed
+    assert_eq!(run("==1.2.3\n"), "_ = ==1.2.3\n");
+}
+
+#[test]
+fn contains() {
+    assert_eq!(
+        run("contains >=1.0.0 && <2.0.0 ; 1.5.0\ncontains >=1.0.0 ; 0.9.0\n"),
+        "true\nfalse\n"
+    );
+}
+
+#[test]
+fn set_operations_on_slots() {
+    let output = run("\
+parse a = >=1.0.0
+parse b = <2.0.0
+and $a ; $b
+contains $_ ; 1.2.3
+not $_
+or $a ; ==0.1.0
+show
+");
+    assert_eq!(
+        output,
+        "\
+a = >=1.0.0
+b = <2.0.0
+_ = >=1.0.0 && <2.0.0
+true
+_ = <1.0.0 || >=2.0.0
+_ = ==0.1.0 || >=1.0.0
+constraints: ==0.1.0 || >=1.0.0
+intervals:   [0.1.0, 0.1.1-0) u [1.0.0, +inf)
+"
+    );
+}
+
+#[test]
+fn errors() {
+    let output = run("show nothing\nand >=1.0.0\ncontains >=1.0.0 ; one\nparse >=x\n");
+    let lines: Vec<_> = output.lines().collect();
+    assert_eq!(lines[0], "Unknown slot `nothing`");
+    assert_eq!(lines[1], "`and` needs two arguments separated by `;`");
+    assert!(lines[2].starts_with("Invalid version"));
+    assert_eq!(lines[3], "Invalid ranges");
+    assert!(lines[4].starts_with("  - "));
+}
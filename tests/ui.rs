@@ -0,0 +1,6 @@
+//! Compile errors of the macros
+
+#[test]
+fn assert_version_req() {
+    trybuild::TestCases::new().compile_fail("tests/ui/assert_version_req/*.rs");
+}
@@ -0,0 +1,3 @@
+areq::assert_version_req!("1.0.0", ">=0.2.0, <0.3.0");
+
+fn main() {}
@@ -0,0 +1,3 @@
+areq::assert_version_req!("0.2.5-rc.1", ">=0.2.0");
+
+fn main() {}
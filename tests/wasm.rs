@@ -0,0 +1,74 @@
+//! Run with `wasm-pack test --node -- --features wasm`
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use areq::wasm::{compare, normalize_req, parse_version, version_matches};
+use js_sys::{BigInt, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn get(target: &JsValue, key: &str) -> JsValue {
+    Reflect::get(target, &key.into()).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn parse_version_fields() {
+    let version = parse_version("1.2.3-rc.1+build.5").unwrap();
+    assert_eq!(get(&version, "major"), JsValue::from(BigInt::from(1u64)));
+    assert_eq!(get(&version, "minor"), JsValue::from(BigInt::from(2u64)));
+    assert_eq!(get(&version, "patch"), JsValue::from(BigInt::from(3u64)));
+    let pre: Vec<_> = js_sys::Array::from(&get(&version, "pre")).iter().collect();
+    assert_eq!(pre, [JsValue::from("rc"), JsValue::from("1")]);
+    let build: Vec<_> = js_sys::Array::from(&get(&version, "build"))
+        .iter()
+        .collect();
+    assert_eq!(build, [JsValue::from("build"), JsValue::from("5")]);
+}
+
+#[wasm_bindgen_test]
+fn parse_version_error() {
+    let error = parse_version("1.2").unwrap_err();
+    assert!(error.is_instance_of::<js_sys::Error>());
+    // The missing patch is located at the end
+    assert_eq!(get(&error, "start").as_f64(), Some(3.0));
+    assert_eq!(get(&error, "end").as_f64(), Some(3.0));
+
+    let error = parse_version("1.02.3").unwrap_err();
+    assert_eq!(get(&error, "start").as_f64(), Some(2.0));
+    assert_eq!(get(&error, "end").as_f64(), Some(4.0));
+
+    // Signed components are errors, not traps
+    let error = parse_version("+1.2.3").unwrap_err();
+    assert_eq!(get(&error, "start").as_f64(), Some(0.0));
+    assert_eq!(get(&error, "end").as_f64(), Some(2.0));
+}
+
+#[wasm_bindgen_test]
+fn matches() {
+    assert!(version_matches("1.4.2", ">=1.2.0 && <2.0.0").unwrap());
+    assert!(!version_matches("2.0.0", ">=1.2.0 && <2.0.0").unwrap());
+    assert!(!version_matches("1.5.0-beta", ">=1.2.0").unwrap());
+}
+
+#[wasm_bindgen_test]
+fn normalize() {
+    assert_eq!(
+        normalize_req("<2.0.0 && >=1.0.0 || ==1.5.0").unwrap(),
+        ">=1.0.0 && <2.0.0"
+    );
+}
+
+#[wasm_bindgen_test]
+fn requirement_error_span() {
+    let error = normalize_req(">=1.0.0 && <2.x").unwrap_err();
+    assert!(error.is_instance_of::<js_sys::Error>());
+    let start = get(&error, "start").as_f64().unwrap();
+    let end = get(&error, "end").as_f64().unwrap();
+    assert!(start >= 11.0 && end <= 15.0);
+}
+
+#[wasm_bindgen_test]
+fn compare_versions() {
+    assert_eq!(compare("1.2.3", "1.10.0").unwrap(), -1);
+    assert_eq!(compare("1.2.3+a", "1.2.3+b").unwrap(), 0);
+    assert_eq!(compare("2.0.0", "2.0.0-rc.1").unwrap(), 1);
+}